@@ -0,0 +1,34 @@
+//! Detection time per window for each algorithm on synthetic plucks.
+//! Run with `cargo bench`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use guitar_tuner::{signal, Tuner};
+
+const SAMPLE_RATE: u32 = 44_100;
+const WINDOW: usize = 4096;
+const MEASURE: Duration = Duration::from_secs(1);
+
+fn main() {
+    for algorithm in ["fft", "yin"] {
+        for (name, freq) in [("E1", 41.20), ("E2", 82.41), ("A4", 440.0), ("E6", 1318.51)] {
+            let samples = signal::pluck(freq, 8, 1.5, SAMPLE_RATE, WINDOW);
+            let mut tuner = Tuner::new(SAMPLE_RATE);
+            tuner.set_algorithm(algorithm).unwrap();
+
+            // Warm up, then run for about `MEASURE`.
+            for _ in 0..10 {
+                black_box(tuner.detect_frequency(black_box(&samples)));
+            }
+            let started = Instant::now();
+            let mut runs = 0u32;
+            while started.elapsed() < MEASURE {
+                black_box(tuner.detect_frequency(black_box(&samples)));
+                runs += 1;
+            }
+            let per_run = started.elapsed() / runs;
+            println!("{:<4} {:<3} {:>9.1} µs/window", algorithm, name, per_run.as_secs_f64() * 1e6);
+        }
+    }
+}
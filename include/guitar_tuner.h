@@ -0,0 +1,54 @@
+/* C API for the guitar-tuner pitch detector. Build with `cargo build --release --features ffi`
+ * and link against target/release/libguitar_tuner.{so,dylib,a} (guitar_tuner.dll/.lib on Windows). */
+#ifndef GUITAR_TUNER_H
+#define GUITAR_TUNER_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct GtTuner GtTuner;
+
+typedef struct {
+    char name[4]; /* NUL-terminated, e.g. "A#" */
+    int octave;
+    float cents;
+} GtNote;
+
+/* Memory owned by the library, mainly for JavaScript callers of the wasm build. */
+void *gt_alloc(size_t size);
+void gt_free(void *ptr, size_t size);
+
+/* Returns NULL if sample_rate is 0. */
+GtTuner *gt_tuner_new(uint32_t sample_rate);
+void gt_tuner_free(GtTuner *tuner);
+
+/* Selects "fft" (default) or "yin"; returns 0 on success, -1 on NULL or an unknown name. */
+int gt_tuner_set_algorithm(GtTuner *tuner, const char *name);
+
+/* Detects on exactly these samples (at least 4096), bypassing the feed buffer; returns Hz or 0. */
+float gt_tuner_detect(GtTuner *tuner, const float *samples, size_t len);
+
+/* Appends mono samples and detects on the newest window due; returns 1 when a detection ran,
+   0 if more samples are needed, -1 on NULL. */
+int gt_tuner_feed(GtTuner *tuner, const float *samples, size_t len);
+
+/* Last detected frequency in Hz, or 0 if none. */
+float gt_tuner_pitch(const GtTuner *tuner);
+/* Share of spectral energy on the detected pitch's harmonics, 0-1. */
+float gt_tuner_confidence(const GtTuner *tuner);
+
+/* Nearest note to freq; returns 0 on success, -1 on NULL or non-positive frequencies. */
+int gt_tuner_note(const GtTuner *tuner, float freq, float a4_freq, GtNote *out);
+
+/* Frequency of a note name ("A", "C#", ...) in an octave; 0 for an unknown name. */
+float gt_note_frequency(const char *name, int octave, float a4_freq);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
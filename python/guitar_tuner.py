@@ -0,0 +1,121 @@
+"""Python access to the guitar-tuner detection engine through its C API.
+
+Build the shared library first:
+
+    cargo build --release --features ffi
+
+The library is looked up in $GUITAR_TUNER_LIB, then in ../target/release
+next to this file.
+
+    import guitar_tuner
+    tuner = guitar_tuner.Tuner(48000, algorithm="yin")
+    freq = tuner.detect_frequency(samples)          # list, array('f'), or NumPy array
+    name, octave, cents = tuner.frequency_to_note(freq)
+"""
+
+import ctypes
+import os
+import sys
+
+__all__ = ["Tuner", "note_name_to_frequency"]
+
+
+class _Note(ctypes.Structure):
+    _fields_ = [("name", ctypes.c_char * 4), ("octave", ctypes.c_int), ("cents", ctypes.c_float)]
+
+
+def _library_path():
+    if os.environ.get("GUITAR_TUNER_LIB"):
+        return os.environ["GUITAR_TUNER_LIB"]
+    name = {"win32": "guitar_tuner.dll", "darwin": "libguitar_tuner.dylib"}.get(sys.platform, "libguitar_tuner.so")
+    return os.path.join(os.path.dirname(os.path.abspath(__file__)), "..", "target", "release", name)
+
+
+_lib = ctypes.CDLL(_library_path())
+_lib.gt_tuner_new.argtypes = [ctypes.c_uint32]
+_lib.gt_tuner_new.restype = ctypes.c_void_p
+_lib.gt_tuner_free.argtypes = [ctypes.c_void_p]
+_lib.gt_tuner_free.restype = None
+_lib.gt_tuner_set_algorithm.argtypes = [ctypes.c_void_p, ctypes.c_char_p]
+_lib.gt_tuner_set_algorithm.restype = ctypes.c_int
+_lib.gt_tuner_detect.argtypes = [ctypes.c_void_p, ctypes.POINTER(ctypes.c_float), ctypes.c_size_t]
+_lib.gt_tuner_detect.restype = ctypes.c_float
+_lib.gt_tuner_feed.argtypes = [ctypes.c_void_p, ctypes.POINTER(ctypes.c_float), ctypes.c_size_t]
+_lib.gt_tuner_feed.restype = ctypes.c_int
+_lib.gt_tuner_pitch.argtypes = [ctypes.c_void_p]
+_lib.gt_tuner_pitch.restype = ctypes.c_float
+_lib.gt_tuner_confidence.argtypes = [ctypes.c_void_p]
+_lib.gt_tuner_confidence.restype = ctypes.c_float
+_lib.gt_tuner_note.argtypes = [ctypes.c_void_p, ctypes.c_float, ctypes.c_float, ctypes.POINTER(_Note)]
+_lib.gt_tuner_note.restype = ctypes.c_int
+_lib.gt_note_frequency.argtypes = [ctypes.c_char_p, ctypes.c_int, ctypes.c_float]
+_lib.gt_note_frequency.restype = ctypes.c_float
+
+
+def _floats(samples):
+    """A ctypes float pointer and length for `samples`, keeping the buffer alive."""
+    try:
+        import numpy
+
+        array = numpy.ascontiguousarray(samples, dtype=numpy.float32)
+        return array, array.ctypes.data_as(ctypes.POINTER(ctypes.c_float)), len(array)
+    except ImportError:
+        array = (ctypes.c_float * len(samples))(*samples)
+        return array, array, len(samples)
+
+
+class Tuner:
+    """A detector for mono audio at `sample_rate` Hz, as used by the terminal app."""
+
+    def __init__(self, sample_rate, algorithm="fft"):
+        self._handle = _lib.gt_tuner_new(sample_rate)
+        if not self._handle:
+            raise ValueError("sample_rate must be positive")
+        if _lib.gt_tuner_set_algorithm(self._handle, algorithm.encode()) != 0:
+            _lib.gt_tuner_free(self._handle)
+            self._handle = None
+            raise ValueError("unknown algorithm %r (expected 'fft' or 'yin')" % algorithm)
+
+    def __del__(self):
+        if getattr(self, "_handle", None):
+            _lib.gt_tuner_free(self._handle)
+            self._handle = None
+
+    def detect_frequency(self, samples):
+        """Fundamental in Hz of the first 4096 samples, or None."""
+        keep, pointer, length = _floats(samples)
+        freq = _lib.gt_tuner_detect(self._handle, pointer, length)
+        del keep
+        return freq or None
+
+    def feed(self, samples):
+        """Streams samples in any chunk size; returns True when a detection ran."""
+        keep, pointer, length = _floats(samples)
+        ran = _lib.gt_tuner_feed(self._handle, pointer, length) == 1
+        del keep
+        return ran
+
+    @property
+    def pitch(self):
+        """The frequency from the last detection, or None."""
+        return _lib.gt_tuner_pitch(self._handle) or None
+
+    @property
+    def confidence(self):
+        """Share of spectral energy on the detected pitch's harmonics, 0-1."""
+        return _lib.gt_tuner_confidence(self._handle)
+
+    def frequency_to_note(self, frequency, a4_freq=440.0):
+        """(name, octave, cents) of the note nearest to `frequency`."""
+        note = _Note()
+        if _lib.gt_tuner_note(self._handle, frequency, a4_freq, ctypes.byref(note)) != 0:
+            raise ValueError("frequencies must be positive")
+        return note.name.decode(), note.octave, note.cents
+
+
+def note_name_to_frequency(name, octave, a4_freq=440.0):
+    """Frequency of a note such as ("A", 4); octaves start at C."""
+    freq = _lib.gt_note_frequency(name.encode(), octave, a4_freq)
+    if freq == 0:
+        raise ValueError("unknown note %r" % name)
+    return freq
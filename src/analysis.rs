@@ -0,0 +1,287 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{select, Receiver, Sender, TrySendError};
+use guitar_tuner::PitchDetector;
+
+use crate::config::Config;
+use crate::i18n::tr;
+use crate::replay::{Event, Recorder};
+use crate::tuner::{Partial, Register, Tuner};
+
+/// Results waiting for the UI. When the UI falls this far behind, new
+/// results are dropped until it catches up.
+const RESULTS: usize = 8;
+/// Normal-register window in fast mode, about 43 ms at 48 kHz, and how many
+/// windows overlap each sample.
+const FAST_WINDOW: usize = 2048;
+const FAST_OVERLAP: usize = 8;
+
+/// One detection, with everything the UI draws from it.
+pub struct Analysis {
+    /// Audio received since the previous result, for the waveform view.
+    pub samples: Vec<f32>,
+    pub freq: Option<f32>,
+    pub spectrum: Vec<f32>,
+    pub partials: Vec<Partial>,
+    /// Time spent in detection.
+    pub elapsed: Duration,
+}
+
+/// Whether analysis favours the last cent or a quick response.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub enum Mode {
+    #[default]
+    Accurate,
+    /// Short windows overlapping eight times, refined FFT peaks, and an
+    /// undamped needle, for touch-ups between songs.
+    Fast,
+}
+
+impl Mode {
+    const ALL: [Mode; 2] = [Mode::Accurate, Mode::Fast];
+
+    pub fn toggle(self) -> Self {
+        match self {
+            Mode::Accurate => Mode::Fast,
+            Mode::Fast => Mode::Accurate,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+
+    /// Name in `[tuner] mode`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Accurate => "accurate",
+            Mode::Fast => "fast",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Mode::Accurate => tr("mode.accurate"),
+            Mode::Fast => tr("mode.fast"),
+        }
+    }
+}
+
+/// The `[tuner]` keys every tuner is built with: `algorithm`, `window`,
+/// `analysis_rate`, and `mode`.
+#[derive(Clone, Default)]
+pub struct Setup {
+    pub algorithm: Option<String>,
+    pub window: Option<usize>,
+    pub analysis_rate: Option<f32>,
+    pub mode: Mode,
+}
+
+impl Setup {
+    pub fn from_config(config: &Config) -> Self {
+        Setup {
+            algorithm: config.get_str("tuner", "algorithm").map(str::to_string),
+            window: config.get_f64("tuner", "window").map(|size| size as usize),
+            analysis_rate: config.get_f64("tuner", "analysis_rate").map(|rate| rate as f32),
+            mode: config.get_str("tuner", "mode").and_then(Mode::from_name).unwrap_or_default(),
+        }
+    }
+
+    /// A tuner for `register` with every key applied; an error names a bad
+    /// algorithm or window. Fast mode only supplies the window and rate that
+    /// are not set explicitly.
+    pub fn tuner(&self, sample_rate: u32, register: Register) -> Result<Tuner, String> {
+        let fast = self.mode == Mode::Fast;
+        let mut tuner = Tuner::for_register(sample_rate, register);
+        if let Some(size) = self.window.or(fast.then_some(FAST_WINDOW)) {
+            tuner.set_window_size(size)?;
+        }
+        if let Some(algorithm) = &self.algorithm {
+            tuner.set_algorithm(algorithm)?;
+        }
+        match self.analysis_rate {
+            Some(rate) => tuner.set_analysis_rate(rate),
+            None if fast => {
+                let windows_per_second = sample_rate as f32 / tuner.window_size() as f32;
+                tuner.set_analysis_rate(FAST_OVERLAP as f32 * windows_per_second);
+            }
+            None => {}
+        }
+        tuner.set_refine_peaks(fast);
+        Ok(tuner)
+    }
+}
+
+enum Control {
+    Reset,
+    Replace(Box<Tuner>),
+}
+
+/// Runs pitch detection on its own thread, so a slow terminal never delays
+/// analysis and drawing never waits for an FFT.
+pub struct Worker {
+    results: Receiver<Analysis>,
+    control: Sender<Control>,
+}
+
+impl Worker {
+    /// With a `recorder`, every frame the detector sees is also written to it.
+    pub fn spawn(audio: Receiver<Vec<f32>>, tuner: Tuner, recorder: Option<Recorder>) -> Self {
+        let (results_tx, results) = crossbeam_channel::bounded(RESULTS);
+        let (control, control_rx) = crossbeam_channel::unbounded();
+        thread::spawn(move || run(tuner, audio, control_rx, results_tx, recorder));
+        Worker { results, control }
+    }
+
+    /// Results computed elsewhere, e.g. by a daemon; `reset` and
+    /// `replace_tuner` then do nothing.
+    pub fn remote(results: Receiver<Analysis>) -> Self {
+        let (control, _) = crossbeam_channel::unbounded();
+        Worker { results, control }
+    }
+
+    pub fn results(&self) -> &Receiver<Analysis> {
+        &self.results
+    }
+
+    /// Discards buffered audio, e.g. when pausing, so stale samples are not analysed.
+    pub fn reset(&self) {
+        let _ = self.control.send(Control::Reset);
+    }
+
+    /// Swaps in a tuner for a new input device or sample rate.
+    pub fn replace_tuner(&self, tuner: Tuner) {
+        let _ = self.control.send(Control::Replace(Box::new(tuner)));
+    }
+}
+
+/// Collects incoming audio into overlapping windows and analyses each one.
+pub struct Windows {
+    buffer: Vec<f32>,
+    fresh: Vec<f32>,
+}
+
+impl Windows {
+    pub fn new() -> Self {
+        Windows {
+            buffer: Vec::with_capacity(8192),
+            fresh: Vec::new(),
+        }
+    }
+
+    /// Adds `samples`, returning an analysis for every window that is now due,
+    /// oldest first, one hop apart. The first carries the new samples.
+    pub fn push(&mut self, tuner: &mut Tuner, samples: &[f32]) -> Vec<Analysis> {
+        self.buffer.extend_from_slice(samples);
+        self.fresh.extend_from_slice(samples);
+        let window = tuner.window_size();
+        let hop = tuner.hop().clamp(1, window);
+        let mut analyses = Vec::new();
+        while self.buffer.len() >= window {
+            analyses.push(analyse(tuner, &self.buffer[..window], std::mem::take(&mut self.fresh)));
+            self.buffer.drain(..hop);
+        }
+        analyses
+    }
+
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.fresh.clear();
+    }
+}
+
+fn run(
+    mut tuner: Tuner,
+    audio: Receiver<Vec<f32>>,
+    control: Receiver<Control>,
+    results: Sender<Analysis>,
+    mut recorder: Option<Recorder>,
+) {
+    let mut windows = Windows::new();
+    let mut record = |event: Event| {
+        if let Some(Err(e)) = recorder.as_mut().map(|recorder| recorder.record(&event)) {
+            log::error!(target: "replay", "Stopped recording samples: {}", e);
+            recorder = None;
+        }
+    };
+    record(Event::Start(tuner.sample_rate()));
+
+    loop {
+        select! {
+            recv(control) -> message => {
+                match message {
+                    Ok(Control::Reset) => record(Event::Reset),
+                    Ok(Control::Replace(new)) => {
+                        tuner = *new;
+                        record(Event::Start(tuner.sample_rate()));
+                    }
+                    Err(_) => return,
+                }
+                windows.clear();
+            }
+            recv(audio) -> samples => {
+                let Ok(samples) = samples else {
+                    return;
+                };
+                let analyses = windows.push(&mut tuner, &samples);
+                record(Event::Frame(samples));
+                for analysis in analyses {
+                    if let Err(TrySendError::Disconnected(_)) = results.try_send(analysis) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn analyse(tuner: &mut Tuner, buffer: &[f32], samples: Vec<f32>) -> Analysis {
+    let started = Instant::now();
+    let freq = tuner.detect_frequency(buffer);
+    Analysis {
+        samples,
+        freq,
+        spectrum: tuner.spectrum().to_vec(),
+        partials: freq.map(|freq| tuner.partials(freq)).unwrap_or_default(),
+        elapsed: started.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Mode, Setup, Windows};
+    use crate::tuner::Register;
+    use guitar_tuner::PitchDetector;
+
+    #[test]
+    fn every_due_window_is_analysed() {
+        let mut tuner = Setup::default().tuner(48000, Register::Normal).unwrap();
+        let (window, hop) = (tuner.window_size(), tuner.hop());
+        let mut windows = Windows::new();
+        assert!(windows.push(&mut tuner, &vec![0.0; window - 1]).is_empty());
+        assert_eq!(windows.push(&mut tuner, &[0.0]).len(), 1);
+
+        // Callbacks much shorter than a hop still yield one result per hop,
+        // and one much longer yields every window it completes.
+        let hops = 20;
+        let mut count = 0;
+        for _ in 0..hops * 4 {
+            count += windows.push(&mut tuner, &vec![0.0; hop / 4]).len();
+        }
+        assert_eq!(count, hops);
+        assert_eq!(windows.push(&mut tuner, &vec![0.0; hops * hop]).len(), hops);
+    }
+
+    #[test]
+    fn fast_mode_overlaps_short_windows_eight_times() {
+        let setup = Setup { mode: Mode::Fast, ..Setup::default() };
+        let mut tuner = setup.tuner(48000, Register::Normal).unwrap();
+        assert_eq!((tuner.window_size(), tuner.hop()), (2048, 256));
+
+        // One second of 10 ms callbacks delivers 187.5 results a second.
+        let mut windows = Windows::new();
+        let count: usize = (0..100).map(|_| windows.push(&mut tuner, &[0.0; 480]).len()).sum();
+        assert_eq!(count, (48000 - 2048) / 256 + 1);
+    }
+}
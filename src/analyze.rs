@@ -0,0 +1,203 @@
+use std::io::{self, Write};
+use std::path::Path;
+
+use guitar_tuner::PitchDetector;
+
+use crate::analysis::Setup;
+use crate::config::Config;
+use crate::tuner::{Register, Tuner};
+use crate::wav;
+
+/// Consecutive windows a note must hold to be listed, so attacks and string
+/// noise between notes are skipped.
+const MIN_WINDOWS: usize = 2;
+
+/// One held note: where it starts and ends in seconds and its median frequency.
+pub struct Segment {
+    pub start: f32,
+    pub end: f32,
+    pub note: String,
+    pub octave: i32,
+    pub freq: f32,
+    pub cents: f32,
+}
+
+/// Runs the detector over a WAV file and prints the notes it holds as
+/// `format`: aligned text (the default), a JSON array, or CSV with a header.
+pub fn run(config: &Config, path: &Path, format: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let a4_freq = config.get_f64("tuner", "a4").map_or(440.0, |a4| (a4 as f32).clamp(432.0, 450.0));
+    let recording = wav::read(path)?;
+    let mut tuner = Setup::from_config(config).tuner(recording.sample_rate, Register::Normal)?;
+
+    let segments = segments(&mut tuner, &recording.samples, recording.sample_rate, a4_freq);
+    print(&mut io::stdout().lock(), &segments, format.unwrap_or("text"), path)?;
+    Ok(())
+}
+
+/// Writes `segments` to `out` as `format`; `path` names the file when text output is empty.
+fn print(out: &mut impl Write, segments: &[Segment], format: &str, path: &Path) -> io::Result<()> {
+    match format {
+        "json" => {
+            let objects: Vec<String> = segments
+                .iter()
+                .map(|s| {
+                    format!(
+                        "  {{\"start\":{:.3},\"end\":{:.3},\"note\":\"{}\",\"octave\":{},\"freq\":{:.3},\"cents\":{:.2}}}",
+                        s.start, s.end, s.note, s.octave, s.freq, s.cents
+                    )
+                })
+                .collect();
+            if objects.is_empty() {
+                writeln!(out, "[]")?;
+            } else {
+                writeln!(out, "[\n{}\n]", objects.join(",\n"))?;
+            }
+        }
+        "csv" => {
+            writeln!(out, "start,end,note,octave,freq,cents")?;
+            for s in segments {
+                writeln!(out, "{:.3},{:.3},{},{},{:.3},{:.2}", s.start, s.end, s.note, s.octave, s.freq, s.cents)?;
+            }
+        }
+        _ => {
+            if segments.is_empty() {
+                writeln!(out, "No notes found in {}", path.display())?;
+            }
+            for segment in segments {
+                writeln!(
+                    out,
+                    "{:7.2}-{:<7.2} {:>2}{:<2} {:8.2} Hz {:+6.1}¢",
+                    segment.start, segment.end, segment.note, segment.octave, segment.freq, segment.cents
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Held notes in `samples`, in order, from windows overlapping as the live
+/// tuner's do.
+pub fn segments(tuner: &mut Tuner, samples: &[f32], sample_rate: u32, a4_freq: f32) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut current: Option<Held> = None;
+    let (window, hop) = (tuner.window_size(), tuner.hop());
+
+    for start in (0..samples.len().saturating_sub(window - 1)).step_by(hop) {
+        let reading = tuner.detect_frequency(&samples[start..start + window]).map(|freq| {
+            let (note, octave, _) = tuner.frequency_to_note(freq, a4_freq);
+            (note, octave, freq)
+        });
+
+        match (&mut current, reading) {
+            (Some(held), Some((note, octave, freq))) if held.note == note && held.octave == octave => {
+                held.freqs.push(freq);
+            }
+            (_, reading) => {
+                segments.extend(current.take().and_then(|held| held.finish(tuner, sample_rate, a4_freq)));
+                current = reading.map(|(note, octave, freq)| Held { note, octave, first: start, freqs: vec![freq] });
+            }
+        }
+    }
+    segments.extend(current.and_then(|held| held.finish(tuner, sample_rate, a4_freq)));
+    segments
+}
+
+/// A note still being held, with the frequency of every window so far.
+struct Held {
+    note: String,
+    octave: i32,
+    first: usize,
+    freqs: Vec<f32>,
+}
+
+impl Held {
+    fn finish(mut self, tuner: &Tuner, sample_rate: u32, a4_freq: f32) -> Option<Segment> {
+        if self.freqs.len() < MIN_WINDOWS {
+            return None;
+        }
+        self.freqs.sort_by(f32::total_cmp);
+        let freq = self.freqs[self.freqs.len() / 2];
+        let last = self.first + (self.freqs.len() - 1) * tuner.hop() + tuner.window_size();
+        Some(Segment {
+            start: self.first as f32 / sample_rate as f32,
+            end: last as f32 / sample_rate as f32,
+            cents: tuner.frequency_to_note(freq, a4_freq).2,
+            note: self.note,
+            octave: self.octave,
+            freq,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use guitar_tuner::signal;
+
+    use super::{print, segments, Segment};
+    use crate::analysis::Setup;
+    use crate::config::Config;
+    use crate::tuner::Register;
+
+    const RATE: u32 = 44_100;
+
+    fn output(segments: &[Segment], format: &str) -> String {
+        let mut out = Vec::new();
+        print(&mut out, segments, format, Path::new("take.wav")).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn take() -> Vec<Segment> {
+        vec![
+            Segment { start: 0.0, end: 1.25, note: "A".to_string(), octave: 2, freq: 110.2, cents: 3.04 },
+            Segment { start: 1.5, end: 2.0, note: "C#".to_string(), octave: 3, freq: 138.1, cents: -7.25 },
+        ]
+    }
+
+    #[test]
+    fn held_notes_are_split_into_segments() {
+        let mut samples = signal::sine(110.0, 0.5, RATE, RATE as usize);
+        samples.extend(vec![0.0; RATE as usize / 2]);
+        samples.extend(signal::sine(329.63, 0.5, RATE, RATE as usize));
+        let mut tuner = Setup::from_config(&Config::default()).tuner(RATE, Register::Normal).unwrap();
+
+        let found = segments(&mut tuner, &samples, RATE, 440.0);
+        let notes: Vec<_> = found.iter().map(|s| (s.note.as_str(), s.octave)).collect();
+        assert_eq!(notes, [("A", 2), ("E", 4)]);
+        assert!(found[0].start < 0.1 && found[0].end > 0.9 && found[0].end < 1.6);
+        assert!(found[1].start > 1.3 && found[1].end > 2.4);
+    }
+
+    #[test]
+    fn text_is_aligned_with_signed_cents() {
+        assert_eq!(
+            output(&take(), "text"),
+            "   0.00-1.25     A2    110.20 Hz   +3.0¢\n   1.50-2.00    C#3    138.10 Hz   -7.2¢\n"
+        );
+        assert_eq!(output(&[], "text"), "No notes found in take.wav\n");
+    }
+
+    #[test]
+    fn json_is_an_array_of_objects() {
+        assert_eq!(
+            output(&take(), "json"),
+            concat!(
+                "[\n",
+                "  {\"start\":0.000,\"end\":1.250,\"note\":\"A\",\"octave\":2,\"freq\":110.200,\"cents\":3.04},\n",
+                "  {\"start\":1.500,\"end\":2.000,\"note\":\"C#\",\"octave\":3,\"freq\":138.100,\"cents\":-7.25}\n",
+                "]\n"
+            )
+        );
+        assert_eq!(output(&[], "json"), "[]\n");
+    }
+
+    #[test]
+    fn csv_has_a_header_row() {
+        assert_eq!(
+            output(&take(), "csv"),
+            "start,end,note,octave,freq,cents\n0.000,1.250,A,2,110.200,3.04\n1.500,2.000,C#,3,138.100,-7.25\n"
+        );
+        assert_eq!(output(&[], "csv"), "start,end,note,octave,freq,cents\n");
+    }
+}
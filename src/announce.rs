@@ -0,0 +1,71 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::i18n::{tr, trf};
+use crate::notify::Notice;
+use crate::ui::UiState;
+
+const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct Announcer {
+    last_status: String,
+    last_target: String,
+    last_at: Option<Instant>,
+}
+
+impl Announcer {
+    pub fn new() -> Self {
+        Announcer {
+            last_status: String::new(),
+            last_target: String::new(),
+            last_at: None,
+        }
+    }
+
+    pub fn notice(&mut self, notice: &Notice) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        write!(stdout, "{}\r\n", notice.text)?;
+        stdout.flush()
+    }
+
+    pub fn update(&mut self, state: &UiState) -> io::Result<()> {
+        let mut stdout = io::stdout();
+
+        let target = trf(
+            "announce.target",
+            &[&format!("{}{}", state.target_note, state.target_octave), &format!("{:.1}", state.a4_freq)],
+        );
+        if target != self.last_target {
+            write!(stdout, "{}\r\n", target)?;
+            self.last_target = target;
+        }
+
+        let status = status_line(state);
+        let due = self.last_at.is_none_or(|at| at.elapsed() >= MIN_INTERVAL);
+        if status != self.last_status && due {
+            write!(stdout, "{}\r\n", status)?;
+            self.last_status = status;
+            self.last_at = Some(Instant::now());
+        }
+
+        stdout.flush()
+    }
+}
+
+pub fn status_line(state: &UiState) -> String {
+    let (Some(note), Some(octave), Some(deviation)) =
+        (state.current_note.as_ref(), state.current_octave, state.deviation_cents)
+    else {
+        return tr("announce.no_signal").to_string();
+    };
+
+    let cents = deviation.abs().round() as i32;
+    let name = format!("{}{}", note, octave);
+    match state.guidance() {
+        None => trf("announce.in_tune", &[&name]),
+        Some(guidance) => trf(
+            if guidance.flat { "announce.flat" } else { "announce.sharp" },
+            &[&name, &cents, &guidance.text.to_lowercase()],
+        ),
+    }
+}
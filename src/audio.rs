@@ -1,6 +1,58 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::{Device, SampleFormat, SampleRate, StreamConfig};
-use crossbeam_channel::Sender;
+use cpal::{Device, FromSample, SampleFormat, SampleRate, SizedSample, StreamConfig};
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::notify::Notice;
+use crate::synth::Synth;
+
+/// Sending half of a bounded queue of captured audio frames. When the
+/// consumer falls behind and the queue is full, the oldest frame is dropped
+/// so the newest audio always gets through and memory stays bounded.
+#[derive(Clone)]
+pub struct FrameSender {
+    tx: Sender<Vec<f32>>,
+    /// Used only to evict the oldest frame.
+    rx: Receiver<Vec<f32>>,
+    dropped: Arc<AtomicU64>,
+}
+
+/// Callback buffers queued between capture and analysis, about a second of
+/// audio at typical buffer sizes.
+pub const FRAME_QUEUE: usize = 64;
+
+/// A queue holding up to `capacity` frames (one per audio callback).
+pub fn frame_channel(capacity: usize) -> (FrameSender, Receiver<Vec<f32>>) {
+    let (tx, rx) = crossbeam_channel::bounded(capacity);
+    let sender = FrameSender {
+        tx,
+        rx: rx.clone(),
+        dropped: Arc::new(AtomicU64::new(0)),
+    };
+    (sender, rx)
+}
+
+impl FrameSender {
+    pub fn send(&self, mut frame: Vec<f32>) {
+        loop {
+            match self.tx.try_send(frame) {
+                Err(TrySendError::Full(returned)) => {
+                    if self.rx.try_recv().is_ok() {
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                    frame = returned;
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Frames dropped so far because the queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
 
 pub struct AudioCapture {
     device: Device,
@@ -14,6 +66,24 @@ impl AudioCapture {
             .default_input_device()
             .ok_or("No input device available")?;
 
+        Self::from_device(device)
+    }
+
+    pub fn with_device(name: &str) -> Result<Self, String> {
+        let host = cpal::default_host();
+        let device = host
+            .input_devices()
+            .map_err(|e| format!("Failed to list input devices: {}", e))?
+            .find(|d| d.name().is_ok_and(|n| n == name))
+            .ok_or_else(|| {
+                let available = input_device_names().join(", ");
+                format!("Input device not found: {} (available: {})", name, available)
+            })?;
+
+        Self::from_device(device)
+    }
+
+    fn from_device(device: Device) -> Result<Self, String> {
         let config = device
             .default_input_config()
             .map_err(|e| format!("Failed to get default config: {}", e))?
@@ -22,8 +92,15 @@ impl AudioCapture {
         Ok(AudioCapture { device, config })
     }
 
-    pub fn start_capture(&self, _sample_rate: SampleRate, sender: Sender<Vec<f32>>) -> Result<cpal::Stream, String> {
-        let err_fn = |err| eprintln!("Error in audio stream: {}", err);
+    pub fn start_capture(
+        &self,
+        _sample_rate: SampleRate,
+        sender: FrameSender,
+        notices: Sender<Notice>,
+    ) -> Result<cpal::Stream, String> {
+        let err_fn = move |err| {
+            let _ = notices.send(Notice::error(format!("Error in input stream: {}", err)));
+        };
 
         let stream = match self.device.default_input_config() {
             Ok(config) => {
@@ -35,7 +112,7 @@ impl AudioCapture {
                             &config,
                             move |data: &[f32], _: &cpal::InputCallbackInfo| {
                                 let samples: Vec<f32> = data.to_vec();
-                                let _ = sender.try_send(samples);
+                                sender.send(samples);
                             },
                             err_fn,
                             None,
@@ -46,7 +123,7 @@ impl AudioCapture {
                             &config,
                             move |data: &[i16], _: &cpal::InputCallbackInfo| {
                                 let samples: Vec<f32> = data.iter().map(|s| *s as f32 / 32768.0).collect();
-                                let _ = sender.try_send(samples);
+                                sender.send(samples);
                             },
                             err_fn,
                             None,
@@ -57,7 +134,7 @@ impl AudioCapture {
                             &config,
                             move |data: &[u16], _: &cpal::InputCallbackInfo| {
                                 let samples: Vec<f32> = data.iter().map(|s| (*s as f32 / 65535.0) * 2.0 - 1.0).collect();
-                                let _ = sender.try_send(samples);
+                                sender.send(samples);
                             },
                             err_fn,
                             None,
@@ -76,5 +153,124 @@ impl AudioCapture {
     pub fn sample_rate(&self) -> u32 {
         self.config.sample_rate.0
     }
+
+    pub fn device_name(&self) -> String {
+        self.device.name().unwrap_or_else(|_| "Unknown device".to_string())
+    }
+}
+
+
+pub struct AudioPlayback {
+    device: Device,
+    config: StreamConfig,
+    sample_format: SampleFormat,
+}
+
+pub fn input_device_names() -> Vec<String> {
+    let host = cpal::default_host();
+    match host.input_devices() {
+        Ok(devices) => devices.filter_map(|d| d.name().ok()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+pub fn output_device_names() -> Vec<String> {
+    let host = cpal::default_host();
+    match host.output_devices() {
+        Ok(devices) => devices.filter_map(|d| d.name().ok()).collect(),
+        Err(_) => Vec::new(),
+    }
 }
 
+pub fn default_input_device_name() -> Option<String> {
+    cpal::default_host().default_input_device().and_then(|d| d.name().ok())
+}
+
+pub fn default_output_device_name() -> Option<String> {
+    cpal::default_host().default_output_device().and_then(|d| d.name().ok())
+}
+
+impl AudioPlayback {
+    pub fn new() -> Result<Self, String> {
+        let host = cpal::default_host();
+        let device = host
+            .default_output_device()
+            .ok_or("No output device available")?;
+
+        Self::from_device(device)
+    }
+
+    pub fn with_device(name: &str) -> Result<Self, String> {
+        let host = cpal::default_host();
+        let device = host
+            .output_devices()
+            .map_err(|e| format!("Failed to list output devices: {}", e))?
+            .find(|d| d.name().is_ok_and(|n| n == name))
+            .ok_or_else(|| format!("Output device not found: {}", name))?;
+
+        Self::from_device(device)
+    }
+
+    fn from_device(device: Device) -> Result<Self, String> {
+        let supported = device
+            .default_output_config()
+            .map_err(|e| format!("Failed to get default output config: {}", e))?;
+        let sample_format = supported.sample_format();
+
+        Ok(AudioPlayback {
+            device,
+            config: supported.into(),
+            sample_format,
+        })
+    }
+
+    pub fn start_playback(&self, synth: Arc<Mutex<Synth>>, notices: Sender<Notice>) -> Result<cpal::Stream, String> {
+        let stream = match self.sample_format {
+            SampleFormat::F32 => self.build_stream::<f32>(synth, notices)?,
+            SampleFormat::I16 => self.build_stream::<i16>(synth, notices)?,
+            SampleFormat::U16 => self.build_stream::<u16>(synth, notices)?,
+            _ => return Err("Unsupported sample format".to_string()),
+        };
+
+        stream.play().map_err(|e| format!("Failed to play stream: {}", e))?;
+        Ok(stream)
+    }
+
+    fn build_stream<T>(&self, synth: Arc<Mutex<Synth>>, notices: Sender<Notice>) -> Result<cpal::Stream, String>
+    where
+        T: SizedSample + FromSample<f32>,
+    {
+        let err_fn = move |err| {
+            let _ = notices.send(Notice::error(format!("Error in output stream: {}", err)));
+        };
+        let channels = self.config.channels as usize;
+
+        self.device
+            .build_output_stream(
+                &self.config,
+                move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+                    let mut synth = match synth.lock() {
+                        Ok(synth) => synth,
+                        Err(poisoned) => poisoned.into_inner(),
+                    };
+                    for frame in data.chunks_mut(channels) {
+                        let sample = T::from_sample(synth.next_sample());
+                        for out in frame.iter_mut() {
+                            *out = sample;
+                        }
+                    }
+                },
+                err_fn,
+                None,
+            )
+            .map_err(|e| format!("Failed to build stream: {}", e))
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.config.sample_rate.0
+    }
+
+    pub fn device_name(&self) -> String {
+        self.device.name().unwrap_or_else(|_| "Unknown device".to_string())
+    }
+}
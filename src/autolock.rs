@@ -0,0 +1,134 @@
+use std::time::{Duration, Instant};
+
+/// Lock times offered on the Settings page, in seconds; 0 turns auto-lock off.
+pub const LOCK_TIMES: [f32; 5] = [0.0, 0.5, 1.0, 2.0, 3.0];
+
+/// A reading must be this close to its nearest note to count towards locking
+/// it, so a pitch sliding between two notes locks neither.
+const NEAR_CENTS: f32 = 25.0;
+/// Silence this long unlocks, so a dropped window mid-note does not.
+const UNLOCK_SILENCE: Duration = Duration::from_millis(500);
+
+/// Chromatic tuning that settles on a note. While unlocked the target
+/// follows the nearest note; once one note has been held near enough for the
+/// lock time it stays the target, so overshooting into the next semitone
+/// reads as +60 cents instead of switching notes. Silence or a key press
+/// unlocks it.
+pub struct AutoLock {
+    /// The note heard near enough since the given time.
+    candidate: Option<(String, i32, Instant)>,
+    locked: bool,
+    silent_since: Option<Instant>,
+}
+
+impl AutoLock {
+    pub fn new() -> Self {
+        AutoLock {
+            candidate: None,
+            locked: false,
+            silent_since: None,
+        }
+    }
+
+    /// Call with each reading's time, nearest note, and deviation, or `None`
+    /// for silence. Returns the note the target should follow while unlocked.
+    pub fn update(
+        &mut self,
+        now: Instant,
+        lock_after: Duration,
+        nearest: Option<(&str, i32, f32)>,
+    ) -> Option<(String, i32)> {
+        let Some((note, octave, cents)) = nearest else {
+            let since = *self.silent_since.get_or_insert(now);
+            if now.duration_since(since) >= UNLOCK_SILENCE {
+                self.unlock();
+            }
+            return None;
+        };
+        self.silent_since = None;
+        if self.locked {
+            return None;
+        }
+        match &self.candidate {
+            _ if cents.abs() > NEAR_CENTS => self.candidate = None,
+            Some((held, held_octave, since)) if held == note && *held_octave == octave => {
+                self.locked = now.duration_since(*since) >= lock_after;
+            }
+            _ => self.candidate = Some((note.to_string(), octave, now)),
+        }
+        Some((note.to_string(), octave))
+    }
+
+    pub fn unlock(&mut self) {
+        self.locked = false;
+        self.candidate = None;
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AutoLock, NEAR_CENTS, UNLOCK_SILENCE};
+    use std::time::{Duration, Instant};
+
+    const LOCK_AFTER: Duration = Duration::from_secs(1);
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn locks_a_note_held_for_the_lock_time() {
+        let (mut lock, start) = (AutoLock::new(), Instant::now());
+        assert_eq!(lock.update(start, LOCK_AFTER, Some(("E", 2, 5.0))), Some(("E".to_string(), 2)));
+        lock.update(start + ms(900), LOCK_AFTER, Some(("E", 2, -8.0)));
+        assert!(!lock.is_locked());
+        lock.update(start + ms(1000), LOCK_AFTER, Some(("E", 2, 3.0)));
+        assert!(lock.is_locked());
+
+        // Overshooting into the next semitone keeps the locked target.
+        assert_eq!(lock.update(start + ms(1100), LOCK_AFTER, Some(("F", 2, -40.0))), None);
+        assert!(lock.is_locked());
+    }
+
+    #[test]
+    fn a_reading_between_notes_restarts_the_wait() {
+        let (mut lock, start) = (AutoLock::new(), Instant::now());
+        lock.update(start, LOCK_AFTER, Some(("A", 2, 0.0)));
+        lock.update(start + ms(500), LOCK_AFTER, Some(("A", 2, NEAR_CENTS + 1.0)));
+        lock.update(start + ms(600), LOCK_AFTER, Some(("A", 2, 0.0)));
+        lock.update(start + ms(1200), LOCK_AFTER, Some(("A", 2, 0.0)));
+        assert!(!lock.is_locked());
+        lock.update(start + ms(1600), LOCK_AFTER, Some(("A", 2, 0.0)));
+        assert!(lock.is_locked());
+    }
+
+    #[test]
+    fn silence_unlocks_only_once_it_lasts() {
+        let (mut lock, start) = (AutoLock::new(), Instant::now());
+        lock.update(start, LOCK_AFTER, Some(("D", 3, 0.0)));
+        lock.update(start + ms(1000), LOCK_AFTER, Some(("D", 3, 0.0)));
+        assert!(lock.is_locked());
+
+        let silent = start + ms(1100);
+        lock.update(silent, LOCK_AFTER, None);
+        lock.update(silent + UNLOCK_SILENCE - ms(1), LOCK_AFTER, None);
+        assert!(lock.is_locked());
+        lock.update(silent + UNLOCK_SILENCE, LOCK_AFTER, None);
+        assert!(!lock.is_locked());
+    }
+
+    #[test]
+    fn unlock_starts_over() {
+        let (mut lock, start) = (AutoLock::new(), Instant::now());
+        lock.update(start, LOCK_AFTER, Some(("G", 3, 0.0)));
+        lock.update(start + ms(1000), LOCK_AFTER, Some(("G", 3, 0.0)));
+        lock.unlock();
+        assert!(!lock.is_locked());
+        assert_eq!(lock.update(start + ms(1100), LOCK_AFTER, Some(("G", 3, 0.0))), Some(("G".to_string(), 3)));
+        assert!(!lock.is_locked());
+    }
+}
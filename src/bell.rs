@@ -0,0 +1,88 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::i18n::tr;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// When the terminal bell rings.
+#[derive(Clone, Copy, PartialEq)]
+pub enum BellMode {
+    Off,
+    /// On entering the in-tune zone.
+    Enter,
+    /// On entering it and on leaving it with the note still sounding.
+    Both,
+}
+
+impl BellMode {
+    const ALL: [BellMode; 3] = [BellMode::Off, BellMode::Enter, BellMode::Both];
+
+    pub fn cycle(self, delta: i32) -> Self {
+        let idx = Self::ALL.iter().position(|&mode| mode == self).unwrap_or(0) as i32;
+        Self::ALL[(idx + delta).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BellMode::Off => "off",
+            BellMode::Enter => "enter",
+            BellMode::Both => "both",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BellMode::Off => tr("common.off"),
+            BellMode::Enter => tr("bell.enter"),
+            BellMode::Both => tr("bell.both"),
+        }
+    }
+}
+
+/// Rings the terminal bell (BEL) as the pitch crosses the in-tune zone, for
+/// SSH sessions with no audio output. The terminal, or tmux and the SSH client
+/// beyond it, decides whether that beeps or flashes. Rings closer together
+/// than `[feedback] bell_interval` are dropped, so a pitch wavering on the edge
+/// of the zone does not buzz.
+pub struct Bell {
+    interval: Duration,
+    in_tune: bool,
+    rung_at: Option<Instant>,
+}
+
+impl Bell {
+    pub fn from_config(config: &Config) -> Self {
+        let interval = config.get_f64("feedback", "bell_interval").filter(|seconds| *seconds >= 0.0);
+        Bell {
+            interval: interval.map_or(DEFAULT_INTERVAL, Duration::from_secs_f64),
+            in_tune: false,
+            rung_at: None,
+        }
+    }
+
+    /// Call after each analysis with whether the pitch is in the in-tune zone
+    /// and whether a note is sounding at all.
+    pub fn update(&mut self, mode: BellMode, in_tune: bool, sounding: bool) -> io::Result<()> {
+        let entered = in_tune && !self.in_tune;
+        let left = !in_tune && self.in_tune && sounding;
+        self.in_tune = in_tune;
+        let ring = match mode {
+            BellMode::Off => false,
+            BellMode::Enter => entered,
+            BellMode::Both => entered || left,
+        };
+        if !ring || self.rung_at.is_some_and(|at| at.elapsed() < self.interval) {
+            return Ok(());
+        }
+        self.rung_at = Some(Instant::now());
+        let mut stdout = io::stdout();
+        stdout.write_all(b"\x07")?;
+        stdout.flush()
+    }
+}
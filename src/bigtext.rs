@@ -0,0 +1,63 @@
+pub const GLYPH_HEIGHT: usize = 5;
+
+fn glyph(c: char) -> [&'static str; GLYPH_HEIGHT] {
+    match c {
+        'A' => [" ### ", "#   #", "#####", "#   #", "#   #"],
+        'B' => ["#### ", "#   #", "#### ", "#   #", "#### "],
+        'C' => [" ####", "#    ", "#    ", "#    ", " ####"],
+        'D' => ["#### ", "#   #", "#   #", "#   #", "#### "],
+        'E' => ["#####", "#    ", "#### ", "#    ", "#####"],
+        'F' => ["#####", "#    ", "#### ", "#    ", "#    "],
+        'G' => [" ####", "#    ", "#  ##", "#   #", " ####"],
+        '#' => [" # # ", "#####", " # # ", "#####", " # # "],
+        '0' => [" ### ", "#  ##", "# # #", "##  #", " ### "],
+        '1' => ["  #  ", " ##  ", "  #  ", "  #  ", " ### "],
+        '2' => [" ### ", "#   #", "  ## ", " #   ", "#####"],
+        '3' => ["#### ", "    #", " ### ", "    #", "#### "],
+        '4' => ["#   #", "#   #", "#####", "    #", "    #"],
+        '5' => ["#####", "#    ", "#### ", "    #", "#### "],
+        '6' => [" ### ", "#    ", "#### ", "#   #", " ### "],
+        '7' => ["#####", "    #", "   # ", "  #  ", "  #  "],
+        '8' => [" ### ", "#   #", " ### ", "#   #", " ### "],
+        '9' => [" ### ", "#   #", " ####", "    #", " ### "],
+        '+' => ["     ", "  #  ", " ### ", "  #  ", "     "],
+        '-' => ["     ", "     ", " ### ", "     ", "     "],
+        '.' => ["     ", "     ", "     ", "     ", "  #  "],
+        'c' => ["     ", " ### ", "#    ", "#    ", " ### "],
+        _ => ["     ", "     ", "     ", "     ", "     "],
+    }
+}
+
+pub fn text_width(text: &str) -> usize {
+    let count = text.chars().count();
+    if count == 0 {
+        0
+    } else {
+        count * 6 - 1
+    }
+}
+
+pub fn render_lines(text: &str, scale: usize) -> Vec<String> {
+    let scale = scale.max(1);
+    let mut lines = Vec::with_capacity(GLYPH_HEIGHT * scale);
+
+    for row in 0..GLYPH_HEIGHT {
+        let mut line = String::new();
+        for (i, c) in text.chars().enumerate() {
+            if i > 0 {
+                line.push_str(&" ".repeat(scale));
+            }
+            for pixel in glyph(c)[row].chars() {
+                let fill = if pixel == '#' { '█' } else { ' ' };
+                for _ in 0..scale {
+                    line.push(fill);
+                }
+            }
+        }
+        for _ in 0..scale {
+            lines.push(line.clone());
+        }
+    }
+
+    lines
+}
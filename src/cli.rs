@@ -0,0 +1,359 @@
+use crate::analysis::Mode;
+use crate::config::{Config, Value};
+use crate::presets;
+use guitar_tuner::detector;
+use guitar_tuner::tuner::{MAX_WINDOW, MIN_WINDOW};
+
+const USAGE: &str = "\
+Usage: guitar-tuner [COMMAND] [OPTIONS]
+
+Commands:
+  tune                Run the tuner (the default)
+  analyze FILE        Print the notes found in a WAV recording
+  devices             List audio input and output devices
+  tone NOTE           Play a reference tone such as A4, Eb2, or 440 until Enter
+  replay FILE         Run the detector over samples saved with --record
+  daemon              Serve readings to other programs over a local socket
+  history             List past sessions and per-string results from the history
+
+Options:
+  --device NAME       Capture from this input device
+  --a4 HZ             Reference pitch for A4, 432-450
+  --instrument NAME   Start with this preset, e.g. \"bass\" or \"Guitar (Drop D)\"
+  --algorithm NAME    Pitch detection algorithm: fft or yin
+  --analysis-rate N   Pitch estimates per second, independent of the display rate
+  --window SAMPLES    Analysis window, a power of two from 1024 to 32768 (default 4096)
+  --mode MODE         Analysis mode: accurate, or fast for the least needle lag
+  --headless          Print readings as JSON lines instead of drawing the UI
+  --plain             Print readings as one plain text line instead of drawing the UI
+  --screen-reader     Announce readings as plain text lines
+  --csv PATH          Append stable readings to a CSV file
+  --report PATH       Write a tuning report on exit, Markdown or .json
+  --overlay PATH      Keep a text or JSON file with the current reading
+  --listen [ADDR]     Serve a live view and API on a port or host:port (default 8765)
+  --midi-out          Open a MIDI output port that plays the detected note
+  --midi-in           Open a MIDI input port that sets the target note
+  --socket SPEC       Daemon socket: a path, a port on 127.0.0.1, or host:port
+  --connect [SPEC]    Show readings from a running daemon instead of capturing
+  --format FORMAT     analyze or history output: text, json, or csv
+  --since DATE        history: sessions from this UTC date, YYYY-MM-DD
+  --until DATE        history: sessions up to and including this UTC date
+  --string NOTE       history: only readings with this target string, e.g. E2
+  --drift             history: how each string drifted between sessions
+  --record PATH       Save the exact samples fed to the detector, for replay
+  --log-level LEVEL   Log file verbosity: off, error, warn, info, debug, or trace
+  --verbose           Also show audio library messages in the UI, not only in the log
+  -h, --help          Print this help
+  -V, --version       Print the version
+
+Options override the matching keys in config.toml for this run.";
+
+const COMMANDS: [&str; 7] = ["tune", "devices", "analyze", "tone", "replay", "daemon", "history"];
+
+#[derive(Default)]
+pub enum Command {
+    #[default]
+    Tune,
+    Analyze(String),
+    Devices,
+    Tone(String),
+    Replay(String),
+    Daemon,
+    History,
+}
+
+/// Command-line options. Each one takes precedence over its config key.
+#[derive(Default)]
+pub struct Args {
+    pub command: Command,
+    pub device: Option<String>,
+    pub a4: Option<f64>,
+    pub instrument: Option<String>,
+    pub algorithm: Option<String>,
+    pub analysis_rate: Option<f64>,
+    pub window: Option<usize>,
+    pub mode: Option<String>,
+    pub headless: bool,
+    pub plain: bool,
+    pub verbose: bool,
+    pub screen_reader: bool,
+    pub csv: Option<String>,
+    pub overlay: Option<String>,
+    pub report: Option<String>,
+    pub listen: Option<String>,
+    pub midi_out: bool,
+    pub midi_in: bool,
+    pub socket: Option<String>,
+    pub connect: Option<String>,
+    pub record: Option<String>,
+    pub format: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub string: Option<String>,
+    pub drift: bool,
+    pub log_level: Option<String>,
+    pub help: bool,
+    pub version: bool,
+}
+
+impl Args {
+    pub fn from_env() -> Result<Self, String> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    /// Accepts both `--flag value` and `--flag=value`.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter().peekable();
+        let mut positional = Vec::new();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+                _ => (arg, None),
+            };
+            let mut value = |name: &str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next_if(|next| !next.starts_with('-')))
+                    .ok_or_else(|| format!("{} needs a value (see --help)", name))
+            };
+
+            match flag.as_str() {
+                "--device" => parsed.device = Some(value("--device")?),
+                "--a4" => {
+                    let text = value("--a4")?;
+                    let hz = text.parse::<f64>().map_err(|_| format!("--a4 needs a number in Hz, got {}", text))?;
+                    if !(432.0..=450.0).contains(&hz) {
+                        return Err(format!("--a4 must be between 432 and 450 Hz, got {}", text));
+                    }
+                    parsed.a4 = Some(hz);
+                }
+                "--instrument" => parsed.instrument = Some(value("--instrument")?),
+                "--algorithm" => {
+                    let name = value("--algorithm")?;
+                    if !detector::BUILTIN.contains(&name.as_str()) {
+                        return Err(format!("--algorithm must be one of {}, got {}", detector::BUILTIN.join(", "), name));
+                    }
+                    parsed.algorithm = Some(name);
+                }
+                "--analysis-rate" => {
+                    let text = value("--analysis-rate")?;
+                    match text.parse::<f64>() {
+                        Ok(rate) if rate > 0.0 => parsed.analysis_rate = Some(rate),
+                        _ => return Err(format!("--analysis-rate needs a positive number per second, got {}", text)),
+                    }
+                }
+                "--window" => {
+                    let text = value("--window")?;
+                    match text.parse::<usize>() {
+                        Ok(size) if size.is_power_of_two() && (MIN_WINDOW..=MAX_WINDOW).contains(&size) => {
+                            parsed.window = Some(size)
+                        }
+                        _ => {
+                            let range = format!("{} to {}", MIN_WINDOW, MAX_WINDOW);
+                            return Err(format!("--window must be a power of two from {}, got {}", range, text));
+                        }
+                    }
+                }
+                "--mode" => {
+                    let name = value("--mode")?;
+                    if Mode::from_name(&name).is_none() {
+                        return Err(format!("--mode must be accurate or fast, got {}", name));
+                    }
+                    parsed.mode = Some(name);
+                }
+                "--headless" => parsed.headless = true,
+                "--plain" => parsed.plain = true,
+                "--verbose" => parsed.verbose = true,
+                "--screen-reader" => parsed.screen_reader = true,
+                "--csv" => parsed.csv = Some(value("--csv")?),
+                "--overlay" => parsed.overlay = Some(value("--overlay")?),
+                "--report" => parsed.report = Some(value("--report")?),
+                // The address is optional: a bare `--listen` uses the default port.
+                "--listen" => parsed.listen = Some(value("--listen").unwrap_or_default()),
+                "--midi-out" => parsed.midi_out = true,
+                "--midi-in" => parsed.midi_in = true,
+                "--socket" => parsed.socket = Some(value("--socket")?),
+                // Without a value, the daemon's default socket.
+                "--connect" => parsed.connect = Some(value("--connect").unwrap_or_default()),
+                "--format" => {
+                    let format = value("--format")?;
+                    if !["text", "json", "csv"].contains(&format.as_str()) {
+                        return Err(format!("--format must be text, json, or csv, got {}", format));
+                    }
+                    parsed.format = Some(format);
+                }
+                "--since" => parsed.since = Some(date(&value("--since")?)?),
+                "--until" => parsed.until = Some(date(&value("--until")?)?),
+                "--string" => parsed.string = Some(value("--string")?),
+                "--drift" => parsed.drift = true,
+                "--record" => parsed.record = Some(value("--record")?),
+                "--log-level" => parsed.log_level = Some(value("--log-level")?),
+                "-h" | "--help" => parsed.help = true,
+                "-V" | "--version" => parsed.version = true,
+                _ if flag.starts_with('-') => return Err(format!("Unknown option: {} (see --help)", flag)),
+                _ => positional.push(flag),
+            }
+        }
+
+        parsed.command = match positional.as_slice() {
+            [] => Command::Tune,
+            [command] if command == "tune" => Command::Tune,
+            [command] if command == "devices" => Command::Devices,
+            [command] if command == "daemon" => Command::Daemon,
+            [command] if command == "history" => Command::History,
+            [command, path] if command == "analyze" => Command::Analyze(path.clone()),
+            [command, note] if command == "tone" => Command::Tone(note.clone()),
+            [command, path] if command == "replay" => Command::Replay(path.clone()),
+            [command] if ["analyze", "tone", "replay"].contains(&command.as_str()) => {
+                return Err(format!("{} needs an argument (see --help)", command))
+            }
+            [command, ..] if !COMMANDS.contains(&command.as_str()) => {
+                return Err(format!("Unknown command: {} (see --help)", command))
+            }
+            [.., extra] => return Err(format!("Unexpected argument: {} (see --help)", extra)),
+        };
+        if parsed.connect.is_some() && parsed.record.is_some() {
+            return Err("--record needs local audio and cannot be used with --connect".to_string());
+        }
+        Ok(parsed)
+    }
+
+    /// Overrides the config keys behind `--device`, `--a4`, `--instrument`,
+    /// `--algorithm`, `--analysis-rate`, `--window`, and `--mode`. Needs the custom presets to be registered first.
+    pub fn apply(&self, config: &mut Config) -> Result<(), String> {
+        if let Some(device) = &self.device {
+            config.override_value("audio", "input_device", Value::String(device.clone()));
+        }
+        if let Some(a4) = self.a4 {
+            config.override_value("tuner", "a4", Value::Number(a4));
+        }
+        if let Some(name) = &self.instrument {
+            let preset = find_preset(name)?;
+            config.override_value("tuner", "instrument", Value::String(preset.to_string()));
+        }
+        if let Some(algorithm) = &self.algorithm {
+            config.override_value("tuner", "algorithm", Value::String(algorithm.clone()));
+        }
+        if let Some(rate) = self.analysis_rate {
+            config.override_value("tuner", "analysis_rate", Value::Number(rate));
+        }
+        if let Some(size) = self.window {
+            config.override_value("tuner", "window", Value::Number(size as f64));
+        }
+        if let Some(mode) = &self.mode {
+            config.override_value("tuner", "mode", Value::String(mode.clone()));
+        }
+        Ok(())
+    }
+}
+
+pub fn usage() -> &'static str {
+    USAGE
+}
+
+/// Checks that `text` is a date written `YYYY-MM-DD`.
+fn date(text: &str) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    let digits = |range: std::ops::Range<usize>| bytes[range].iter().all(u8::is_ascii_digit);
+    if bytes.len() == 10 && bytes[4] == b'-' && bytes[7] == b'-' && digits(0..4) && digits(5..7) && digits(8..10) {
+        Ok(text.to_string())
+    } else {
+        Err(format!("Dates are written YYYY-MM-DD, got {}", text))
+    }
+}
+
+/// The preset named `name`, ignoring case, or else the first one starting
+/// with it, so `bass` picks `Bass (4-string)`.
+pub fn find_preset(name: &str) -> Result<&'static str, String> {
+    let wanted = name.to_lowercase();
+    let all = presets::all();
+    all.iter()
+        .find(|p| p.name.to_lowercase() == wanted)
+        .or_else(|| all.iter().find(|p| p.name.to_lowercase().starts_with(&wanted)))
+        .map(|p| p.name)
+        .ok_or_else(|| {
+            let names: Vec<&str> = all.iter().map(|p| p.name).collect();
+            format!("Unknown instrument: {} (available: {})", name, names.join(", "))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_preset, Args, Command};
+
+    fn parse(line: &str) -> Result<Args, String> {
+        Args::parse(line.split_whitespace().map(str::to_string))
+    }
+
+    fn error(line: &str) -> String {
+        match parse(line) {
+            Ok(_) => panic!("{:?} parsed", line),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn values_follow_a_space_or_an_equals_sign() {
+        for line in ["--a4 442 --device Mic --window 8192", "--a4=442 --device=Mic --window=8192"] {
+            let args = parse(line).unwrap();
+            assert_eq!(args.a4, Some(442.0));
+            assert_eq!(args.device.as_deref(), Some("Mic"));
+            assert_eq!(args.window, Some(8192));
+        }
+        assert_eq!(parse("--csv=a=b.csv").unwrap().csv.as_deref(), Some("a=b.csv"));
+    }
+
+    #[test]
+    fn unknown_options_and_commands_are_rejected() {
+        assert!(error("--frobnicate").contains("Unknown option: --frobnicate"));
+        assert!(error("--a4x=442").contains("Unknown option: --a4x"));
+        assert!(error("-q").contains("Unknown option: -q"));
+        assert!(error("frobnicate").contains("Unknown command: frobnicate"));
+        assert!(error("devices extra").contains("Unexpected argument: extra"));
+    }
+
+    #[test]
+    fn missing_values_are_reported() {
+        assert!(error("--device").contains("--device needs a value"));
+        assert!(error("--csv --plain").contains("--csv needs a value"));
+        assert!(error("analyze").contains("analyze needs an argument"));
+        // Both take an optional value.
+        assert_eq!(parse("--listen").unwrap().listen.as_deref(), Some(""));
+        assert_eq!(parse("--connect --verbose").unwrap().connect.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn numbers_and_names_are_checked() {
+        assert!(error("--a4 high").contains("needs a number"));
+        assert!(error("--a4 460").contains("between 432 and 450"));
+        assert!(error("--analysis-rate 0").contains("positive number"));
+        assert!(error("--analysis-rate=fast").contains("positive number"));
+        assert!(error("--window 3000").contains("power of two"));
+        assert!(error("--window 65536").contains("power of two"));
+        assert!(error("--mode slow").contains("accurate or fast"));
+        assert!(error("--algorithm mpm").contains("--algorithm must be one of"));
+        assert!(error("--format xml").contains("text, json, or csv"));
+        assert!(error("history --since 2024-1-5").contains("YYYY-MM-DD"));
+        assert!(error("--connect /tmp/t.sock --record out.tuner").contains("cannot be used with --connect"));
+    }
+
+    #[test]
+    fn commands_take_their_arguments() {
+        assert!(matches!(parse("").unwrap().command, Command::Tune));
+        assert!(matches!(parse("analyze take.wav").unwrap().command, Command::Analyze(path) if path == "take.wav"));
+        assert!(matches!(parse("tone A4 --a4 440").unwrap().command, Command::Tone(note) if note == "A4"));
+        let args = parse("history --since 2024-01-05 --drift").unwrap();
+        assert!(matches!(args.command, Command::History));
+        assert_eq!(args.since.as_deref(), Some("2024-01-05"));
+        assert!(args.drift);
+    }
+
+    #[test]
+    fn presets_are_found_by_name_or_prefix() {
+        assert_eq!(find_preset("guitar (drop d)"), Ok("Guitar (Drop D)"));
+        assert_eq!(find_preset("bass"), Ok("Bass (4-string)"));
+        assert!(find_preset("kazoo").is_err());
+    }
+}
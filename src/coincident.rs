@@ -0,0 +1,61 @@
+use crate::practice::JUST_RATIOS;
+use crate::tuner::{Partial, PARTIALS};
+
+/// Highest partial of the target counted. The target is not sounding, so its
+/// partials are worked out rather than measured and any of them is available.
+const TARGET_PARTIALS: usize = 10;
+/// Widest interval checked: two octaves, a double octave's 4:1.
+const MAX_SEMITONES: u32 = 24;
+
+/// Two partials that coincide in a just interval, numbered on the lower and
+/// upper note as aural piano tuners name them (4:2 is the fourth partial of
+/// the lower note against the second of the upper), and how fast they beat.
+pub struct Coincidence {
+    pub lower: usize,
+    pub upper: usize,
+    /// Beats per second, positive when the sounding note's partial is the higher.
+    pub beats: f32,
+}
+
+/// The interval in semitones from the target to the sounding note at `freq`,
+/// and the beat rate of each pair of their coincident partials that was
+/// measured. The sounding note's partials come from the spectrum; the
+/// target's are taken as whole multiples of `target`, a captured reference
+/// or the equal-tempered pitch. A positive rate means the sounding note is
+/// sharp of a beatless interval, whichever of the two is the lower.
+pub fn coincidences(freq: f32, partials: &[Partial], target: f32) -> Option<(u32, Vec<Coincidence>)> {
+    let semitones = (12.0 * (freq / target).log2()).abs().round() as u32;
+    if semitones > MAX_SEMITONES {
+        return None;
+    }
+    let (octaves, within) = match semitones {
+        0 => (0, 0),
+        _ => ((semitones - 1) / 12, (semitones - 1) % 12 + 1),
+    };
+    let (numerator, denominator) = JUST_RATIOS[within as usize];
+    let (numerator, denominator) = reduce((numerator << octaves) as usize, denominator as usize);
+
+    // In an interval of numerator:denominator, partial `numerator * k` of the
+    // lower note meets partial `denominator * k` of the upper note.
+    let sounding_is_upper = freq >= target;
+    let mut pairs = Vec::new();
+    for k in 1.. {
+        let (lower, upper) = (numerator * k, denominator * k);
+        let (measured, worked_out) = if sounding_is_upper { (upper, lower) } else { (lower, upper) };
+        if measured > PARTIALS || worked_out > TARGET_PARTIALS {
+            break;
+        }
+        if let Some(partial) = partials.iter().find(|partial| partial.number == measured) {
+            pairs.push(Coincidence { lower, upper, beats: partial.freq - target * worked_out as f32 });
+        }
+    }
+    Some((semitones, pairs))
+}
+
+fn reduce(a: usize, b: usize) -> (usize, usize) {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    (a / x, b / x)
+}
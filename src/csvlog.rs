@@ -0,0 +1,89 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::ui::Reading;
+
+const HEADER: &str = "timestamp,session_seconds,note,octave,frequency_hz,cents,target";
+
+/// Appends every stable reading to a CSV file, one row per analysis frame.
+pub struct CsvLog {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    started: Instant,
+}
+
+impl CsvLog {
+    /// Opens `path` for appending, writing the header if the file is new or empty.
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        let empty = file.metadata().map(|m| m.len() == 0).unwrap_or(true);
+
+        let mut log = CsvLog {
+            path: path.to_path_buf(),
+            writer: BufWriter::new(file),
+            started: Instant::now(),
+        };
+        if empty {
+            log.write_line(HEADER)?;
+        }
+        Ok(log)
+    }
+
+    pub fn record(&mut self, reading: &Reading, target: &str) -> Result<(), String> {
+        let line = format!(
+            "{},{:.3},{},{},{:.3},{:.2},{}",
+            utc_timestamp(SystemTime::now()),
+            reading.at.saturating_duration_since(self.started).as_secs_f64(),
+            reading.note,
+            reading.octave,
+            reading.freq,
+            reading.deviation_cents,
+            target
+        );
+        self.write_line(&line)
+    }
+
+    fn write_line(&mut self, line: &str) -> Result<(), String> {
+        // Flushed per row so a crash or power cut loses at most one reading.
+        writeln!(self.writer, "{}", line)
+            .and_then(|_| self.writer.flush())
+            .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))
+    }
+}
+
+/// `time` as UTC ISO 8601 with milliseconds, e.g. `2024-05-01T19:30:00.250Z`.
+pub fn utc_timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs / 3600 % 24,
+        secs / 60 % 60,
+        secs % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Days since 1970-01-01 to a proleptic Gregorian (year, month, day).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
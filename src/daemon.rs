@@ -0,0 +1,563 @@
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::fs::FileTypeExt;
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use cpal::SampleRate;
+use crossbeam_channel::{select, Sender};
+
+use crate::analysis::{Analysis, Setup, Worker};
+use crate::audio::{self, AudioCapture};
+use crate::config::Config;
+use crate::i18n;
+use crate::json::json_escape;
+use crate::notify::Notice;
+use crate::replay::Recorder;
+use crate::shutdown::Shutdown;
+use crate::tuner::{Partial, Register, Tuner};
+use crate::ui::AnalysisStatus;
+
+#[cfg(not(unix))]
+const DEFAULT_PORT: u16 = 8766;
+#[cfg(unix)]
+const SOCKET_NAME: &str = "rust_tuner.sock";
+/// How long a slow client may hold up a reading before it is dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_millis(200);
+const RECONNECT_EVERY: Duration = Duration::from_secs(1);
+/// How often the daemon checks for a shutdown request while no audio arrives.
+const POLL: Duration = Duration::from_millis(200);
+const RESULTS: usize = 8;
+
+/// Where the daemon listens and clients connect.
+#[derive(Clone)]
+pub enum Address {
+    #[cfg(unix)]
+    Unix(PathBuf),
+    Tcp(String),
+}
+
+impl Address {
+    /// `host:port`, or a bare port on 127.0.0.1, is TCP; anything else is a
+    /// Unix socket path. Empty means `$XDG_RUNTIME_DIR/rust_tuner.sock` (or
+    /// `/tmp/rust_tuner-$USER.sock`), and TCP port 8766 on Windows.
+    pub fn parse(spec: &str) -> Address {
+        match spec {
+            "" => Address::default_for_platform(),
+            port if port.parse::<u16>().is_ok() => Address::Tcp(format!("127.0.0.1:{}", port)),
+            #[cfg(unix)]
+            path if !path.contains(':') => Address::Unix(PathBuf::from(path)),
+            address => Address::Tcp(address.to_string()),
+        }
+    }
+
+    #[cfg(unix)]
+    fn default_for_platform() -> Address {
+        let dir = std::env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty());
+        Address::Unix(match dir {
+            Some(dir) => PathBuf::from(dir).join(SOCKET_NAME),
+            None => {
+                let user = std::env::var("USER").unwrap_or_else(|_| "user".to_string());
+                std::env::temp_dir().join(format!("rust_tuner-{}.sock", user))
+            }
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn default_for_platform() -> Address {
+        Address::Tcp(format!("127.0.0.1:{}", DEFAULT_PORT))
+    }
+
+    fn connect(&self) -> io::Result<Connection> {
+        match self {
+            #[cfg(unix)]
+            Address::Unix(path) => UnixStream::connect(path).map(Connection::Unix),
+            Address::Tcp(address) => TcpStream::connect(address).map(Connection::Tcp),
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(unix)]
+            Address::Unix(path) => write!(f, "{}", path.display()),
+            Address::Tcp(address) => write!(f, "{}", address),
+        }
+    }
+}
+
+/// A client connection over either kind of socket.
+enum Connection {
+    #[cfg(unix)]
+    Unix(UnixStream),
+    Tcp(TcpStream),
+}
+
+impl Connection {
+    fn try_clone(&self) -> io::Result<Connection> {
+        match self {
+            #[cfg(unix)]
+            Connection::Unix(stream) => stream.try_clone().map(Connection::Unix),
+            Connection::Tcp(stream) => stream.try_clone().map(Connection::Tcp),
+        }
+    }
+
+    fn set_write_timeout(&self, timeout: Duration) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Connection::Unix(stream) => stream.set_write_timeout(Some(timeout)),
+            Connection::Tcp(stream) => stream.set_write_timeout(Some(timeout)),
+        }
+    }
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            #[cfg(unix)]
+            Connection::Unix(stream) => stream.read(buf),
+            Connection::Tcp(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            #[cfg(unix)]
+            Connection::Unix(stream) => stream.write(buf),
+            Connection::Tcp(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Connection::Unix(stream) => stream.flush(),
+            Connection::Tcp(stream) => stream.flush(),
+        }
+    }
+}
+
+enum Listener {
+    #[cfg(unix)]
+    Unix(UnixListener),
+    Tcp(TcpListener),
+}
+
+impl Listener {
+    /// A socket file left behind by a daemon that did not exit cleanly is
+    /// replaced; one that still answers means a daemon is already running.
+    /// Anything else at the path is left alone.
+    fn bind(address: &Address) -> Result<Listener, String> {
+        let failed = |e: io::Error| format!("Failed to listen on {}: {}", address, e);
+        match address {
+            #[cfg(unix)]
+            Address::Unix(path) => {
+                if let Ok(metadata) = std::fs::symlink_metadata(path) {
+                    if !metadata.file_type().is_socket() {
+                        return Err(format!("Not replacing {}, which is not a socket", path.display()));
+                    }
+                    if UnixStream::connect(path).is_ok() {
+                        return Err(format!("A tuner daemon is already listening on {}", path.display()));
+                    }
+                    std::fs::remove_file(path).map_err(failed)?;
+                }
+                UnixListener::bind(path).map(Listener::Unix).map_err(failed)
+            }
+            Address::Tcp(spec) => TcpListener::bind(spec).map(Listener::Tcp).map_err(failed),
+        }
+    }
+
+    fn accept(&self) -> io::Result<Connection> {
+        match self {
+            #[cfg(unix)]
+            Listener::Unix(listener) => listener.accept().map(|(stream, _)| Connection::Unix(stream)),
+            Listener::Tcp(listener) => listener.accept().map(|(stream, _)| Connection::Tcp(stream)),
+        }
+    }
+}
+
+/// What connection threads share with the daemon loop.
+struct Shared {
+    clients: Mutex<Vec<Connection>>,
+    /// The `hello` line, sent to each new client and in answer to `status`.
+    hello: Mutex<String>,
+}
+
+/// Captures audio without a terminal and serves readings to any number of
+/// clients, one JSON object per line, until stopped by a signal. Clients may
+/// send `a4 HZ`, `algorithm NAME`, and `status` lines.
+pub fn run(config: &Config, socket: Option<&str>, recorder: Option<Recorder>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut a4_freq = config.get_f64("tuner", "a4").map_or(440.0, |a4| (a4 as f32).clamp(432.0, 450.0));
+    let capture = match config.get_str("audio", "input_device") {
+        Some(name) => AudioCapture::with_device(name)?,
+        None => AudioCapture::new()?,
+    };
+    let sample_rate = capture.sample_rate();
+    let device = capture.device_name();
+    let setup = Setup::from_config(config);
+    let tuner = setup.tuner(sample_rate, Register::Normal)?;
+    let mut algorithm = tuner.algorithm();
+    let window = tuner.window();
+
+    let (frames, rx) = audio::frame_channel(audio::FRAME_QUEUE);
+    let (notice_tx, notice_rx) = crossbeam_channel::unbounded();
+    let worker = Worker::spawn(rx, tuner, recorder);
+    let _stream = capture.start_capture(SampleRate(sample_rate), frames, notice_tx)?;
+
+    let address = Address::parse(socket.unwrap_or(""));
+    let listener = Listener::bind(&address)?;
+    let shutdown = Shutdown::install()?;
+    let hello = |algorithm: &str, a4_freq: f32| {
+        format!(
+            "{{\"type\":\"hello\",\"version\":\"{}\",\"device\":\"{}\",\"sample_rate\":{},\"algorithm\":\"{}\",\"window_ms\":{:.1},\"a4\":{:.1}}}",
+            env!("CARGO_PKG_VERSION"),
+            json_escape(&device),
+            sample_rate,
+            algorithm,
+            window.as_secs_f64() * 1000.0,
+            a4_freq
+        )
+    };
+    let shared = Arc::new(Shared {
+        clients: Mutex::new(Vec::new()),
+        hello: Mutex::new(hello(algorithm, a4_freq)),
+    });
+
+    let (request_tx, requests) = crossbeam_channel::unbounded();
+    let accepting = Arc::clone(&shared);
+    thread::spawn(move || loop {
+        match listener.accept() {
+            Ok(connection) => accept(connection, &accepting, &request_tx),
+            Err(e) => log::warn!(target: "daemon", "Failed to accept a client: {}", e),
+        }
+    });
+    log::info!(target: "daemon", "Listening on {}", address);
+    eprintln!("Listening on {} ({} at {} Hz). Stop with Ctrl-C.", address, device, sample_rate);
+
+    let mut running = true;
+    while running && !shutdown.requested() {
+        for notice in notice_rx.try_iter() {
+            log::warn!(target: "audio", "{}", notice.text);
+            eprintln!("{}", notice.text);
+        }
+        for (request, mut reply) in requests.try_iter() {
+            let answer = match parse_request(&request) {
+                Ok(Request::A4(hz)) => {
+                    a4_freq = hz;
+                    Ok(None)
+                }
+                Ok(Request::Algorithm(name)) => {
+                    let setup = Setup { algorithm: Some(name), ..setup.clone() };
+                    setup.tuner(sample_rate, Register::Normal).map(|tuner| {
+                        algorithm = tuner.algorithm();
+                        worker.replace_tuner(tuner);
+                        None
+                    })
+                }
+                Ok(Request::Status) => Ok(Some(hello(algorithm, a4_freq))),
+                Err(e) => Err(e),
+            };
+            if let Ok(mut latest) = shared.hello.lock() {
+                *latest = hello(algorithm, a4_freq);
+            }
+            let line = match answer {
+                Ok(Some(status)) => status,
+                Ok(None) => "{\"type\":\"ok\"}".to_string(),
+                Err(e) => format!("{{\"type\":\"error\",\"message\":\"{}\"}}", json_escape(&e)),
+            };
+            let _ = writeln!(reply, "{}", line);
+        }
+
+        select! {
+            recv(worker.results()) -> analysis => {
+                match analysis {
+                    Ok(analysis) => {
+                        let line = format!("{}\n", reading_json(&analysis, a4_freq));
+                        if let Ok(mut clients) = shared.clients.lock() {
+                            clients.retain_mut(|client| client.write_all(line.as_bytes()).is_ok());
+                        }
+                    }
+                    Err(_) => running = false,
+                }
+            }
+            default(POLL) => {}
+        }
+    }
+
+    log::info!(target: "daemon", "Stopping");
+    #[cfg(unix)]
+    if let Address::Unix(path) = &address {
+        let _ = std::fs::remove_file(path);
+    }
+    Ok(())
+}
+
+/// Greets a new client and reads its requests on a thread of its own.
+fn accept(connection: Connection, shared: &Arc<Shared>, requests: &Sender<(String, Connection)>) {
+    let hello = shared.hello.lock().map(|hello| hello.clone()).unwrap_or_default();
+    let (Ok(mut writer), Ok(reply)) = (connection.try_clone(), connection.try_clone()) else {
+        return;
+    };
+    let _ = writer.set_write_timeout(WRITE_TIMEOUT);
+    if writeln!(writer, "{}", hello).is_err() {
+        return;
+    }
+    if let Ok(mut clients) = shared.clients.lock() {
+        clients.push(writer);
+    }
+
+    let requests = requests.clone();
+    thread::spawn(move || {
+        for line in BufReader::new(connection).lines() {
+            let (Ok(line), Ok(writer)) = (line, reply.try_clone()) else {
+                break;
+            };
+            if !line.trim().is_empty() && requests.send((line, writer)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+enum Request {
+    A4(f32),
+    Algorithm(String),
+    Status,
+}
+
+fn parse_request(line: &str) -> Result<Request, String> {
+    let mut words = line.split_whitespace();
+    match (words.next(), words.next()) {
+        (Some("a4"), Some(hz)) => hz
+            .parse::<f32>()
+            .ok()
+            .filter(|hz| (432.0..=450.0).contains(hz))
+            .map(Request::A4)
+            .ok_or_else(|| "a4 must be between 432 and 450".to_string()),
+        (Some("algorithm"), Some(name)) => Ok(Request::Algorithm(name.to_string())),
+        (Some("status"), None) => Ok(Request::Status),
+        _ => Err(format!("unknown request `{}` (expected a4 HZ, algorithm NAME, or status)", line.trim())),
+    }
+}
+
+/// One analysis as a `reading` line; `freq`, `note`, `octave`, and `cents`
+/// are `null` without a pitch, and each partial is `[number, Hz, cents]`.
+fn reading_json(analysis: &Analysis, a4_freq: f32) -> String {
+    let (freq, note, octave, cents) = match analysis.freq {
+        Some(freq) => {
+            let (note, octave, cents) = Tuner::nearest_note(freq, a4_freq);
+            (format!("{:.3}", freq), format!("\"{}\"", note), octave.to_string(), format!("{:.2}", cents))
+        }
+        None => ("null".to_string(), "null".to_string(), "null".to_string(), "null".to_string()),
+    };
+    let partials: Vec<String> = analysis
+        .partials
+        .iter()
+        .map(|p| format!("[{},{:.3},{:.2}]", p.number, p.freq, p.deviation_cents))
+        .collect();
+    format!(
+        "{{\"type\":\"reading\",\"freq\":{},\"note\":{},\"octave\":{},\"cents\":{},\"partials\":[{}],\"elapsed_us\":{}}}",
+        freq,
+        note,
+        octave,
+        cents,
+        partials.join(","),
+        analysis.elapsed.as_micros()
+    )
+}
+
+/// A daemon connection for the terminal UI, which then shows the daemon's
+/// readings instead of capturing audio itself.
+pub struct Remote {
+    address: Address,
+    reader: BufReader<Connection>,
+    status: AnalysisStatus,
+}
+
+/// Connects to the daemon at `spec` (see [`Address::parse`]) and reads its greeting.
+pub fn connect(spec: &str) -> Result<Remote, String> {
+    let address = Address::parse(spec);
+    let connection = address
+        .connect()
+        .map_err(|e| format!("Failed to connect to the tuner daemon at {}: {}", address, e))?;
+    let mut reader = BufReader::new(connection);
+    let status = read_hello(&mut reader).map_err(|e| format!("{}: {}", address, e))?;
+    Ok(Remote { address, reader, status })
+}
+
+fn read_hello(reader: &mut impl BufRead) -> Result<AnalysisStatus, String> {
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|e| e.to_string())?;
+    if field(&line, "type") != Some("\"hello\"") {
+        return Err("not a tuner daemon".to_string());
+    }
+    let device = field(&line, "device").map(unquote).unwrap_or_default();
+    let sample_rate = field(&line, "sample_rate").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let algorithm = match field(&line, "algorithm").map(unquote).as_deref() {
+        Some("YIN") => "YIN",
+        _ => "FFT peak",
+    };
+    let window = field(&line, "window_ms").and_then(|v| v.parse::<f64>().ok()).unwrap_or(0.0);
+    Ok(AnalysisStatus::new(device, sample_rate, algorithm, Duration::from_secs_f64(window / 1000.0)))
+}
+
+impl Remote {
+    /// Feeds readings to the UI from a background thread, reconnecting
+    /// whenever the daemon goes away. Also returns the daemon's input and
+    /// analysis settings for the status bar.
+    pub fn spawn(self, notices: Sender<Notice>) -> (Worker, AnalysisStatus) {
+        let (results_tx, results) = crossbeam_channel::bounded(RESULTS);
+        let Remote { address, mut reader, status } = self;
+        thread::spawn(move || loop {
+            for line in (&mut reader).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if field(&line, "type") != Some("\"reading\"") {
+                    continue;
+                }
+                if let Err(crossbeam_channel::TrySendError::Disconnected(_)) = results_tx.try_send(parse_reading(&line)) {
+                    return;
+                }
+            }
+
+            log::warn!(target: "daemon", "Lost the connection to {}", address);
+            let _ = notices.send(Notice::warning(i18n::trf("notice.daemon_lost", &[&address])));
+            reader = loop {
+                thread::sleep(RECONNECT_EVERY);
+                let Ok(connection) = address.connect() else {
+                    continue;
+                };
+                let mut reader = BufReader::new(connection);
+                if read_hello(&mut reader).is_ok() {
+                    break reader;
+                }
+            };
+            let _ = notices.send(Notice::info(i18n::trf("notice.daemon_back", &[&address])));
+        });
+        (Worker::remote(results), status)
+    }
+}
+
+fn parse_reading(line: &str) -> Analysis {
+    let partials = field(line, "partials")
+        .map(|list| {
+            list.trim_matches(|c| c == '[' || c == ']')
+                .split("],[")
+                .filter_map(|partial| {
+                    let mut values = partial.split(',').map(str::parse::<f32>);
+                    match (values.next(), values.next(), values.next()) {
+                        (Some(Ok(number)), Some(Ok(freq)), Some(Ok(deviation_cents))) => Some(Partial {
+                            number: number as usize,
+                            freq,
+                            deviation_cents,
+                        }),
+                        _ => None,
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Analysis {
+        samples: Vec::new(),
+        freq: field(line, "freq").and_then(|v| v.parse().ok()),
+        spectrum: Vec::new(),
+        partials,
+        elapsed: Duration::from_micros(field(line, "elapsed_us").and_then(|v| v.parse().ok()).unwrap_or(0)),
+    }
+}
+
+/// The raw text of `key`'s value in one of the daemon's own JSON lines,
+/// which are flat apart from nested number arrays.
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let start = line.find(&format!("\"{}\":", key))? + key.len() + 3;
+    let rest = &line[start..];
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in rest.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '[' if !in_string => depth += 1,
+            ']' if !in_string && depth > 0 => depth -= 1,
+            ',' | '}' if !in_string && depth == 0 => return Some(&rest[..i]),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn unquote(value: &str) -> String {
+    value.trim_matches('"').replace("\\\"", "\"").replace("\\\\", "\\")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{field, parse_reading, parse_request, unquote, Address, Request};
+
+    #[test]
+    fn requests_parse() {
+        assert!(matches!(parse_request("a4 442"), Ok(Request::A4(hz)) if hz == 442.0));
+        assert!(matches!(parse_request("  a4   432.5\n"), Ok(Request::A4(hz)) if hz == 432.5));
+        assert!(matches!(parse_request("algorithm yin"), Ok(Request::Algorithm(name)) if name == "yin"));
+        assert!(matches!(parse_request("status"), Ok(Request::Status)));
+    }
+
+    #[test]
+    fn malformed_requests_are_errors() {
+        for line in ["", "a4", "a4 500", "a4 sharp", "algorithm", "status now", "STATUS", "tune E2"] {
+            assert!(parse_request(line).is_err(), "{:?}", line);
+        }
+        assert!(parse_request("a4 400").err().is_some_and(|e| e.contains("between 432 and 450")));
+    }
+
+    #[test]
+    fn fields_skip_escaped_quotes_and_nested_arrays() {
+        let line = concat!(
+            r#"{"type":"hello","device":"Mic \"USB\", left","rate":48000,"#,
+            r#""partials":[[1,110.0,0.5],[2,220.1,1.2]],"x":null}"#
+        );
+        assert_eq!(field(line, "type"), Some(r#""hello""#));
+        assert_eq!(field(line, "device").map(unquote).as_deref(), Some(r#"Mic "USB", left"#));
+        assert_eq!(field(line, "rate"), Some("48000"));
+        assert_eq!(field(line, "partials"), Some("[[1,110.0,0.5],[2,220.1,1.2]]"));
+        assert_eq!(field(line, "x"), Some("null"));
+        assert_eq!(field(line, "missing"), None);
+        assert_eq!(field(r#"{"open":"never closed"#, "open"), None);
+        assert_eq!(unquote(r#""back\\slash""#), r"back\slash");
+    }
+
+    #[test]
+    fn readings_parse_with_and_without_a_pitch() {
+        let line = r#"{"type":"reading","freq":110.250,"partials":[[1,110.2,0.3],[2,220.6,1.1]],"elapsed_us":850}"#;
+        let reading = parse_reading(line);
+        assert_eq!(reading.freq, Some(110.25));
+        assert_eq!(reading.partials.len(), 2);
+        assert_eq!((reading.partials[1].number, reading.partials[1].freq), (2, 220.6));
+        assert_eq!(reading.elapsed.as_micros(), 850);
+
+        let silent = parse_reading(r#"{"type":"reading","freq":null,"partials":[],"elapsed_us":40}"#);
+        assert_eq!(silent.freq, None);
+        assert!(silent.partials.is_empty());
+    }
+
+    #[test]
+    fn addresses_parse() {
+        assert!(matches!(Address::parse("8766"), Address::Tcp(spec) if spec == "127.0.0.1:8766"));
+        assert!(matches!(Address::parse("0.0.0.0:8766"), Address::Tcp(spec) if spec == "0.0.0.0:8766"));
+        #[cfg(unix)]
+        assert!(matches!(Address::parse("/tmp/tuner.sock"), Address::Unix(path) if path.ends_with("tuner.sock")));
+    }
+}
@@ -0,0 +1,38 @@
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Shows a desktop notification through the platform's own tool:
+/// `notify-send` on Linux and the BSDs, `osascript` on macOS. The tool runs in
+/// the background; an error means it could not be started.
+pub fn notify(summary: &str, body: &str) -> Result<(), String> {
+    let mut command = command(summary, body)?;
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run {:?} for a desktop notification: {}", command.get_program(), e))?;
+    // Reaped off the main loop, which must not wait on a notification daemon.
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn command(summary: &str, body: &str) -> Result<Command, String> {
+    let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut command = Command::new("osascript");
+    command.arg("-e").arg(format!("display notification {} with title {}", quote(body), quote(summary)));
+    Ok(command)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn command(summary: &str, body: &str) -> Result<Command, String> {
+    let mut command = Command::new("notify-send");
+    command.args(["--app-name=Guitar Tuner", "--expire-time=4000", summary, body]);
+    Ok(command)
+}
+
+#[cfg(not(unix))]
+fn command(_summary: &str, _body: &str) -> Result<Command, String> {
+    Err("Desktop notifications are not supported on this platform".to_string())
+}
@@ -0,0 +1,113 @@
+/// A pitch detection algorithm. [`Tuner`](crate::Tuner) runs the built-in
+/// FFT peak picker by default; any other implementation can be plugged in
+/// with [`Tuner::set_detector`](crate::Tuner::set_detector) while the tuner
+/// keeps computing the spectrum for display, partials, and confidence.
+pub trait PitchDetector: Send {
+    /// Shown in the status bar, e.g. `"YIN"`.
+    fn name(&self) -> &'static str;
+
+    /// Samples looked at by one call to `detect`.
+    fn window_size(&self) -> usize;
+
+    /// Fundamental frequency in Hz of the first `window_size` samples, or
+    /// `None` for silence, noise, or too few samples.
+    fn detect(&mut self, samples: &[f32]) -> Option<f32>;
+}
+
+/// Names accepted by [`Tuner::set_algorithm`](crate::Tuner::set_algorithm)
+/// and `[tuner] algorithm`.
+pub const BUILTIN: [&str; 2] = ["fft", "yin"];
+
+/// Name the built-in YIN detector reports.
+pub(crate) const YIN: &str = "YIN";
+const YIN_WINDOW: usize = 4096;
+const YIN_THRESHOLD: f32 = 0.15;
+const YIN_MIN_FREQ: f32 = 20.0;
+const YIN_MAX_FREQ: f32 = 5000.0;
+/// Below this RMS level a window counts as silence.
+const YIN_MIN_RMS: f32 = 1e-4;
+
+/// The YIN autocorrelation method (de Cheveigné and Kawahara, 2002), which
+/// tracks the fundamental of harmonic-rich or weak-fundamental tones better
+/// than an FFT peak.
+pub struct Yin {
+    sample_rate: u32,
+    window: usize,
+    min_freq: f32,
+    max_freq: f32,
+    difference: Vec<f32>,
+}
+
+impl Yin {
+    pub fn new(sample_rate: u32) -> Self {
+        Self::with_range(sample_rate, YIN_WINDOW, YIN_MIN_FREQ, YIN_MAX_FREQ)
+    }
+
+    /// Looks at `window` samples per detection instead of 4096, for notes
+    /// between `min_freq` and `max_freq` rather than 20 Hz and 5 kHz.
+    pub fn with_range(sample_rate: u32, window: usize, min_freq: f32, max_freq: f32) -> Self {
+        Yin {
+            sample_rate,
+            window,
+            min_freq,
+            max_freq,
+            difference: Vec::with_capacity(window / 2),
+        }
+    }
+}
+
+impl PitchDetector for Yin {
+    fn name(&self) -> &'static str {
+        YIN
+    }
+
+    fn window_size(&self) -> usize {
+        self.window
+    }
+
+    fn detect(&mut self, samples: &[f32]) -> Option<f32> {
+        if samples.len() < self.window {
+            return None;
+        }
+        let samples = &samples[..self.window];
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / self.window as f32).sqrt();
+        if rms < YIN_MIN_RMS {
+            return None;
+        }
+
+        let sample_rate = self.sample_rate as f32;
+        let max_lag = ((sample_rate / self.min_freq) as usize).min(self.window / 2);
+        let min_lag = ((sample_rate / self.max_freq) as usize).max(2);
+        let span = self.window - max_lag;
+
+        // Cumulative mean normalized difference function.
+        self.difference.clear();
+        self.difference.push(1.0);
+        let mut running_sum = 0.0;
+        for lag in 1..max_lag {
+            let diff: f32 = samples[..span]
+                .iter()
+                .zip(&samples[lag..lag + span])
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum();
+            running_sum += diff;
+            self.difference.push(if running_sum > 0.0 { diff * lag as f32 / running_sum } else { 1.0 });
+        }
+
+        let mut lag = min_lag;
+        while lag < max_lag - 1 && self.difference[lag] >= YIN_THRESHOLD {
+            lag += 1;
+        }
+        if lag >= max_lag - 1 {
+            return None;
+        }
+        while lag + 1 < max_lag - 1 && self.difference[lag + 1] < self.difference[lag] {
+            lag += 1;
+        }
+
+        let (prev, curr, next) = (self.difference[lag - 1], self.difference[lag], self.difference[lag + 1]);
+        let denom = prev - 2.0 * curr + next;
+        let offset = if denom.abs() > 1e-10 { (0.5 * (prev - next) / denom).clamp(-1.0, 1.0) } else { 0.0 };
+        Some(sample_rate / (lag as f32 + offset))
+    }
+}
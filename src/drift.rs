@@ -0,0 +1,218 @@
+use crate::json::json_escape;
+
+/// Readings further than this from the target belong to another string.
+pub const MAX_CENTS: f32 = 100.0;
+/// A string whose every gap moved the same way by more than this is
+/// reported as consistently flat or sharp.
+const CONSISTENT_CENTS: f32 = 3.0;
+
+/// A stored reading near its target, as the history reads it back.
+pub struct Reading {
+    pub instrument: String,
+    pub target: String,
+    pub session: i64,
+    pub at: String,
+    /// `at` as a Julian day number, for the days between sessions.
+    pub day: f64,
+    pub cents: f32,
+}
+
+/// How one string arrived in a session compared with how it was left at the
+/// end of the previous one.
+pub struct Gap {
+    /// Date the string was last left at `left_cents`, and when it next arrived.
+    pub from: String,
+    pub to: String,
+    pub days: f64,
+    pub left_cents: f32,
+    pub arrived_cents: f32,
+}
+
+impl Gap {
+    pub fn drift(&self) -> f32 {
+        self.arrived_cents - self.left_cents
+    }
+}
+
+/// Every gap of one string of one instrument, in order.
+pub struct StringDrift {
+    pub instrument: String,
+    pub target: String,
+    pub sessions: usize,
+    pub gaps: Vec<Gap>,
+}
+
+impl StringDrift {
+    pub fn mean_drift(&self) -> Option<f32> {
+        (!self.gaps.is_empty()).then(|| self.gaps.iter().map(Gap::drift).sum::<f32>() / self.gaps.len() as f32)
+    }
+
+    pub fn mean_days(&self) -> Option<f64> {
+        (!self.gaps.is_empty()).then(|| self.gaps.iter().map(|gap| gap.days).sum::<f64>() / self.gaps.len() as f64)
+    }
+
+    /// With two or more gaps, whether every one drifted the same way by
+    /// more than `CONSISTENT_CENTS`.
+    pub fn consistent(&self) -> bool {
+        self.gaps.len() >= 2
+            && (self.gaps.iter().all(|gap| gap.drift() < -CONSISTENT_CENTS)
+                || self.gaps.iter().all(|gap| gap.drift() > CONSISTENT_CENTS))
+    }
+}
+
+/// Groups `readings`, sorted by instrument, target, and time, into each
+/// string's gaps between consecutive sessions: its last reading in one
+/// session against its first in the next.
+pub fn strings(readings: &[Reading]) -> Vec<StringDrift> {
+    let mut strings: Vec<StringDrift> = Vec::new();
+    // The first and last reading of the string in the session being read.
+    let mut visit: Option<(&Reading, &Reading)> = None;
+    let mut previous: Option<&Reading> = None;
+
+    for reading in readings {
+        let same_string = strings
+            .last()
+            .is_some_and(|string| string.instrument == reading.instrument && string.target == reading.target);
+        if !same_string {
+            close(&mut strings, visit.take(), &mut previous);
+            previous = None;
+            strings.push(StringDrift {
+                instrument: reading.instrument.clone(),
+                target: reading.target.clone(),
+                sessions: 0,
+                gaps: Vec::new(),
+            });
+        }
+        visit = match visit {
+            Some((first, _)) if first.session == reading.session => Some((first, reading)),
+            other => {
+                close(&mut strings, other, &mut previous);
+                Some((reading, reading))
+            }
+        };
+    }
+    close(&mut strings, visit, &mut previous);
+    strings
+}
+
+/// Ends one session's visit to the last string: counts it and records the
+/// gap from the visit before.
+fn close<'a>(strings: &mut [StringDrift], visit: Option<(&'a Reading, &'a Reading)>, previous: &mut Option<&'a Reading>) {
+    let (Some(string), Some((first, last))) = (strings.last_mut(), visit) else {
+        return;
+    };
+    string.sessions += 1;
+    if let Some(left) = previous {
+        string.gaps.push(Gap {
+            from: date(&left.at),
+            to: date(&first.at),
+            days: first.day - left.day,
+            left_cents: left.cents,
+            arrived_cents: first.cents,
+        });
+    }
+    *previous = Some(last);
+}
+
+fn date(stamp: &str) -> String {
+    stamp.get(..10).unwrap_or(stamp).to_string()
+}
+
+fn direction(cents: f32) -> &'static str {
+    if cents < 0.0 {
+        "flat"
+    } else {
+        "sharp"
+    }
+}
+
+pub fn print_text(strings: &[StringDrift]) {
+    if strings.iter().all(|string| string.gaps.is_empty()) {
+        println!("No string has readings from two sessions yet.");
+        return;
+    }
+    let mut instrument = "";
+    for string in strings.iter().filter(|string| !string.gaps.is_empty()) {
+        if string.instrument != instrument {
+            if !instrument.is_empty() {
+                println!();
+            }
+            instrument = &string.instrument;
+            println!("{}:", instrument);
+        }
+        let (Some(drift), Some(days)) = (string.mean_drift(), string.mean_days()) else {
+            continue;
+        };
+        let summary = if string.consistent() {
+            format!("consistently {:.1}c {} after {:.1} days", drift.abs(), direction(drift), days)
+        } else {
+            format!("{:+.1}c on average after {:.1} days", drift, days)
+        };
+        println!("  {:<4} {}  ({} gaps in {} sessions)", string.target, summary, string.gaps.len(), string.sessions);
+        for gap in &string.gaps {
+            println!(
+                "         {} left {:+.1}c, {} arrived {:+.1}c: {:+.1}c in {:.1} days",
+                gap.from,
+                gap.left_cents,
+                gap.to,
+                gap.arrived_cents,
+                gap.drift(),
+                gap.days
+            );
+        }
+    }
+}
+
+pub fn print_json(strings: &[StringDrift]) {
+    let number = |value: Option<f32>| value.map_or("null".to_string(), |value| format!("{:.2}", value));
+    let objects: Vec<String> = strings
+        .iter()
+        .map(|s| {
+            let gaps: Vec<String> = s
+                .gaps
+                .iter()
+                .map(|g| {
+                    format!(
+                        "{{\"from\":\"{}\",\"to\":\"{}\",\"days\":{:.2},\"left_cents\":{:.2},\"arrived_cents\":{:.2}}}",
+                        g.from, g.to, g.days, g.left_cents, g.arrived_cents
+                    )
+                })
+                .collect();
+            format!(
+                "  {{\"instrument\":\"{}\",\"target\":\"{}\",\"sessions\":{},\"mean_drift_cents\":{},\
+                 \"mean_days\":{},\"consistent\":{},\"gaps\":[{}]}}",
+                json_escape(&s.instrument),
+                s.target,
+                s.sessions,
+                number(s.mean_drift()),
+                s.mean_days().map_or("null".to_string(), |days| format!("{:.2}", days)),
+                s.consistent(),
+                gaps.join(",")
+            )
+        })
+        .collect();
+    if objects.is_empty() {
+        println!("[]");
+    } else {
+        println!("[\n{}\n]", objects.join(",\n"));
+    }
+}
+
+pub fn print_csv(strings: &[StringDrift]) {
+    println!("instrument,target,from,to,days,left_cents,arrived_cents,drift_cents");
+    for string in strings {
+        for gap in &string.gaps {
+            println!(
+                "\"{}\",{},{},{},{:.2},{:.2},{:.2},{:.2}",
+                string.instrument.replace('"', "\"\""),
+                string.target,
+                gap.from,
+                gap.to,
+                gap.days,
+                gap.left_cents,
+                gap.arrived_cents,
+                gap.drift()
+            );
+        }
+    }
+}
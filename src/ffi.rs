@@ -0,0 +1,257 @@
+//! C API around [`Tuner`], enabled with the `ffi` feature and always built
+//! for wasm32. See `include/guitar_tuner.h` for the matching declarations.
+
+use std::alloc::{self, Layout};
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+use std::slice;
+
+use crate::detector::PitchDetector;
+use crate::tuner::Tuner;
+
+const NOTES: [&str; 12] = ["A", "A#", "B", "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#"];
+
+/// Opaque handle owning a detector and its sample buffer.
+pub struct GtTuner {
+    tuner: Tuner,
+    buffer: Vec<f32>,
+    pitch: Option<f32>,
+    confidence: f32,
+}
+
+/// The note nearest to a frequency: a NUL-terminated name such as `"A#"`,
+/// its octave, and the deviation from it in cents.
+#[repr(C)]
+pub struct GtNote {
+    pub name: [c_char; 4],
+    pub octave: c_int,
+    pub cents: f32,
+}
+
+/// Allocates `size` bytes inside the library, for callers such as JavaScript
+/// that cannot hand over their own memory. Release with `gt_free`.
+#[no_mangle]
+pub extern "C" fn gt_alloc(size: usize) -> *mut u8 {
+    match Layout::from_size_align(size.max(1), 8) {
+        // SAFETY: the layout has a non-zero size.
+        Ok(layout) => unsafe { alloc::alloc(layout) },
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// # Safety
+///
+/// `ptr` must come from `gt_alloc(size)` with the same `size`, or be null.
+#[no_mangle]
+pub unsafe extern "C" fn gt_free(ptr: *mut u8, size: usize) {
+    if let (false, Ok(layout)) = (ptr.is_null(), Layout::from_size_align(size.max(1), 8)) {
+        alloc::dealloc(ptr, layout);
+    }
+}
+
+/// Creates a detector for mono audio at `sample_rate` Hz. Free it with
+/// `gt_tuner_free`.
+#[no_mangle]
+pub extern "C" fn gt_tuner_new(sample_rate: u32) -> *mut GtTuner {
+    if sample_rate == 0 {
+        return std::ptr::null_mut();
+    }
+    let tuner = Tuner::new(sample_rate);
+    Box::into_raw(Box::new(GtTuner {
+        buffer: Vec::with_capacity(tuner.window_size() * 2),
+        tuner,
+        pitch: None,
+        confidence: 0.0,
+    }))
+}
+
+/// # Safety
+///
+/// `tuner` must come from `gt_tuner_new` and not have been freed, or be null.
+#[no_mangle]
+pub unsafe extern "C" fn gt_tuner_free(tuner: *mut GtTuner) {
+    if !tuner.is_null() {
+        drop(Box::from_raw(tuner));
+    }
+}
+
+/// Appends `len` mono samples and detects on the newest full window. Windows
+/// start one hop apart, as in the terminal app, so however the samples are
+/// chunked the pitch is that of the latest window due. Returns 1 when a
+/// detection ran, 0 when more samples are needed, and -1 on a null argument.
+///
+/// # Safety
+///
+/// `tuner` must be a live handle and `samples` must point to `len` floats.
+#[no_mangle]
+pub unsafe extern "C" fn gt_tuner_feed(tuner: *mut GtTuner, samples: *const f32, len: usize) -> c_int {
+    let (Some(handle), false) = (tuner.as_mut(), samples.is_null()) else {
+        return -1;
+    };
+
+    handle.buffer.extend_from_slice(slice::from_raw_parts(samples, len));
+    let (window, hop) = (handle.tuner.window_size(), handle.tuner.hop().max(1));
+    if handle.buffer.len() < window {
+        return 0;
+    }
+
+    // Windows due before the newest would only be overwritten, so skip them.
+    let skipped = (handle.buffer.len() - window) / hop * hop;
+    handle.buffer.drain(..skipped);
+    handle.pitch = handle.tuner.detect_frequency(&handle.buffer[..window]);
+    handle.confidence = handle.pitch.map_or(0.0, |freq| handle.tuner.confidence(freq));
+    handle.buffer.drain(..hop);
+    1
+}
+
+/// Selects a built-in algorithm by NUL-terminated name (`"fft"` or `"yin"`).
+/// Returns 0 on success and -1 on a null argument or unknown name.
+///
+/// # Safety
+///
+/// `tuner` must be a live handle and `name` a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn gt_tuner_set_algorithm(tuner: *mut GtTuner, name: *const c_char) -> c_int {
+    let (Some(handle), false) = (tuner.as_mut(), name.is_null()) else {
+        return -1;
+    };
+    match CStr::from_ptr(name).to_str().map(|name| handle.tuner.set_algorithm(name)) {
+        Ok(Ok(())) => 0,
+        _ => -1,
+    }
+}
+
+/// Runs one detection on exactly the given samples, bypassing the feed
+/// buffer, and returns the frequency in Hz or 0. At least 4096 samples are
+/// needed.
+///
+/// # Safety
+///
+/// `tuner` must be a live handle and `samples` must point to `len` floats.
+#[no_mangle]
+pub unsafe extern "C" fn gt_tuner_detect(tuner: *mut GtTuner, samples: *const f32, len: usize) -> f32 {
+    let (Some(handle), false) = (tuner.as_mut(), samples.is_null()) else {
+        return 0.0;
+    };
+    let samples = slice::from_raw_parts(samples, len);
+    handle.pitch = handle.tuner.detect_frequency(samples);
+    handle.confidence = handle.pitch.map_or(0.0, |freq| handle.tuner.confidence(freq));
+    handle.pitch.unwrap_or(0.0)
+}
+
+/// The frequency found by the last detection in Hz, or 0 if there was none.
+///
+/// # Safety
+///
+/// `tuner` must be a live handle or null.
+#[no_mangle]
+pub unsafe extern "C" fn gt_tuner_pitch(tuner: *const GtTuner) -> f32 {
+    tuner.as_ref().and_then(|handle| handle.pitch).unwrap_or(0.0)
+}
+
+/// Share of the last spectrum's energy on the detected pitch's harmonics, 0-1.
+///
+/// # Safety
+///
+/// `tuner` must be a live handle or null.
+#[no_mangle]
+pub unsafe extern "C" fn gt_tuner_confidence(tuner: *const GtTuner) -> f32 {
+    tuner.as_ref().map_or(0.0, |handle| handle.confidence)
+}
+
+/// Fills `out` with the note nearest to `freq` for the reference `a4_freq`.
+/// Returns 0 on success and -1 on a null argument or non-positive frequency.
+///
+/// # Safety
+///
+/// `tuner` must be a live handle and `out` must point to a writable `GtNote`.
+#[no_mangle]
+pub unsafe extern "C" fn gt_tuner_note(tuner: *const GtTuner, freq: f32, a4_freq: f32, out: *mut GtNote) -> c_int {
+    let (Some(handle), Some(out)) = (tuner.as_ref(), out.as_mut()) else {
+        return -1;
+    };
+    if freq <= 0.0 || a4_freq <= 0.0 {
+        return -1;
+    }
+
+    let (name, octave, cents) = handle.tuner.frequency_to_note(freq, a4_freq);
+    out.name = [0; 4];
+    for (slot, byte) in out.name.iter_mut().zip(name.bytes().take(3)) {
+        *slot = byte as c_char;
+    }
+    out.octave = octave;
+    out.cents = cents;
+    0
+}
+
+/// Frequency in Hz of a note such as `"A"`, 4 for the reference `a4_freq`,
+/// or 0 for a null or unknown name.
+///
+/// # Safety
+///
+/// `name` must be a NUL-terminated string or null.
+#[no_mangle]
+pub unsafe extern "C" fn gt_note_frequency(name: *const c_char, octave: c_int, a4_freq: f32) -> f32 {
+    if name.is_null() {
+        return 0.0;
+    }
+    match CStr::from_ptr(name).to_str() {
+        Ok(name) if NOTES.contains(&name) => Tuner::note_name_to_frequency(name, octave, a4_freq),
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gt_tuner_feed, gt_tuner_free, gt_tuner_new, gt_tuner_pitch};
+    use crate::signal::{self, cents_off};
+
+    const RATE: u32 = 48_000;
+    const WINDOW: usize = 4096;
+    const HOP: usize = 2048;
+    /// The FFT's bins are wide, so these only check the note is right.
+    const CENTS: f32 = 50.0;
+
+    fn feed(tuner: *mut super::GtTuner, samples: &[f32]) -> i32 {
+        // SAFETY: `tuner` is live for the test and `samples` is a valid slice.
+        unsafe { gt_tuner_feed(tuner, samples.as_ptr(), samples.len()) }
+    }
+
+    fn pitch(tuner: *mut super::GtTuner) -> f32 {
+        // SAFETY: `tuner` is live for the test.
+        unsafe { gt_tuner_pitch(tuner) }
+    }
+
+    #[test]
+    fn small_chunks_detect_once_per_hop() {
+        let tuner = gt_tuner_new(RATE);
+        let samples = signal::sine(110.0, 0.5, RATE, WINDOW + 10 * HOP);
+        let ran: i32 = samples.chunks(256).map(|chunk| feed(tuner, chunk)).sum();
+        assert_eq!(ran, 11);
+        assert!(cents_off(pitch(tuner), 110.0).abs() < CENTS, "{} Hz", pitch(tuner));
+        // SAFETY: `tuner` came from `gt_tuner_new` and is not used again.
+        unsafe { gt_tuner_free(tuner) };
+    }
+
+    #[test]
+    fn a_full_window_detects_and_one_sample_less_does_not() {
+        let tuner = gt_tuner_new(RATE);
+        let samples = signal::sine(196.0, 0.5, RATE, WINDOW);
+        assert_eq!(feed(tuner, &samples[..WINDOW - 1]), 0);
+        assert_eq!(feed(tuner, &samples[WINDOW - 1..]), 1);
+        assert!(cents_off(pitch(tuner), 196.0).abs() < CENTS, "{} Hz", pitch(tuner));
+        // SAFETY: `tuner` came from `gt_tuner_new` and is not used again.
+        unsafe { gt_tuner_free(tuner) };
+    }
+
+    #[test]
+    fn a_large_feed_reports_its_newest_window() {
+        let tuner = gt_tuner_new(RATE);
+        let mut samples = signal::sine(110.0, 0.5, RATE, RATE as usize);
+        samples.extend(signal::sine(329.63, 0.5, RATE, RATE as usize));
+        assert_eq!(feed(tuner, &samples), 1);
+        assert!(cents_off(pitch(tuner), 329.63).abs() < CENTS, "{} Hz", pitch(tuner));
+        // SAFETY: `tuner` came from `gt_tuner_new` and is not used again.
+        unsafe { gt_tuner_free(tuner) };
+    }
+}
@@ -0,0 +1,109 @@
+use std::io::{self, IsTerminal, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use cpal::SampleRate;
+
+use crate::analysis::{Setup, Windows};
+use crate::audio::{self, AudioCapture};
+use crate::config::Config;
+use crate::replay::{Event, Recorder};
+use crate::tuner::{Register, Tuner};
+
+/// What `run` writes for each reading.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Output {
+    /// One JSON object per detected pitch.
+    Json,
+    /// One plain line such as `A2 +0.8c 110.05 Hz` whenever the reading
+    /// changes, and `-` when the signal stops. On a terminal the line is
+    /// rewritten in place; piped, each reading gets its own line, for status
+    /// bars, `watch`, and `tee`.
+    Plain,
+}
+
+/// Runs without a terminal UI, writing readings to stdout until the input
+/// stream ends or stdout is closed.
+pub fn run(config: &Config, output: Output, mut recorder: Option<Recorder>) -> Result<(), Box<dyn std::error::Error>> {
+    let a4_freq = config.get_f64("tuner", "a4").map_or(440.0, |a4| (a4 as f32).clamp(432.0, 450.0));
+
+    let audio_capture = match config.get_str("audio", "input_device") {
+        Some(name) => AudioCapture::with_device(name)?,
+        None => AudioCapture::new()?,
+    };
+    let sample_rate = audio_capture.sample_rate();
+    let (tx, rx) = audio::frame_channel(audio::FRAME_QUEUE);
+    let (notice_tx, notice_rx) = crossbeam_channel::unbounded();
+    let _stream = audio_capture.start_capture(SampleRate(sample_rate), tx, notice_tx)?;
+
+    let mut tuner = Setup::from_config(config).tuner(sample_rate, Register::Normal)?;
+    if let Some(recorder) = &mut recorder {
+        recorder.record(&Event::Start(sample_rate))?;
+    }
+    let mut windows = Windows::new();
+    let mut stdout = io::stdout().lock();
+    let in_place = output == Output::Plain && stdout.is_terminal();
+    let mut last_line = String::new();
+
+    'capture: while let Ok(samples) = rx.recv() {
+        for notice in notice_rx.try_iter() {
+            eprintln!("{}", notice.text);
+        }
+
+        let analyses = windows.push(&mut tuner, &samples);
+        if let Some(recorder) = &mut recorder {
+            recorder.record(&Event::Frame(samples))?;
+        }
+        for analysis in analyses {
+            let line = match (output, analysis.freq) {
+                (Output::Json, Some(freq)) => {
+                    let (note, octave, cents) = tuner.frequency_to_note(freq, a4_freq);
+                    format!(
+                        "{{\"timestamp\":{:.3},\"freq\":{:.3},\"note\":\"{}\",\"octave\":{},\"cents\":{:.2},\"confidence\":{:.3}}}",
+                        unix_time(),
+                        freq,
+                        note,
+                        octave,
+                        cents,
+                        tuner.confidence(freq)
+                    )
+                }
+                (Output::Json, None) => continue,
+                (Output::Plain, freq) => {
+                    let line = plain_line(&tuner, freq, a4_freq);
+                    if line == last_line {
+                        continue;
+                    }
+                    last_line.clone_from(&line);
+                    line
+                }
+            };
+            let written = if in_place {
+                write!(stdout, "\r{}\x1b[K", line)
+            } else {
+                writeln!(stdout, "{}", line)
+            };
+            if written.and_then(|_| stdout.flush()).is_err() {
+                break 'capture;
+            }
+        }
+    }
+    if in_place {
+        writeln!(stdout)?;
+    }
+
+    Ok(())
+}
+
+fn plain_line(tuner: &Tuner, freq: Option<f32>, a4_freq: f32) -> String {
+    match freq {
+        Some(freq) => {
+            let (note, octave, cents) = tuner.frequency_to_note(freq, a4_freq);
+            format!("{}{} {:+.1}c {:.2} Hz", note, octave, cents, freq)
+        }
+        None => "-".to_string(),
+    }
+}
+
+fn unix_time() -> f64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0.0, |d| d.as_secs_f64())
+}
@@ -0,0 +1,438 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::config::{self, Config};
+use crate::csvlog::utc_timestamp;
+use crate::drift;
+use crate::json::json_escape;
+use crate::presets;
+use crate::sqlite::{Column, Connection, Param};
+use crate::stats;
+use crate::ui::UiState;
+
+const HISTORY_FILE: &str = "history.sqlite3";
+/// Whether this build links SQLite, so a history can be kept at all.
+pub const AVAILABLE: bool = cfg!(feature = "history");
+/// A held note is stored at most this often, so an hour of practice is a few
+/// thousand rows rather than one per analysis window.
+const RECORD_INTERVAL: Duration = Duration::from_secs(1);
+
+const SCHEMA: [&str; 3] = [
+    "CREATE TABLE IF NOT EXISTS sessions (
+        id INTEGER PRIMARY KEY,
+        started TEXT NOT NULL,
+        ended TEXT,
+        seconds REAL,
+        instrument TEXT NOT NULL,
+        a4 REAL NOT NULL,
+        notes INTEGER,
+        in_tune_percent REAL,
+        average_cents REAL
+    )",
+    "CREATE TABLE IF NOT EXISTS readings (
+        session INTEGER NOT NULL REFERENCES sessions(id),
+        at TEXT NOT NULL,
+        instrument TEXT NOT NULL,
+        target TEXT NOT NULL,
+        note TEXT NOT NULL,
+        frequency REAL NOT NULL,
+        cents REAL NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS readings_by_session ON readings(session)",
+];
+
+/// Where the history is kept: `[history] file`, or `history.sqlite3` in the
+/// platform data directory (`~/.local/share/rust_tuner` on Linux).
+fn path(config: &Config) -> Result<PathBuf, String> {
+    match config.get_str("history", "file") {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => data_dir().map(|dir| dir.join(HISTORY_FILE)).ok_or_else(|| "No directory for the history".to_string()),
+    }
+}
+
+fn data_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "linux") {
+        std::env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+            .map(|dir| dir.join(config::APP_DIR))
+    } else {
+        // macOS and Windows keep application data beside the configuration.
+        config::config_dir()
+    }
+}
+
+fn open(config: &Config) -> Result<Connection, String> {
+    let path = path(config)?;
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let db = Connection::open(&path)?;
+    for statement in SCHEMA {
+        db.execute(statement, &[])?;
+    }
+    Ok(db)
+}
+
+/// Stores the stable readings of a session and, on exit, its summary, for
+/// practice tracking across sessions with `guitar-tuner history`.
+pub struct History {
+    db: Connection,
+    session: i64,
+    started: Instant,
+    last: Option<(String, Instant)>,
+}
+
+impl History {
+    /// Opens the database when `[history] enabled` is set.
+    pub fn from_config(config: &Config, state: &UiState) -> Result<Option<Self>, String> {
+        if !config.get_bool("history", "enabled").unwrap_or(false) {
+            return Ok(None);
+        }
+        let db = open(config)?;
+        let started = utc_timestamp(SystemTime::now());
+        let instrument = presets::all()[state.preset].name;
+        db.execute(
+            "INSERT INTO sessions (started, instrument, a4) VALUES (?, ?, ?)",
+            &[Param::Text(&started), Param::Text(instrument), Param::Real(f64::from(state.a4_freq))],
+        )?;
+        let session = db.last_insert_rowid();
+        Ok(Some(History { db, session, started: Instant::now(), last: None }))
+    }
+
+    /// Call after each analysis; a stable reading is stored when its note
+    /// changes or `RECORD_INTERVAL` after the last one.
+    pub fn record(&mut self, state: &UiState) -> Result<(), String> {
+        let Some(reading) = state.stable_reading() else {
+            return Ok(());
+        };
+        let note = format!("{}{}", reading.note, reading.octave);
+        if self.last.as_ref().is_some_and(|(last, at)| *last == note && at.elapsed() < RECORD_INTERVAL) {
+            return Ok(());
+        }
+        let target = format!("{}{}", state.target_note, state.target_octave);
+        self.db.execute(
+            "INSERT INTO readings (session, at, instrument, target, note, frequency, cents) VALUES (?, ?, ?, ?, ?, ?, ?)",
+            &[
+                Param::Int(self.session),
+                Param::Text(&utc_timestamp(SystemTime::now())),
+                Param::Text(presets::all()[state.preset].name),
+                Param::Text(&target),
+                Param::Text(&note),
+                Param::Real(f64::from(reading.freq)),
+                Param::Real(f64::from(reading.deviation_cents)),
+            ],
+        )?;
+        self.last = Some((note, Instant::now()));
+        Ok(())
+    }
+
+    /// Stores the session's end time and statistics.
+    pub fn finish(&self, state: &UiState) -> Result<(), String> {
+        let optional = |value: Option<f32>| value.map_or(Param::Null, |value| Param::Real(value.into()));
+        self.db.execute(
+            "UPDATE sessions SET ended = ?, seconds = ?, notes = ?, in_tune_percent = ?, average_cents = ? WHERE id = ?",
+            &[
+                Param::Text(&utc_timestamp(SystemTime::now())),
+                Param::Real(self.started.elapsed().as_secs_f64()),
+                Param::Int(state.stats.notes_measured.into()),
+                optional(state.stats.in_tune_percent()),
+                optional(state.stats.average_deviation()),
+                Param::Int(self.session),
+            ],
+        )
+    }
+}
+
+/// What `guitar-tuner history` lists: sessions that started on or after
+/// `since` and on or before `until` (UTC dates, `YYYY-MM-DD`), with readings
+/// on `instrument` and, if given, with `string` as the target.
+#[derive(Default)]
+pub struct Query {
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub instrument: Option<String>,
+    pub string: Option<String>,
+    /// Report how each string drifts between sessions instead.
+    pub drift: bool,
+}
+
+impl Query {
+    /// A `WHERE` condition on `sessions s` and `readings r` and its parameters.
+    /// Readings filter on their own instrument and target.
+    fn filter(&self) -> (String, Vec<Param<'_>>) {
+        let mut filter = String::from("1 = 1");
+        let mut params = Vec::new();
+        if let Some(since) = &self.since {
+            filter.push_str(" AND substr(s.started, 1, 10) >= ?");
+            params.push(Param::Text(since));
+        }
+        if let Some(until) = &self.until {
+            filter.push_str(" AND substr(s.started, 1, 10) <= ?");
+            params.push(Param::Text(until));
+        }
+        if let Some(instrument) = &self.instrument {
+            filter.push_str(" AND r.instrument = ?");
+            params.push(Param::Text(instrument));
+        }
+        if let Some(string) = &self.string {
+            filter.push_str(" AND r.target = ? COLLATE NOCASE");
+            params.push(Param::Text(string));
+        }
+        (filter, params)
+    }
+}
+
+struct Session {
+    id: i64,
+    started: String,
+    ended: Option<String>,
+    seconds: Option<f64>,
+    instrument: String,
+    a4: f64,
+    notes: Option<i64>,
+    in_tune_percent: Option<f64>,
+    average_cents: Option<f64>,
+}
+
+/// One target string's readings across the matching sessions.
+struct StringSummary {
+    target: String,
+    instrument: String,
+    readings: i64,
+    mean_cents: f64,
+    mean_abs_cents: f64,
+    last_at: String,
+    last_cents: f64,
+}
+
+/// Prints the sessions and per-string summaries that match `query`, or with
+/// `csv` the matching readings themselves, or with `drift` the drift report.
+pub fn run(config: &Config, query: &Query, format: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let db = open(config)?;
+    let (filter, params) = query.filter();
+    if query.drift {
+        return print_drift(&db, &filter, &params, format);
+    }
+    // Sessions are listed when they have a matching reading.
+    let filtered = query.instrument.is_some() || query.string.is_some();
+    let join = if filtered { "JOIN" } else { "LEFT JOIN" };
+
+    if format == Some("csv") {
+        let rows = db.query(
+            &format!(
+                "SELECT s.id, r.at, r.instrument, r.target, r.note, r.frequency, r.cents
+                 FROM sessions s JOIN readings r ON r.session = s.id WHERE {} ORDER BY r.at",
+                filter
+            ),
+            &params,
+        )?;
+        println!("session,timestamp,instrument,target,note,frequency_hz,cents");
+        for row in rows {
+            println!(
+                "{},{},\"{}\",{},{},{:.3},{:.2}",
+                int(&row[0]),
+                text(&row[1]),
+                text(&row[2]).replace('"', "\"\""),
+                text(&row[3]),
+                text(&row[4]),
+                real(&row[5]).unwrap_or(0.0),
+                real(&row[6]).unwrap_or(0.0)
+            );
+        }
+        return Ok(());
+    }
+
+    let sessions: Vec<Session> = db
+        .query(
+            &format!(
+                "SELECT DISTINCT s.id, s.started, s.ended, s.seconds, s.instrument, s.a4, s.notes, s.in_tune_percent,
+                    s.average_cents
+                 FROM sessions s {} readings r ON r.session = s.id WHERE {} ORDER BY s.started",
+                join, filter
+            ),
+            &params,
+        )?
+        .into_iter()
+        .map(|row| Session {
+            id: int(&row[0]),
+            started: text(&row[1]),
+            ended: row[2].as_str().map(str::to_string),
+            seconds: real(&row[3]),
+            instrument: text(&row[4]),
+            a4: real(&row[5]).unwrap_or(440.0),
+            notes: real(&row[6]).map(|notes| notes as i64),
+            in_tune_percent: real(&row[7]),
+            average_cents: real(&row[8]),
+        })
+        .collect();
+    let strings: Vec<StringSummary> = db
+        .query(
+            &format!(
+                // With a single max(), SQLite takes the bare `r.cents` from the row holding it.
+                "SELECT r.target, r.instrument, count(*), avg(r.cents), avg(abs(r.cents)), max(r.at), r.cents
+                 FROM sessions s JOIN readings r ON r.session = s.id WHERE {}
+                 GROUP BY r.instrument, r.target ORDER BY r.instrument, r.target",
+                filter
+            ),
+            &params,
+        )?
+        .into_iter()
+        .map(|row| StringSummary {
+            target: text(&row[0]),
+            instrument: text(&row[1]),
+            readings: int(&row[2]),
+            mean_cents: real(&row[3]).unwrap_or(0.0),
+            mean_abs_cents: real(&row[4]).unwrap_or(0.0),
+            last_at: text(&row[5]),
+            last_cents: real(&row[6]).unwrap_or(0.0),
+        })
+        .collect();
+
+    match format.unwrap_or("text") {
+        "json" => print_json(&sessions, &strings),
+        _ => print_text(&sessions, &strings),
+    }
+    Ok(())
+}
+
+fn print_text(sessions: &[Session], strings: &[StringSummary]) {
+    if sessions.is_empty() {
+        println!("No sessions match.");
+        return;
+    }
+    println!("Sessions (UTC):");
+    for session in sessions {
+        let length = match session.seconds {
+            Some(seconds) => stats::format_duration(Duration::from_secs_f64(seconds)),
+            None => "unfinished".to_string(),
+        };
+        let mut line = format!(
+            "  #{:<4} {}  {:>10}  {}  A4 {:.1}",
+            session.id,
+            session.started.get(..16).unwrap_or(&session.started).replace('T', " "),
+            length,
+            session.instrument,
+            session.a4
+        );
+        if let Some(notes) = session.notes {
+            line.push_str(&format!("  notes {}", notes));
+        }
+        if let Some(percent) = session.in_tune_percent {
+            line.push_str(&format!("  {:.0}% in tune", percent));
+        }
+        if let Some(cents) = session.average_cents {
+            line.push_str(&format!("  avg {:.1}c", cents));
+        }
+        println!("{}", line);
+    }
+    if strings.is_empty() {
+        return;
+    }
+    println!();
+    println!("Strings:");
+    for string in strings {
+        println!(
+            "  {:<4} {}  readings {}  mean {:+.1}c  mean absolute {:.1}c  last {:+.1}c on {}",
+            string.target,
+            string.instrument,
+            string.readings,
+            string.mean_cents,
+            string.mean_abs_cents,
+            string.last_cents,
+            string.last_at.get(..10).unwrap_or(&string.last_at)
+        );
+    }
+}
+
+fn print_json(sessions: &[Session], strings: &[StringSummary]) {
+    let number = |value: Option<f64>| value.map_or("null".to_string(), |value| format!("{:.2}", value));
+    let sessions: Vec<String> = sessions
+        .iter()
+        .map(|s| {
+            format!(
+                "    {{\"id\":{},\"started\":\"{}\",\"ended\":{},\"instrument\":\"{}\",\"a4\":{:.1},\"notes\":{},\
+                 \"in_tune_percent\":{},\"average_cents\":{}}}",
+                s.id,
+                s.started,
+                s.ended.as_ref().map_or("null".to_string(), |ended| format!("\"{}\"", ended)),
+                json_escape(&s.instrument),
+                s.a4,
+                s.notes.map_or("null".to_string(), |notes| format!("{}", notes)),
+                number(s.in_tune_percent),
+                number(s.average_cents)
+            )
+        })
+        .collect();
+    let strings: Vec<String> = strings
+        .iter()
+        .map(|s| {
+            format!(
+                "    {{\"target\":\"{}\",\"instrument\":\"{}\",\"readings\":{},\"mean_cents\":{:.2},\
+                 \"mean_abs_cents\":{:.2},\"last_at\":\"{}\",\"last_cents\":{:.2}}}",
+                s.target,
+                json_escape(&s.instrument),
+                s.readings,
+                s.mean_cents,
+                s.mean_abs_cents,
+                s.last_at,
+                s.last_cents
+            )
+        })
+        .collect();
+    let list = |items: Vec<String>| if items.is_empty() { "[]".to_string() } else { format!("[\n{}\n  ]", items.join(",\n")) };
+    println!("{{\n  \"sessions\": {},\n  \"strings\": {}\n}}", list(sessions), list(strings));
+}
+
+/// Each string's readings near its target, oldest first, reduced to how far
+/// it had moved off pitch at the start of each session since the end of the
+/// one before.
+fn print_drift(
+    db: &Connection,
+    filter: &str,
+    params: &[Param],
+    format: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let rows = db.query(
+        &format!(
+            "SELECT r.instrument, r.target, s.id, r.at, julianday(r.at), r.cents
+             FROM sessions s JOIN readings r ON r.session = s.id
+             WHERE {} AND abs(r.cents) <= ? ORDER BY r.instrument, r.target, r.at",
+            filter
+        ),
+        &[params, &[Param::Real(f64::from(drift::MAX_CENTS))]].concat(),
+    )?;
+    let readings: Vec<drift::Reading> = rows
+        .iter()
+        .map(|row| drift::Reading {
+            instrument: text(&row[0]),
+            target: text(&row[1]),
+            session: int(&row[2]),
+            at: text(&row[3]),
+            day: real(&row[4]).unwrap_or(0.0),
+            cents: real(&row[5]).unwrap_or(0.0) as f32,
+        })
+        .collect();
+    let strings = drift::strings(&readings);
+    match format.unwrap_or("text") {
+        "json" => drift::print_json(&strings),
+        "csv" => drift::print_csv(&strings),
+        _ => drift::print_text(&strings),
+    }
+    Ok(())
+}
+
+fn int(column: &Column) -> i64 {
+    column.as_f64().unwrap_or(0.0) as i64
+}
+
+fn real(column: &Column) -> Option<f64> {
+    column.as_f64().filter(|value| value.is_finite())
+}
+
+fn text(column: &Column) -> String {
+    column.as_str().unwrap_or_default().to_string()
+}
+
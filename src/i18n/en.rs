@@ -0,0 +1,310 @@
+pub const STRINGS: &[(&str, &str)] = &[
+    ("app.title", "Guitar Tuner"),
+    ("tab.tuner", "Tuner"),
+    ("tab.spectrum", "Spectrum"),
+    ("tab.settings", "Settings"),
+    ("tab.log", "Log"),
+    ("tab.practice", "Practice"),
+    ("tab.spectrogram", "Spectrogram"),
+    ("status.in_tune", "IN TUNE"),
+    ("status.close", "CLOSE"),
+    ("status.far", "OUT OF TUNE"),
+    ("status.no_signal", "NO SIGNAL"),
+    ("badge.paused", "PAUSED"),
+    ("statusbar.input", "Input:"),
+    ("statusbar.latency", "latency {} ms"),
+    ("statusbar.cpu", "CPU {}%"),
+    ("statusbar.dropped", "{} dropped"),
+    ("notice.output", "Output: {}"),
+    ("notice.server", "Remote view: http://{}/"),
+    ("notice.daemon_lost", "Lost the tuner daemon at {}; reconnecting"),
+    ("notice.daemon_back", "Reconnected to the tuner daemon at {}"),
+    ("common.on", "on"),
+    ("common.off", "off"),
+    ("common.none", "none"),
+    ("fmt.cents", "{} cents"),
+    ("damping.off", "raw"),
+    ("damping.fast", "fast"),
+    ("damping.smooth", "smooth"),
+    ("damping.strobe", "strobe-like"),
+    ("guide.tighten_string", "Tighten string"),
+    ("guide.loosen_string", "Loosen string"),
+    ("guide.tighten_bowed", "Tighten (peg or fine tuner clockwise)"),
+    ("guide.loosen_bowed", "Loosen (peg or fine tuner anticlockwise)"),
+    ("indicator.title", "Tuning Indicator ({}, ±{}c)"),
+    ("bignote.held", "Held {}s"),
+    ("bignote.target", "Target"),
+    ("bignote.exit", "Exit big-note mode"),
+    ("pipe.title", "Pitch Pipe - {}"),
+    ("pipe.playing_sequence", "Playing sequence..."),
+    ("pipe.playing", "Playing"),
+    ("pipe.stopped", "Stopped"),
+    ("pipe.string", "String"),
+    ("pipe.play_stop", "Play/Stop"),
+    ("pipe.play_all", "Play all"),
+    ("pipe.instrument", "Instrument"),
+    ("frequency.title", "Frequency"),
+    ("frequency.held", "held {}s"),
+    ("frequency.spread", "(±{}c)"),
+    ("beat.no_output", "Beats: no output device"),
+    ("beat.none", "Beats: ---"),
+    ("beat.sharp", "Beats: {}/s sharp"),
+    ("beat.flat", "Beats: {}/s flat"),
+    ("beat.unison", "Beats: none"),
+    ("target.title", "Target"),
+    ("target.label", "Target"),
+    ("target.output", "Out"),
+    ("controls.title", "Controls"),
+    ("controls.note", "Note"),
+    ("controls.octave", "Octave"),
+    ("controls.a4", "A4 Freq"),
+    ("controls.pages", "Pages"),
+    ("controls.pause", "Pause"),
+    ("controls.help", "Help"),
+    ("controls.quit", "Quit"),
+    ("keyboard.title", "Keyboard"),
+    ("settings.title", "Settings"),
+    ("settings.select", "Select"),
+    ("settings.change", "Change"),
+    ("settings.save", "Save to config"),
+    ("settings.saved", "Saved to {}"),
+    ("spectrum.title", "Spectrum"),
+    ("spectrum.title_peak", "Spectrum - peak {} Hz"),
+    ("spectrum.partials", "Partials (cents vs. n × f0)"),
+    ("waveform.title", "Waveform - {}"),
+    ("waveform.clip", "CLIP"),
+    ("waveform.peak", "peak {} dBFS"),
+    ("log.session", "Session"),
+    ("log.time", "Time"),
+    ("log.notes", "Notes"),
+    ("log.in_tune", "In tune"),
+    ("log.average", "Avg deviation"),
+    ("log.readings", "Readings ({})"),
+    ("log.empty", "No stable readings yet - play a note"),
+    ("log.note", "Note"),
+    ("log.frequency", "Frequency"),
+    ("log.deviation", "Deviation"),
+    ("log.history", "Deviation History"),
+    ("log.now", "now"),
+    ("help.title", "Help - press {} or {} to close"),
+    ("audio_error.title", "Audio input unavailable"),
+    ("audio_error.choose", "Choose an input device:"),
+    ("audio_error.default", "Default input device"),
+    ("audio_error.hint", "{} choose   {} open   {} quit"),
+    ("audio_error.stalled", "No audio from {} for {} seconds"),
+    ("help.current", "Current settings"),
+    ("help.note", "Target note (string in pitch pipe)"),
+    ("help.octave", "Target octave"),
+    ("help.a4", "A4 reference frequency"),
+    ("help.pages", "Switch page"),
+    ("help.page_jump", "Jump to page"),
+    ("help.beat", "Beat-frequency comparison"),
+    ("help.metronome", "Metronome on/off"),
+    ("help.pattern", "Metronome accent pattern"),
+    ("help.tempo", "Metronome tempo -/+ 1 BPM"),
+    ("help.tempo_coarse", "Metronome tempo -/+ 10 BPM"),
+    ("help.chime", "In-tune chime"),
+    ("help.flash", "In-tune screen flash"),
+    ("help.output", "Cycle output device"),
+    ("help.pipe", "Pitch pipe screen"),
+    ("help.pipe_play", "Pitch pipe play/stop"),
+    ("help.pipe_all", "Pitch pipe play all strings"),
+    ("help.instrument", "Cycle instrument preset"),
+    ("help.waveform", "Waveform panel"),
+    ("help.history", "Deviation history graph"),
+    ("help.keyboard", "Piano keyboard"),
+    ("help.damping", "Needle damping"),
+    ("help.range", "Meter range ±10/25/50 cents"),
+    ("help.vertical_meter", "Vertical meter for narrow terminals"),
+    ("help.big_note", "Big-note mode"),
+    ("help.zen", "Zen mode: note and meter only"),
+    ("help.theme", "Cycle theme"),
+    ("help.pause", "Pause/resume analysis and display"),
+    ("help.save", "Save settings (Settings page)"),
+    ("help.help", "Toggle this help"),
+    ("help.quit", "Close help / quit"),
+    ("mode.beat", "Beat comparison"),
+    ("mode.metronome", "Metronome"),
+    ("mode.pipe", "Pitch pipe"),
+    ("mode.waveform", "Waveform"),
+    ("mode.history", "History graph"),
+    ("mode.keyboard", "Keyboard"),
+    ("setting.a4", "A4 reference"),
+    ("setting.in_tune", "In-tune within"),
+    ("setting.close", "Close within"),
+    ("setting.target_note", "Target note"),
+    ("setting.target_octave", "Target octave"),
+    ("setting.instrument", "Instrument"),
+    ("setting.damping", "Needle damping"),
+    ("setting.meter_range", "Meter range"),
+    ("setting.vertical_meter", "Vertical meter"),
+    ("setting.theme", "Theme"),
+    ("setting.output", "Output device"),
+    ("setting.chime", "In-tune chime"),
+    ("setting.flash", "In-tune flash"),
+    ("setting.bpm", "Metronome tempo"),
+    ("setting.pattern", "Metronome meter"),
+    ("announce.target", "Target {}, A4 {} Hz"),
+    ("announce.no_signal", "No signal"),
+    ("announce.in_tune", "{}, in tune"),
+    ("announce.flat", "{}, {} cents flat, {}"),
+    ("announce.sharp", "{}, {} cents sharp, {}"),
+    ("practice.title", "Interval ear training"),
+    ("practice.start", "Press {} to hear a root note"),
+    ("practice.listen", "Listen: {}"),
+    ("practice.ask", "Sing or play a {} above {}"),
+    ("practice.result", "{} above {}: {}"),
+    ("practice.no_answer", "Nothing heard in time"),
+    ("practice.hint", "{}: new question, or replay the root"),
+    ("practice.rounds", "Rounds (average error {})"),
+    ("interval.m2", "minor second"),
+    ("interval.M2", "major second"),
+    ("interval.m3", "minor third"),
+    ("interval.M3", "major third"),
+    ("interval.P4", "perfect fourth"),
+    ("interval.TT", "tritone"),
+    ("interval.P5", "perfect fifth"),
+    ("interval.m6", "minor sixth"),
+    ("interval.M6", "major sixth"),
+    ("interval.m7", "minor seventh"),
+    ("interval.M7", "major seventh"),
+    ("interval.P8", "octave"),
+    ("exercise.intervals", "Intervals"),
+    ("exercise.game", "Pitch match"),
+    ("game.title", "Pitch matching game"),
+    ("game.start", "Press {} to start: {} notes around {}"),
+    ("game.rules", "Hit each note and hold it for a second; faster and closer scores more"),
+    ("game.hit", "Hit {}"),
+    ("game.left", "{} s left"),
+    ("game.stop", "{}: stop the game"),
+    ("game.over", "Game over: {} points"),
+    ("game.stats", "Score"),
+    ("game.score", "{} points"),
+    ("game.round", "Note {} of {}"),
+    ("game.accuracy", "Hits: {} ({})"),
+    ("game.average", "Average error: {}"),
+    ("game.streak", "Streak: {} (best {})"),
+    ("game.last_hit", "{}: +{} points, {}"),
+    ("game.last_miss", "{}: missed"),
+    ("exercise.long_tone", "Long tone"),
+    ("hold.title", "Long-tone stability"),
+    ("hold.start", "Press {} and play or sing one note for {} seconds"),
+    ("hold.length", "{}: longer or shorter tone"),
+    ("hold.waiting", "Play a note to start the {} second tone"),
+    ("hold.cancel", "{}: cancel"),
+    ("hold.holding", "Holding {} for {} s"),
+    ("hold.stop", "{}: stop now"),
+    ("hold.stats", "offset {} cents, spread {} cents, drift {} cents"),
+    ("hold.grade", "Stability grade {} on {}"),
+    ("hold.results", "Recent long tones"),
+    ("hold.broken", "broke off at {} s"),
+    ("exercise.warmup", "Warm-up"),
+    ("warmup.title", "Vocal warm-up"),
+    ("warmup.chromatic", "Chromatic five-note slide"),
+    ("warmup.five_note", "Five-note scale"),
+    ("warmup.arpeggio", "Octave arpeggio"),
+    ("warmup.timer", "moves on a timer"),
+    ("warmup.match", "moves when matched"),
+    ("warmup.start", "Press {} to start from {}"),
+    ("warmup.choose", "{}: choose the pattern"),
+    ("warmup.sing", "Sing {} (step {} of {})"),
+    ("warmup.stop", "{}: stop"),
+    ("warmup.done", "Finished, average error {}"),
+    ("warmup.steps", "Steps (cents, time to match)"),
+    ("exercise.scale", "Scales"),
+    ("scale.title", "Scale practice"),
+    ("scale.name", "{} {}"),
+    ("scale.major", "major"),
+    ("scale.natural_minor", "natural minor"),
+    ("scale.harmonic_minor", "harmonic minor"),
+    ("scale.melodic_minor", "melodic minor"),
+    ("scale.dorian", "dorian"),
+    ("scale.major_pentatonic", "major pentatonic"),
+    ("scale.minor_pentatonic", "minor pentatonic"),
+    ("scale.degree", "Degree {} ({})"),
+    ("scale.outside", "{} is not in {}"),
+    ("scale.hint", "{}: scale, {}: {}; the key is the tuner's target note"),
+    ("scale.follow_on", "follow the scale"),
+    ("scale.follow_off", "stop following"),
+    ("scale.averages", "Average intonation by degree"),
+    ("exercise.sequence", "Arpeggios"),
+    ("sequence.title", "Arpeggio and sequence drill"),
+    ("sequence.major", "Major arpeggio on {}"),
+    ("sequence.minor", "Minor arpeggio on {}"),
+    ("sequence.dominant", "Dominant seventh on {}"),
+    ("sequence.diminished", "Diminished seventh on {}"),
+    ("sequence.play", "Play {} (note {} of {})"),
+    ("sequence.stop", "{}: stop"),
+    ("sequence.start", "{}: start, {}: choose the sequence"),
+    ("sequence.chart", "Error by note (cents)"),
+    ("sequence.skipped", "skipped"),
+    ("sequence.average", "Average error {}"),
+    ("spectrogram.title", "Spectrogram (time →, frequency ↑)"),
+    ("exercise.double_stop", "Double stops"),
+    ("double_stop.title", "Interval analyzer"),
+    ("interval.P1", "unison"),
+    ("double_stop.compound", "compound {}"),
+    ("double_stop.notes", "{} and {}"),
+    ("double_stop.size", "{}: {}"),
+    ("double_stop.equal", "Equal-tempered {}"),
+    ("double_stop.just", "Just {} {}"),
+    ("double_stop.listening", "Listening for two notes..."),
+    ("double_stop.hint", "Sustain two notes together, as a double stop or against a drone"),
+    ("double_stop.comparison", "Interval against equal temperament (cents)"),
+    ("double_stop.legend", "● measured   │ equal-tempered   J just"),
+    ("help.reference", "Capture the held note as a reference to tune to"),
+    ("help.clear_references", "Clear captured references"),
+    ("mode.reference", "Reference instrument"),
+    ("mode.reference_notes", "{} notes"),
+    ("notice.reference", "Reference {} captured at {} Hz"),
+    ("notice.reference_none", "Hold a steady note to capture it as a reference"),
+    ("notice.references_cleared", "References cleared; tuning to A4 again"),
+    ("target.reference", "Ref {} {}"),
+    ("help.capo", "Capo chart for the instrument"),
+    ("capo.title", "Capo chart: {} ({} closes)"),
+    ("capo.fret", "Capo"),
+    ("capo.strings", "Open strings"),
+    ("capo.hint", "Columns are open chord shapes; rows show what they sound as at each capo fret"),
+    ("statusbar.low_register", "low register"),
+    ("setting.register", "Register"),
+    ("register.auto", "auto ({})"),
+    ("register.normal", "normal"),
+    ("register.low", "low (bass)"),
+    ("register.high", "high (whistle, piccolo)"),
+    ("statusbar.high_register", "high register"),
+    ("coincident.title", "Coincident partials: {} with {} (lower:upper, beats/s)"),
+    ("coincident.title_none", "Coincident partials with {}"),
+    ("coincident.rate", "{}/s"),
+    ("coincident.no_pairs", "No partials coincide in this interval"),
+    ("help.continuous", "Continuous pitch: no note snapping, two-octave scale"),
+    ("mode.continuous", "Continuous pitch"),
+    ("continuous.title", "Continuous pitch vs {} ({} Hz)"),
+    ("help.copy", "Copy the reading to the clipboard"),
+    ("clipboard.reading", "{} {} Hz, {}c vs {}"),
+    ("notice.copied", "Copied: {}"),
+    ("notice.copy_none", "Hold a steady note to copy its reading"),
+    ("notice.copy_failed", "Could not copy the reading: {}"),
+    ("help.snapshot", "Save a snapshot of the screen to files"),
+    ("notice.snapshot", "Saved a snapshot to {}"),
+    ("setting.countdown", "Countdown per string"),
+    ("timing.running", "⏱ {}s"),
+    ("timing.over", "⏱ {}s over"),
+    ("timing.done", "✓ {}s"),
+    ("setting.notify", "Desktop notification"),
+    ("desktop.title", "{} in tune"),
+    ("desktop.body", "{} cents at {} Hz"),
+    ("setting.bell", "Terminal bell"),
+    ("bell.enter", "entering"),
+    ("bell.both", "entering and leaving"),
+    ("setting.dead_zone", "Needle dead zone"),
+    ("statusbar.rate", "{} readings/s"),
+    ("setting.mode", "Analysis mode"),
+    ("mode.accurate", "accurate"),
+    ("mode.fast", "fast"),
+    ("statusbar.fast", "fast mode"),
+    ("setting.auto_lock", "Auto-lock target"),
+    ("lock.following", "chromatic"),
+    ("lock.locked", "locked"),
+    ("setting.off_note", "Off-note alarm"),
+    ("offnote.warning", "⚠ Hearing {}, the target is {}: wrong string?"),
+];
@@ -0,0 +1,310 @@
+pub const STRINGS: &[(&str, &str)] = &[
+    ("app.title", "Afinador de Guitarra"),
+    ("tab.tuner", "Afinador"),
+    ("tab.spectrum", "Espectro"),
+    ("tab.settings", "Ajustes"),
+    ("tab.log", "Registro"),
+    ("tab.practice", "Práctica"),
+    ("tab.spectrogram", "Espectrograma"),
+    ("status.in_tune", "AFINADO"),
+    ("status.close", "CERCA"),
+    ("status.far", "DESAFINADO"),
+    ("status.no_signal", "SIN SEÑAL"),
+    ("badge.paused", "EN PAUSA"),
+    ("statusbar.input", "Entrada:"),
+    ("statusbar.latency", "latencia {} ms"),
+    ("statusbar.cpu", "CPU {}%"),
+    ("statusbar.dropped", "{} descartados"),
+    ("notice.output", "Salida: {}"),
+    ("notice.server", "Vista remota: http://{}/"),
+    ("notice.daemon_lost", "Conexión perdida con el daemon en {}; reconectando"),
+    ("notice.daemon_back", "Reconectado al daemon en {}"),
+    ("common.on", "sí"),
+    ("common.off", "no"),
+    ("common.none", "ninguno"),
+    ("fmt.cents", "{} cents"),
+    ("damping.off", "directo"),
+    ("damping.fast", "rápido"),
+    ("damping.smooth", "suave"),
+    ("damping.strobe", "estroboscópico"),
+    ("guide.tighten_string", "Tense la cuerda"),
+    ("guide.loosen_string", "Afloje la cuerda"),
+    ("guide.tighten_bowed", "Tense (clavija o afinador fino en sentido horario)"),
+    ("guide.loosen_bowed", "Afloje (clavija o afinador fino en sentido antihorario)"),
+    ("indicator.title", "Indicador de Afinación ({}, ±{}c)"),
+    ("bignote.held", "Retenido {}s"),
+    ("bignote.target", "Objetivo"),
+    ("bignote.exit", "Salir del modo nota grande"),
+    ("pipe.title", "Diapasón - {}"),
+    ("pipe.playing_sequence", "Tocando secuencia..."),
+    ("pipe.playing", "Tocando"),
+    ("pipe.stopped", "Detenido"),
+    ("pipe.string", "Cuerda"),
+    ("pipe.play_stop", "Tocar/Parar"),
+    ("pipe.play_all", "Tocar todas"),
+    ("pipe.instrument", "Instrumento"),
+    ("frequency.title", "Frecuencia"),
+    ("frequency.held", "retenido {}s"),
+    ("frequency.spread", "(±{}c)"),
+    ("beat.no_output", "Batidos: sin dispositivo de salida"),
+    ("beat.none", "Batidos: ---"),
+    ("beat.sharp", "Batidos: {}/s alto"),
+    ("beat.flat", "Batidos: {}/s bajo"),
+    ("beat.unison", "Batidos: ninguno"),
+    ("target.title", "Objetivo"),
+    ("target.label", "Objetivo"),
+    ("target.output", "Salida"),
+    ("controls.title", "Controles"),
+    ("controls.note", "Nota"),
+    ("controls.octave", "Octava"),
+    ("controls.a4", "Frec. La4"),
+    ("controls.pages", "Páginas"),
+    ("controls.pause", "Pausa"),
+    ("controls.help", "Ayuda"),
+    ("controls.quit", "Salir"),
+    ("keyboard.title", "Teclado"),
+    ("settings.title", "Ajustes"),
+    ("settings.select", "Elegir"),
+    ("settings.change", "Cambiar"),
+    ("settings.save", "Guardar configuración"),
+    ("settings.saved", "Guardado en {}"),
+    ("spectrum.title", "Espectro"),
+    ("spectrum.title_peak", "Espectro - pico {} Hz"),
+    ("spectrum.partials", "Parciales (cents vs. n × f0)"),
+    ("waveform.title", "Forma de onda - {}"),
+    ("waveform.clip", "SATURACIÓN"),
+    ("waveform.peak", "pico {} dBFS"),
+    ("log.session", "Sesión"),
+    ("log.time", "Tiempo"),
+    ("log.notes", "Notas"),
+    ("log.in_tune", "Afinado"),
+    ("log.average", "Desviación media"),
+    ("log.readings", "Lecturas ({})"),
+    ("log.empty", "Aún no hay lecturas estables - toque una nota"),
+    ("log.note", "Nota"),
+    ("log.frequency", "Frecuencia"),
+    ("log.deviation", "Desviación"),
+    ("log.history", "Historial de Desviación"),
+    ("log.now", "ahora"),
+    ("help.title", "Ayuda - pulse {} o {} para cerrar"),
+    ("audio_error.title", "Entrada de audio no disponible"),
+    ("audio_error.choose", "Elija un dispositivo de entrada:"),
+    ("audio_error.default", "Dispositivo de entrada predeterminado"),
+    ("audio_error.hint", "{} elegir   {} abrir   {} salir"),
+    ("audio_error.stalled", "Sin audio de {} desde hace {} segundos"),
+    ("help.current", "Ajustes actuales"),
+    ("help.note", "Nota objetivo (cuerda en el diapasón)"),
+    ("help.octave", "Octava objetivo"),
+    ("help.a4", "Frecuencia de referencia del La4"),
+    ("help.pages", "Cambiar de página"),
+    ("help.page_jump", "Ir a la página"),
+    ("help.beat", "Comparación por batido"),
+    ("help.metronome", "Metrónomo sí/no"),
+    ("help.pattern", "Patrón de acentos del metrónomo"),
+    ("help.tempo", "Tempo -/+ 1 BPM"),
+    ("help.tempo_coarse", "Tempo -/+ 10 BPM"),
+    ("help.chime", "Aviso sonoro al afinar"),
+    ("help.flash", "Destello de pantalla al afinar"),
+    ("help.output", "Cambiar dispositivo de salida"),
+    ("help.pipe", "Pantalla del diapasón"),
+    ("help.pipe_play", "Diapasón tocar/parar"),
+    ("help.pipe_all", "Diapasón tocar todas las cuerdas"),
+    ("help.instrument", "Cambiar instrumento"),
+    ("help.waveform", "Panel de forma de onda"),
+    ("help.history", "Gráfico del historial de desviación"),
+    ("help.keyboard", "Teclado de piano"),
+    ("help.damping", "Amortiguación de la aguja"),
+    ("help.range", "Escala del medidor ±10/25/50 cents"),
+    ("help.vertical_meter", "Medidor vertical para terminales estrechas"),
+    ("help.big_note", "Modo nota grande"),
+    ("help.zen", "Modo zen: solo nota y medidor"),
+    ("help.theme", "Cambiar tema"),
+    ("help.pause", "Pausar/reanudar análisis y pantalla"),
+    ("help.save", "Guardar ajustes (página Ajustes)"),
+    ("help.help", "Mostrar/ocultar esta ayuda"),
+    ("help.quit", "Cerrar ayuda / salir"),
+    ("mode.beat", "Comparación por batido"),
+    ("mode.metronome", "Metrónomo"),
+    ("mode.pipe", "Diapasón"),
+    ("mode.waveform", "Forma de onda"),
+    ("mode.history", "Gráfico de historial"),
+    ("mode.keyboard", "Teclado"),
+    ("setting.a4", "Referencia La4"),
+    ("setting.in_tune", "Afinado dentro de"),
+    ("setting.close", "Cerca dentro de"),
+    ("setting.target_note", "Nota objetivo"),
+    ("setting.target_octave", "Octava objetivo"),
+    ("setting.instrument", "Instrumento"),
+    ("setting.damping", "Amortiguación"),
+    ("setting.meter_range", "Escala del medidor"),
+    ("setting.vertical_meter", "Medidor vertical"),
+    ("setting.theme", "Tema"),
+    ("setting.output", "Dispositivo de salida"),
+    ("setting.chime", "Aviso al afinar"),
+    ("setting.flash", "Destello al afinar"),
+    ("setting.bpm", "Tempo"),
+    ("setting.pattern", "Compás"),
+    ("announce.target", "Objetivo {}, La4 {} Hz"),
+    ("announce.no_signal", "Sin señal"),
+    ("announce.in_tune", "{}, afinado"),
+    ("announce.flat", "{}, {} cents bajo, {}"),
+    ("announce.sharp", "{}, {} cents alto, {}"),
+    ("practice.title", "Entrenamiento auditivo de intervalos"),
+    ("practice.start", "Pulse {} para oír una nota fundamental"),
+    ("practice.listen", "Escuche: {}"),
+    ("practice.ask", "Cante o toque una {} por encima de {}"),
+    ("practice.result", "{} por encima de {}: {}"),
+    ("practice.no_answer", "No se oyó nada a tiempo"),
+    ("practice.hint", "{}: nueva pregunta, o repetir la fundamental"),
+    ("practice.rounds", "Rondas (error medio {})"),
+    ("interval.m2", "segunda menor"),
+    ("interval.M2", "segunda mayor"),
+    ("interval.m3", "tercera menor"),
+    ("interval.M3", "tercera mayor"),
+    ("interval.P4", "cuarta justa"),
+    ("interval.TT", "cuarta aumentada"),
+    ("interval.P5", "quinta justa"),
+    ("interval.m6", "sexta menor"),
+    ("interval.M6", "sexta mayor"),
+    ("interval.m7", "séptima menor"),
+    ("interval.M7", "séptima mayor"),
+    ("interval.P8", "octava"),
+    ("exercise.intervals", "Intervalos"),
+    ("exercise.game", "Afinación"),
+    ("game.title", "Juego de afinación"),
+    ("game.start", "Pulse {} para empezar: {} notas alrededor de {}"),
+    ("game.rules", "Acierte cada nota y manténgala un segundo; más rápido y más cerca puntúa más"),
+    ("game.hit", "Acierte {}"),
+    ("game.left", "quedan {} s"),
+    ("game.stop", "{}: detener el juego"),
+    ("game.over", "Fin del juego: {} puntos"),
+    ("game.stats", "Puntuación"),
+    ("game.score", "{} puntos"),
+    ("game.round", "Nota {} de {}"),
+    ("game.accuracy", "Aciertos: {} ({})"),
+    ("game.average", "Error medio: {}"),
+    ("game.streak", "Racha: {} (mejor {})"),
+    ("game.last_hit", "{}: +{} puntos, {}"),
+    ("game.last_miss", "{}: fallada"),
+    ("exercise.long_tone", "Nota larga"),
+    ("hold.title", "Estabilidad de nota larga"),
+    ("hold.start", "Pulse {} y toque o cante una nota durante {} segundos"),
+    ("hold.length", "{}: nota más larga o más corta"),
+    ("hold.waiting", "Toque una nota para empezar la nota de {} segundos"),
+    ("hold.cancel", "{}: cancelar"),
+    ("hold.holding", "Sosteniendo {} durante {} s"),
+    ("hold.stop", "{}: detener ahora"),
+    ("hold.stats", "desvío {} cents, dispersión {} cents, deriva {} cents"),
+    ("hold.grade", "Nota de estabilidad {} en {}"),
+    ("hold.results", "Notas largas recientes"),
+    ("hold.broken", "interrumpida a los {} s"),
+    ("exercise.warmup", "Calentamiento"),
+    ("warmup.title", "Calentamiento vocal"),
+    ("warmup.chromatic", "Cromático de cinco notas"),
+    ("warmup.five_note", "Escala de cinco notas"),
+    ("warmup.arpeggio", "Arpegio de octava"),
+    ("warmup.timer", "avanza por tiempo"),
+    ("warmup.match", "avanza al acertar"),
+    ("warmup.start", "Pulse {} para empezar en {}"),
+    ("warmup.choose", "{}: elegir el patrón"),
+    ("warmup.sing", "Cante {} (paso {} de {})"),
+    ("warmup.stop", "{}: detener"),
+    ("warmup.done", "Terminado, error medio {}"),
+    ("warmup.steps", "Pasos (cents, tiempo hasta acertar)"),
+    ("exercise.scale", "Escalas"),
+    ("scale.title", "Práctica de escalas"),
+    ("scale.name", "{} {}"),
+    ("scale.major", "mayor"),
+    ("scale.natural_minor", "menor natural"),
+    ("scale.harmonic_minor", "menor armónica"),
+    ("scale.melodic_minor", "menor melódica"),
+    ("scale.dorian", "dórica"),
+    ("scale.major_pentatonic", "pentatónica mayor"),
+    ("scale.minor_pentatonic", "pentatónica menor"),
+    ("scale.degree", "Grado {} ({})"),
+    ("scale.outside", "{} no está en {}"),
+    ("scale.hint", "{}: escala, {}: {}; la tónica es la nota objetivo del afinador"),
+    ("scale.follow_on", "seguir la escala"),
+    ("scale.follow_off", "dejar de seguir"),
+    ("scale.averages", "Afinación media por grado"),
+    ("exercise.sequence", "Arpegios"),
+    ("sequence.title", "Ejercicio de arpegios y secuencias"),
+    ("sequence.major", "Arpegio mayor en {}"),
+    ("sequence.minor", "Arpegio menor en {}"),
+    ("sequence.dominant", "Séptima de dominante en {}"),
+    ("sequence.diminished", "Séptima disminuida en {}"),
+    ("sequence.play", "Toque {} (nota {} de {})"),
+    ("sequence.stop", "{}: detener"),
+    ("sequence.start", "{}: empezar, {}: elegir la secuencia"),
+    ("sequence.chart", "Error por nota (cents)"),
+    ("sequence.skipped", "omitida"),
+    ("sequence.average", "Error medio {}"),
+    ("spectrogram.title", "Espectrograma (tiempo →, frecuencia ↑)"),
+    ("exercise.double_stop", "Dobles cuerdas"),
+    ("double_stop.title", "Analizador de intervalos"),
+    ("interval.P1", "unísono"),
+    ("double_stop.compound", "{} compuesta"),
+    ("double_stop.notes", "{} y {}"),
+    ("double_stop.size", "{}: {}"),
+    ("double_stop.equal", "Temperado {}"),
+    ("double_stop.just", "Justo {} {}"),
+    ("double_stop.listening", "Esperando dos notas..."),
+    ("double_stop.hint", "Mantén dos notas juntas, en dobles cuerdas o sobre un bordón"),
+    ("double_stop.comparison", "Intervalo frente al temperamento igual (cents)"),
+    ("double_stop.legend", "● medido   │ temperado   J justo"),
+    ("help.reference", "Capturar la nota sostenida como referencia"),
+    ("help.clear_references", "Borrar las referencias capturadas"),
+    ("mode.reference", "Instrumento de referencia"),
+    ("mode.reference_notes", "{} notas"),
+    ("notice.reference", "Referencia {} capturada en {} Hz"),
+    ("notice.reference_none", "Sostén una nota estable para capturarla como referencia"),
+    ("notice.references_cleared", "Referencias borradas; afinando con el A4 de nuevo"),
+    ("target.reference", "Ref {} {}"),
+    ("help.capo", "Tabla de cejilla del instrumento"),
+    ("capo.title", "Tabla de cejilla: {} ({} cierra)"),
+    ("capo.fret", "Traste"),
+    ("capo.strings", "Cuerdas al aire"),
+    ("capo.hint", "Columnas: formas de acorde abierto; filas: cómo suenan en cada traste"),
+    ("statusbar.low_register", "registro grave"),
+    ("setting.register", "Registro"),
+    ("register.auto", "automático ({})"),
+    ("register.normal", "normal"),
+    ("register.low", "grave (bajo)"),
+    ("register.high", "agudo (silbato, flautín)"),
+    ("statusbar.high_register", "registro agudo"),
+    ("coincident.title", "Parciales coincidentes: {} con {} (inferior:superior, batidos/s)"),
+    ("coincident.title_none", "Parciales coincidentes con {}"),
+    ("coincident.rate", "{}/s"),
+    ("coincident.no_pairs", "Ningún parcial coincide en este intervalo"),
+    ("help.continuous", "Altura continua: sin ajuste a la nota, escala de dos octavas"),
+    ("mode.continuous", "Altura continua"),
+    ("continuous.title", "Altura continua vs {} ({} Hz)"),
+    ("help.copy", "Copiar la lectura al portapapeles"),
+    ("clipboard.reading", "{} {} Hz, {}c vs {}"),
+    ("notice.copied", "Copiado: {}"),
+    ("notice.copy_none", "Sostén una nota estable para copiar su lectura"),
+    ("notice.copy_failed", "No se pudo copiar la lectura: {}"),
+    ("help.snapshot", "Guardar una captura de la pantalla en archivos"),
+    ("notice.snapshot", "Captura guardada en {}"),
+    ("setting.countdown", "Cuenta atrás"),
+    ("timing.running", "⏱ {}s"),
+    ("timing.over", "⏱ {}s de más"),
+    ("timing.done", "✓ {}s"),
+    ("setting.notify", "Aviso de escritorio"),
+    ("desktop.title", "{} afinada"),
+    ("desktop.body", "{} cents a {} Hz"),
+    ("setting.bell", "Campana del terminal"),
+    ("bell.enter", "al entrar"),
+    ("bell.both", "al entrar y salir"),
+    ("setting.dead_zone", "Zona muerta de aguja"),
+    ("statusbar.rate", "{} lecturas/s"),
+    ("setting.mode", "Modo de análisis"),
+    ("mode.accurate", "preciso"),
+    ("mode.fast", "rápido"),
+    ("statusbar.fast", "modo rápido"),
+    ("setting.auto_lock", "Bloquear objetivo"),
+    ("lock.following", "cromático"),
+    ("lock.locked", "bloqueado"),
+    ("setting.off_note", "Alarma de nota errónea"),
+    ("offnote.warning", "⚠ Se oye {}, el objetivo es {}: ¿cuerda equivocada?"),
+];
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::sync::OnceLock;
+
+use crate::config::{self, Config, Value};
+
+mod en;
+mod es;
+mod pt;
+
+pub const LANGUAGES: [(&str, &[(&str, &str)]); 3] = [("en", en::STRINGS), ("es", es::STRINGS), ("pt", pt::STRINGS)];
+
+static CATALOG: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+
+/// Selects the UI language from `[ui] language`, falling back to the locale
+/// environment and then English. A `lang/<code>.toml` file in the config
+/// directory can add a new language or override strings of a bundled one.
+pub fn init(config: &Config) -> Result<(), String> {
+    let configured = config.get_str("ui", "language").map(str::to_string);
+    let code = configured.clone().or_else(locale_language).unwrap_or_else(|| "en".to_string());
+
+    let mut strings: HashMap<&'static str, &'static str> = en::STRINGS.iter().copied().collect();
+    let builtin = LANGUAGES.iter().find(|(name, _)| *name == code);
+    if let Some((_, table)) = builtin {
+        strings.extend(table.iter().copied());
+    }
+
+    let overrides = load_overrides(&code)?;
+    if builtin.is_none() && overrides.is_none() && configured.is_some() {
+        return Err(format!("Unknown language: {}", code));
+    }
+    for (key, text) in overrides.into_iter().flatten() {
+        let (key, _) = en::STRINGS
+            .iter()
+            .find(|(k, _)| *k == key)
+            .ok_or_else(|| format!("lang/{}.toml: unknown string `{}`", code, key))?;
+        strings.insert(key, Box::leak(text.into_boxed_str()));
+    }
+
+    let _ = CATALOG.set(strings);
+    Ok(())
+}
+
+/// The translation of `key`, or the key itself if no language defines it.
+pub fn tr(key: &'static str) -> &'static str {
+    match CATALOG.get() {
+        Some(strings) => strings.get(key).copied().unwrap_or(key),
+        None => en::STRINGS.iter().find(|(k, _)| *k == key).map_or(key, |(_, text)| text),
+    }
+}
+
+/// Translates `key` and fills each `{}` in order with `args`.
+pub fn trf(key: &'static str, args: &[&dyn Display]) -> String {
+    let mut out = String::new();
+    let mut args = args.iter();
+    let mut parts = tr(key).split("{}");
+
+    if let Some(first) = parts.next() {
+        out.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+fn locale_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| value.chars().take_while(|c| c.is_ascii_alphabetic()).collect::<String>())
+        .filter(|code| code.len() == 2)
+        .map(|code| code.to_lowercase())
+}
+
+fn load_overrides(code: &str) -> Result<Option<Vec<(String, String)>>, String> {
+    let Some(path) = config::config_dir().map(|dir| dir.join("lang").join(format!("{}.toml", code))) else {
+        return Ok(None);
+    };
+
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let file = Config::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    file.section("strings")
+        .map(|(key, value)| match value {
+            Value::String(text) => Ok((key.clone(), text.clone())),
+            _ => Err(format!("{}: {} must be a string", path.display(), key)),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
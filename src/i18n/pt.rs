@@ -0,0 +1,310 @@
+pub const STRINGS: &[(&str, &str)] = &[
+    ("app.title", "Afinador de Guitarra"),
+    ("tab.tuner", "Afinador"),
+    ("tab.spectrum", "Espectro"),
+    ("tab.settings", "Ajustes"),
+    ("tab.log", "Histórico"),
+    ("tab.practice", "Prática"),
+    ("tab.spectrogram", "Espectrograma"),
+    ("status.in_tune", "AFINADO"),
+    ("status.close", "QUASE"),
+    ("status.far", "DESAFINADO"),
+    ("status.no_signal", "SEM SINAL"),
+    ("badge.paused", "PAUSADO"),
+    ("statusbar.input", "Entrada:"),
+    ("statusbar.latency", "latência {} ms"),
+    ("statusbar.cpu", "CPU {}%"),
+    ("statusbar.dropped", "{} descartados"),
+    ("notice.output", "Saída: {}"),
+    ("notice.server", "Visualização remota: http://{}/"),
+    ("notice.daemon_lost", "Conexão perdida com o daemon em {}; reconectando"),
+    ("notice.daemon_back", "Reconectado ao daemon em {}"),
+    ("common.on", "ligado"),
+    ("common.off", "desligado"),
+    ("common.none", "nenhum"),
+    ("fmt.cents", "{} cents"),
+    ("damping.off", "bruto"),
+    ("damping.fast", "rápido"),
+    ("damping.smooth", "suave"),
+    ("damping.strobe", "estroboscópico"),
+    ("guide.tighten_string", "Aperte a corda"),
+    ("guide.loosen_string", "Afrouxe a corda"),
+    ("guide.tighten_bowed", "Aperte (cravelha ou microafinador no sentido horário)"),
+    ("guide.loosen_bowed", "Afrouxe (cravelha ou microafinador no sentido anti-horário)"),
+    ("indicator.title", "Indicador de Afinação ({}, ±{}c)"),
+    ("bignote.held", "Mantido {}s"),
+    ("bignote.target", "Alvo"),
+    ("bignote.exit", "Sair do modo nota grande"),
+    ("pipe.title", "Diapasão - {}"),
+    ("pipe.playing_sequence", "Tocando sequência..."),
+    ("pipe.playing", "Tocando"),
+    ("pipe.stopped", "Parado"),
+    ("pipe.string", "Corda"),
+    ("pipe.play_stop", "Tocar/Parar"),
+    ("pipe.play_all", "Tocar todas"),
+    ("pipe.instrument", "Instrumento"),
+    ("frequency.title", "Frequência"),
+    ("frequency.held", "mantido {}s"),
+    ("frequency.spread", "(±{}c)"),
+    ("beat.no_output", "Batimentos: sem dispositivo de saída"),
+    ("beat.none", "Batimentos: ---"),
+    ("beat.sharp", "Batimentos: {}/s acima"),
+    ("beat.flat", "Batimentos: {}/s abaixo"),
+    ("beat.unison", "Batimentos: nenhum"),
+    ("target.title", "Alvo"),
+    ("target.label", "Alvo"),
+    ("target.output", "Saída"),
+    ("controls.title", "Controles"),
+    ("controls.note", "Nota"),
+    ("controls.octave", "Oitava"),
+    ("controls.a4", "Freq. Lá4"),
+    ("controls.pages", "Páginas"),
+    ("controls.pause", "Pausar"),
+    ("controls.help", "Ajuda"),
+    ("controls.quit", "Sair"),
+    ("keyboard.title", "Teclado"),
+    ("settings.title", "Ajustes"),
+    ("settings.select", "Selecionar"),
+    ("settings.change", "Alterar"),
+    ("settings.save", "Salvar configuração"),
+    ("settings.saved", "Salvo em {}"),
+    ("spectrum.title", "Espectro"),
+    ("spectrum.title_peak", "Espectro - pico {} Hz"),
+    ("spectrum.partials", "Parciais (cents vs. n × f0)"),
+    ("waveform.title", "Forma de onda - {}"),
+    ("waveform.clip", "SATURADO"),
+    ("waveform.peak", "pico {} dBFS"),
+    ("log.session", "Sessão"),
+    ("log.time", "Tempo"),
+    ("log.notes", "Notas"),
+    ("log.in_tune", "Afinado"),
+    ("log.average", "Desvio médio"),
+    ("log.readings", "Leituras ({})"),
+    ("log.empty", "Nenhuma leitura estável ainda - toque uma nota"),
+    ("log.note", "Nota"),
+    ("log.frequency", "Frequência"),
+    ("log.deviation", "Desvio"),
+    ("log.history", "Histórico de Desvio"),
+    ("log.now", "agora"),
+    ("help.title", "Ajuda - pressione {} ou {} para fechar"),
+    ("audio_error.title", "Entrada de áudio indisponível"),
+    ("audio_error.choose", "Escolha um dispositivo de entrada:"),
+    ("audio_error.default", "Dispositivo de entrada padrão"),
+    ("audio_error.hint", "{} escolher   {} abrir   {} sair"),
+    ("audio_error.stalled", "Sem áudio de {} há {} segundos"),
+    ("help.current", "Ajustes atuais"),
+    ("help.note", "Nota alvo (corda no diapasão)"),
+    ("help.octave", "Oitava alvo"),
+    ("help.a4", "Frequência de referência do Lá4"),
+    ("help.pages", "Trocar de página"),
+    ("help.page_jump", "Ir para a página"),
+    ("help.beat", "Comparação por batimento"),
+    ("help.metronome", "Metrônomo liga/desliga"),
+    ("help.pattern", "Padrão de acentos do metrônomo"),
+    ("help.tempo", "Andamento -/+ 1 BPM"),
+    ("help.tempo_coarse", "Andamento -/+ 10 BPM"),
+    ("help.chime", "Sinal sonoro ao afinar"),
+    ("help.flash", "Piscar a tela ao afinar"),
+    ("help.output", "Trocar dispositivo de saída"),
+    ("help.pipe", "Tela do diapasão"),
+    ("help.pipe_play", "Diapasão tocar/parar"),
+    ("help.pipe_all", "Diapasão tocar todas as cordas"),
+    ("help.instrument", "Trocar instrumento"),
+    ("help.waveform", "Painel de forma de onda"),
+    ("help.history", "Gráfico do histórico de desvio"),
+    ("help.keyboard", "Teclado de piano"),
+    ("help.damping", "Amortecimento do ponteiro"),
+    ("help.range", "Escala do medidor ±10/25/50 cents"),
+    ("help.vertical_meter", "Medidor vertical para terminais estreitos"),
+    ("help.big_note", "Modo nota grande"),
+    ("help.zen", "Modo zen: só nota e medidor"),
+    ("help.theme", "Trocar tema"),
+    ("help.pause", "Pausar/retomar análise e tela"),
+    ("help.save", "Salvar ajustes (página Ajustes)"),
+    ("help.help", "Mostrar/ocultar esta ajuda"),
+    ("help.quit", "Fechar ajuda / sair"),
+    ("mode.beat", "Comparação por batimento"),
+    ("mode.metronome", "Metrônomo"),
+    ("mode.pipe", "Diapasão"),
+    ("mode.waveform", "Forma de onda"),
+    ("mode.history", "Gráfico de histórico"),
+    ("mode.keyboard", "Teclado"),
+    ("setting.a4", "Referência Lá4"),
+    ("setting.in_tune", "Afinado dentro de"),
+    ("setting.close", "Quase dentro de"),
+    ("setting.target_note", "Nota alvo"),
+    ("setting.target_octave", "Oitava alvo"),
+    ("setting.instrument", "Instrumento"),
+    ("setting.damping", "Amortecimento"),
+    ("setting.meter_range", "Escala do medidor"),
+    ("setting.vertical_meter", "Medidor vertical"),
+    ("setting.theme", "Tema"),
+    ("setting.output", "Dispositivo de saída"),
+    ("setting.chime", "Sinal ao afinar"),
+    ("setting.flash", "Piscar ao afinar"),
+    ("setting.bpm", "Andamento"),
+    ("setting.pattern", "Compasso"),
+    ("announce.target", "Alvo {}, Lá4 {} Hz"),
+    ("announce.no_signal", "Sem sinal"),
+    ("announce.in_tune", "{}, afinado"),
+    ("announce.flat", "{}, {} cents abaixo, {}"),
+    ("announce.sharp", "{}, {} cents acima, {}"),
+    ("practice.title", "Treino auditivo de intervalos"),
+    ("practice.start", "Pressione {} para ouvir uma nota fundamental"),
+    ("practice.listen", "Ouça: {}"),
+    ("practice.ask", "Cante ou toque uma {} acima de {}"),
+    ("practice.result", "{} acima de {}: {}"),
+    ("practice.no_answer", "Nada foi ouvido a tempo"),
+    ("practice.hint", "{}: nova pergunta, ou repetir a fundamental"),
+    ("practice.rounds", "Rodadas (erro médio {})"),
+    ("interval.m2", "segunda menor"),
+    ("interval.M2", "segunda maior"),
+    ("interval.m3", "terça menor"),
+    ("interval.M3", "terça maior"),
+    ("interval.P4", "quarta justa"),
+    ("interval.TT", "quarta aumentada"),
+    ("interval.P5", "quinta justa"),
+    ("interval.m6", "sexta menor"),
+    ("interval.M6", "sexta maior"),
+    ("interval.m7", "sétima menor"),
+    ("interval.M7", "sétima maior"),
+    ("interval.P8", "oitava"),
+    ("exercise.intervals", "Intervalos"),
+    ("exercise.game", "Afinação"),
+    ("game.title", "Jogo de afinação"),
+    ("game.start", "Pressione {} para começar: {} notas em torno de {}"),
+    ("game.rules", "Acerte cada nota e segure por um segundo; mais rápido e mais perto vale mais"),
+    ("game.hit", "Acerte {}"),
+    ("game.left", "faltam {} s"),
+    ("game.stop", "{}: parar o jogo"),
+    ("game.over", "Fim de jogo: {} pontos"),
+    ("game.stats", "Placar"),
+    ("game.score", "{} pontos"),
+    ("game.round", "Nota {} de {}"),
+    ("game.accuracy", "Acertos: {} ({})"),
+    ("game.average", "Erro médio: {}"),
+    ("game.streak", "Sequência: {} (melhor {})"),
+    ("game.last_hit", "{}: +{} pontos, {}"),
+    ("game.last_miss", "{}: errou"),
+    ("exercise.long_tone", "Nota longa"),
+    ("hold.title", "Estabilidade de nota longa"),
+    ("hold.start", "Pressione {} e toque ou cante uma nota por {} segundos"),
+    ("hold.length", "{}: nota mais longa ou mais curta"),
+    ("hold.waiting", "Toque uma nota para começar a nota de {} segundos"),
+    ("hold.cancel", "{}: cancelar"),
+    ("hold.holding", "Segurando {} há {} s"),
+    ("hold.stop", "{}: parar agora"),
+    ("hold.stats", "desvio {} cents, dispersão {} cents, deriva {} cents"),
+    ("hold.grade", "Nota de estabilidade {} em {}"),
+    ("hold.results", "Notas longas recentes"),
+    ("hold.broken", "interrompida em {} s"),
+    ("exercise.warmup", "Aquecimento"),
+    ("warmup.title", "Aquecimento vocal"),
+    ("warmup.chromatic", "Cromático de cinco notas"),
+    ("warmup.five_note", "Escala de cinco notas"),
+    ("warmup.arpeggio", "Arpejo de oitava"),
+    ("warmup.timer", "avança por tempo"),
+    ("warmup.match", "avança ao acertar"),
+    ("warmup.start", "Pressione {} para começar em {}"),
+    ("warmup.choose", "{}: escolher o padrão"),
+    ("warmup.sing", "Cante {} (passo {} de {})"),
+    ("warmup.stop", "{}: parar"),
+    ("warmup.done", "Concluído, erro médio {}"),
+    ("warmup.steps", "Passos (cents, tempo até acertar)"),
+    ("exercise.scale", "Escalas"),
+    ("scale.title", "Prática de escalas"),
+    ("scale.name", "{} {}"),
+    ("scale.major", "maior"),
+    ("scale.natural_minor", "menor natural"),
+    ("scale.harmonic_minor", "menor harmônica"),
+    ("scale.melodic_minor", "menor melódica"),
+    ("scale.dorian", "dórica"),
+    ("scale.major_pentatonic", "pentatônica maior"),
+    ("scale.minor_pentatonic", "pentatônica menor"),
+    ("scale.degree", "Grau {} ({})"),
+    ("scale.outside", "{} não está em {}"),
+    ("scale.hint", "{}: escala, {}: {}; a tônica é a nota-alvo do afinador"),
+    ("scale.follow_on", "seguir a escala"),
+    ("scale.follow_off", "parar de seguir"),
+    ("scale.averages", "Afinação média por grau"),
+    ("exercise.sequence", "Arpejos"),
+    ("sequence.title", "Treino de arpejos e sequências"),
+    ("sequence.major", "Arpejo maior em {}"),
+    ("sequence.minor", "Arpejo menor em {}"),
+    ("sequence.dominant", "Sétima da dominante em {}"),
+    ("sequence.diminished", "Sétima diminuta em {}"),
+    ("sequence.play", "Toque {} (nota {} de {})"),
+    ("sequence.stop", "{}: parar"),
+    ("sequence.start", "{}: começar, {}: escolher a sequência"),
+    ("sequence.chart", "Erro por nota (cents)"),
+    ("sequence.skipped", "pulada"),
+    ("sequence.average", "Erro médio {}"),
+    ("spectrogram.title", "Espectrograma (tempo →, frequência ↑)"),
+    ("exercise.double_stop", "Cordas duplas"),
+    ("double_stop.title", "Analisador de intervalos"),
+    ("interval.P1", "uníssono"),
+    ("double_stop.compound", "{} composta"),
+    ("double_stop.notes", "{} e {}"),
+    ("double_stop.size", "{}: {}"),
+    ("double_stop.equal", "Temperado {}"),
+    ("double_stop.just", "Justo {} {}"),
+    ("double_stop.listening", "Aguardando duas notas..."),
+    ("double_stop.hint", "Sustente duas notas juntas, em cordas duplas ou sobre um bordão"),
+    ("double_stop.comparison", "Intervalo em relação ao temperamento igual (cents)"),
+    ("double_stop.legend", "● medido   │ temperado   J justo"),
+    ("help.reference", "Capturar a nota sustentada como referência"),
+    ("help.clear_references", "Apagar as referências capturadas"),
+    ("mode.reference", "Instrumento de referência"),
+    ("mode.reference_notes", "{} notas"),
+    ("notice.reference", "Referência {} capturada em {} Hz"),
+    ("notice.reference_none", "Sustente uma nota estável para capturá-la como referência"),
+    ("notice.references_cleared", "Referências apagadas; afinando pelo A4 novamente"),
+    ("target.reference", "Ref {} {}"),
+    ("help.capo", "Tabela de capotraste do instrumento"),
+    ("capo.title", "Tabela de capotraste: {} ({} fecha)"),
+    ("capo.fret", "Casa"),
+    ("capo.strings", "Cordas soltas"),
+    ("capo.hint", "Colunas são formatos de acorde abertos; linhas mostram como soam em cada casa"),
+    ("statusbar.low_register", "registro grave"),
+    ("setting.register", "Registro"),
+    ("register.auto", "automático ({})"),
+    ("register.normal", "normal"),
+    ("register.low", "grave (baixo)"),
+    ("register.high", "agudo (apito, flautim)"),
+    ("statusbar.high_register", "registro agudo"),
+    ("coincident.title", "Parciais coincidentes: {} com {} (inferior:superior, batimentos/s)"),
+    ("coincident.title_none", "Parciais coincidentes com {}"),
+    ("coincident.rate", "{}/s"),
+    ("coincident.no_pairs", "Nenhum parcial coincide neste intervalo"),
+    ("help.continuous", "Altura contínua: sem ajuste à nota, escala de duas oitavas"),
+    ("mode.continuous", "Altura contínua"),
+    ("continuous.title", "Altura contínua vs {} ({} Hz)"),
+    ("help.copy", "Copiar a leitura para a área de transferência"),
+    ("clipboard.reading", "{} {} Hz, {}c vs {}"),
+    ("notice.copied", "Copiado: {}"),
+    ("notice.copy_none", "Sustente uma nota estável para copiar a leitura"),
+    ("notice.copy_failed", "Não foi possível copiar a leitura: {}"),
+    ("help.snapshot", "Salvar uma captura da tela em arquivos"),
+    ("notice.snapshot", "Captura salva em {}"),
+    ("setting.countdown", "Contagem por corda"),
+    ("timing.running", "⏱ {}s"),
+    ("timing.over", "⏱ {}s a mais"),
+    ("timing.done", "✓ {}s"),
+    ("setting.notify", "Notificação do sistema"),
+    ("desktop.title", "{} afinada"),
+    ("desktop.body", "{} cents a {} Hz"),
+    ("setting.bell", "Campainha do terminal"),
+    ("bell.enter", "ao entrar"),
+    ("bell.both", "ao entrar e sair"),
+    ("setting.dead_zone", "Zona morta da agulha"),
+    ("statusbar.rate", "{} leituras/s"),
+    ("setting.mode", "Modo de análise"),
+    ("mode.accurate", "preciso"),
+    ("mode.fast", "rápido"),
+    ("statusbar.fast", "modo rápido"),
+    ("setting.auto_lock", "Travar alvo"),
+    ("lock.following", "cromático"),
+    ("lock.locked", "travado"),
+    ("setting.off_note", "Alarme de nota errada"),
+    ("offnote.warning", "⚠ Ouvindo {}, o alvo é {}: corda errada?"),
+];
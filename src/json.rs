@@ -0,0 +1,32 @@
+//! Helpers for the JSON the tuner writes by hand: the server and daemon
+//! feeds, reports, and history exports.
+
+/// `text` as the inside of a JSON string, with control characters as
+/// `\uXXXX` escapes.
+pub fn json_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c < ' ' => {
+                escaped.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::json_escape;
+
+    #[test]
+    fn escapes_quotes_backslashes_and_control_characters() {
+        assert_eq!(json_escape("Guitar (Drop D)"), "Guitar (Drop D)");
+        assert_eq!(json_escape(r#"12" "Bass\"#), r#"12\" \"Bass\\"#);
+        assert_eq!(json_escape("tab\there\nnew\u{1}"), "tab\\u0009here\\u000anew\\u0001");
+        assert_eq!(json_escape("Viola d'amore \u{e9}"), "Viola d'amore \u{e9}");
+    }
+}
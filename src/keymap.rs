@@ -0,0 +1,247 @@
+use crossterm::event::KeyCode;
+
+use crate::config::{Config, Value};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Action {
+    Quit,
+    Help,
+    Pause,
+    NextPage,
+    PreviousPage,
+    Page(usize),
+    NotePrevious,
+    NoteNext,
+    OctaveUp,
+    OctaveDown,
+    Select,
+    A4Up,
+    A4Down,
+    Beat,
+    Reference,
+    ClearReferences,
+    Copy,
+    Snapshot,
+    Metronome,
+    MetronomePattern,
+    TempoDown,
+    TempoUp,
+    TempoDownCoarse,
+    TempoUpCoarse,
+    Chime,
+    Flash,
+    Output,
+    PitchPipe,
+    PipeAll,
+    Instrument,
+    Capo,
+    Waveform,
+    History,
+    Piano,
+    Damping,
+    MeterRange,
+    VerticalMeter,
+    Continuous,
+    BigNote,
+    Zen,
+    Theme,
+    Save,
+}
+
+/// Every action with its name in the `[keys]` config section and its default keys.
+pub const ACTIONS: [(Action, &str, &[KeyCode]); 47] = [
+    (Action::Quit, "quit", &[KeyCode::Esc, KeyCode::Char('q')]),
+    (Action::Help, "help", &[KeyCode::Char('?')]),
+    (Action::Pause, "pause", &[KeyCode::Char(' ')]),
+    (Action::NextPage, "next_page", &[KeyCode::Tab]),
+    (Action::PreviousPage, "previous_page", &[KeyCode::BackTab]),
+    (Action::Page(0), "page_1", &[KeyCode::Char('1')]),
+    (Action::Page(1), "page_2", &[KeyCode::Char('2')]),
+    (Action::Page(2), "page_3", &[KeyCode::Char('3')]),
+    (Action::Page(3), "page_4", &[KeyCode::Char('4')]),
+    (Action::Page(4), "page_5", &[KeyCode::Char('5')]),
+    (Action::Page(5), "page_6", &[KeyCode::Char('6')]),
+    (Action::NotePrevious, "note_previous", &[KeyCode::Left, KeyCode::Char('h')]),
+    (Action::NoteNext, "note_next", &[KeyCode::Right, KeyCode::Char('l')]),
+    (Action::OctaveUp, "octave_up", &[KeyCode::Up, KeyCode::Char('k')]),
+    (Action::OctaveDown, "octave_down", &[KeyCode::Down, KeyCode::Char('j')]),
+    (Action::Select, "select", &[KeyCode::Enter]),
+    (Action::A4Up, "a4_up", &[KeyCode::Char('+'), KeyCode::Char('=')]),
+    (Action::A4Down, "a4_down", &[KeyCode::Char('-'), KeyCode::Char('_')]),
+    (Action::Beat, "beat", &[KeyCode::Char('b')]),
+    (Action::Reference, "reference", &[KeyCode::Char('x')]),
+    (Action::ClearReferences, "clear_references", &[KeyCode::Char('X')]),
+    (Action::Copy, "copy", &[KeyCode::Char('y')]),
+    (Action::Snapshot, "snapshot", &[KeyCode::Char('e')]),
+    (Action::Metronome, "metronome", &[KeyCode::Char('m')]),
+    (Action::MetronomePattern, "metronome_pattern", &[KeyCode::Char('M')]),
+    (Action::TempoDown, "tempo_down", &[KeyCode::Char('[')]),
+    (Action::TempoUp, "tempo_up", &[KeyCode::Char(']')]),
+    (Action::TempoDownCoarse, "tempo_down_coarse", &[KeyCode::Char('{')]),
+    (Action::TempoUpCoarse, "tempo_up_coarse", &[KeyCode::Char('}')]),
+    (Action::Chime, "chime", &[KeyCode::Char('c')]),
+    (Action::Flash, "flash", &[KeyCode::Char('f')]),
+    (Action::Output, "output_device", &[KeyCode::Char('o')]),
+    (Action::PitchPipe, "pitch_pipe", &[KeyCode::Char('p')]),
+    (Action::PipeAll, "pipe_all", &[KeyCode::Char('a')]),
+    (Action::Instrument, "instrument", &[KeyCode::Char('i')]),
+    (Action::Capo, "capo", &[KeyCode::Char('C')]),
+    (Action::Waveform, "waveform", &[KeyCode::Char('w')]),
+    (Action::History, "history", &[KeyCode::Char('g')]),
+    (Action::Piano, "piano", &[KeyCode::Char('K')]),
+    (Action::Damping, "damping", &[KeyCode::Char('d')]),
+    (Action::MeterRange, "meter_range", &[KeyCode::Char('r')]),
+    (Action::VerticalMeter, "vertical_meter", &[KeyCode::Char('v')]),
+    (Action::Continuous, "continuous", &[KeyCode::Char('F')]),
+    (Action::BigNote, "big_note", &[KeyCode::Char('n')]),
+    (Action::Zen, "zen", &[KeyCode::Char('z')]),
+    (Action::Theme, "theme", &[KeyCode::Char('t')]),
+    (Action::Save, "save", &[KeyCode::Char('s')]),
+];
+
+#[derive(Clone)]
+pub struct Keymap {
+    bindings: Vec<(KeyCode, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = ACTIONS
+            .iter()
+            .flat_map(|(action, _, keys)| keys.iter().map(move |&key| (key, *action)))
+            .collect();
+        Keymap { bindings }
+    }
+}
+
+impl Keymap {
+    /// Starts from the default keys and replaces those of every action named
+    /// in `[keys]`, e.g. `quit = "q"` or `note_previous = ["left", "h"]`.
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        let mut keymap = Keymap::default();
+
+        for (name, value) in config.section("keys") {
+            let action = ACTIONS
+                .iter()
+                .find(|(_, n, _)| n == name)
+                .map(|(action, _, _)| *action)
+                .ok_or_else(|| format!("Unknown key action: {}", name))?;
+            let specs = match value {
+                Value::String(spec) => vec![spec.as_str()],
+                Value::Array(items) => items
+                    .iter()
+                    .map(|item| match item {
+                        Value::String(spec) => Ok(spec.as_str()),
+                        _ => Err(format!("keys.{} must list key names", name)),
+                    })
+                    .collect::<Result<_, _>>()?,
+                _ => return Err(format!("keys.{} must be a key name or a list of key names", name)),
+            };
+
+            keymap.bindings.retain(|(_, a)| *a != action);
+            for spec in specs {
+                let key = parse_key(spec).ok_or_else(|| format!("keys.{}: unknown key `{}`", name, spec))?;
+                keymap.bindings.push((key, action));
+            }
+        }
+
+        for (i, (key, action)) in keymap.bindings.iter().enumerate() {
+            if let Some((_, other)) = keymap.bindings[i + 1..].iter().find(|(k, _)| k == key) {
+                return Err(format!(
+                    "Key {} is bound to both {} and {}",
+                    key_label(*key),
+                    action_name(*action),
+                    action_name(*other)
+                ));
+            }
+        }
+
+        Ok(keymap)
+    }
+
+    pub fn action(&self, key: KeyCode) -> Option<Action> {
+        self.bindings.iter().find(|(k, _)| *k == key).map(|(_, action)| *action)
+    }
+
+    /// The first key bound to `action`, as shown in hints and the help overlay.
+    pub fn label(&self, action: Action) -> String {
+        self.bindings
+            .iter()
+            .find(|(_, a)| *a == action)
+            .map_or_else(|| "-".to_string(), |(key, _)| key_label(*key))
+    }
+
+    /// Labels of a pair of opposite actions, e.g. `←/→`.
+    pub fn pair_label(&self, first: Action, second: Action) -> String {
+        format!("{}/{}", self.label(first), self.label(second))
+    }
+}
+
+fn action_name(action: Action) -> &'static str {
+    ACTIONS.iter().find(|(a, _, _)| *a == action).map_or("?", |(_, name, _)| name)
+}
+
+fn parse_key(spec: &str) -> Option<KeyCode> {
+    let mut chars = spec.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+
+    let lower = spec.to_lowercase();
+    if let Some(rest) = lower.strip_prefix("shift+") {
+        let mut chars = rest.chars();
+        return match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_alphabetic() => Some(KeyCode::Char(c.to_ascii_uppercase())),
+            _ if rest == "tab" => Some(KeyCode::BackTab),
+            _ => None,
+        };
+    }
+    if let Some(number) = lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+        return (1..=12).contains(&number).then_some(KeyCode::F(number));
+    }
+
+    match lower.as_str() {
+        "esc" | "escape" => Some(KeyCode::Esc),
+        "enter" | "return" => Some(KeyCode::Enter),
+        "tab" => Some(KeyCode::Tab),
+        "backtab" => Some(KeyCode::BackTab),
+        "space" => Some(KeyCode::Char(' ')),
+        "backspace" => Some(KeyCode::Backspace),
+        "left" => Some(KeyCode::Left),
+        "right" => Some(KeyCode::Right),
+        "up" => Some(KeyCode::Up),
+        "down" => Some(KeyCode::Down),
+        "home" => Some(KeyCode::Home),
+        "end" => Some(KeyCode::End),
+        "pageup" => Some(KeyCode::PageUp),
+        "pagedown" => Some(KeyCode::PageDown),
+        "insert" => Some(KeyCode::Insert),
+        "delete" => Some(KeyCode::Delete),
+        _ => None,
+    }
+}
+
+pub fn key_label(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) if c.is_ascii_uppercase() => format!("Shift+{}", c),
+        KeyCode::Char(c) => c.to_ascii_uppercase().to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::BackTab => "Shift+Tab".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::Insert => "Ins".to_string(),
+        KeyCode::Delete => "Del".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        _ => "?".to_string(),
+    }
+}
@@ -0,0 +1,42 @@
+//! Pitch detection, note conversion, instrument presets, and audio I/O
+//! behind the `guitar-tuner` terminal app, usable on their own.
+//!
+//! ```
+//! use guitar_tuner::Tuner;
+//!
+//! let sample_rate = 44_100;
+//! let samples: Vec<f32> = (0..8192)
+//!     .map(|i| (2.0 * std::f32::consts::PI * 110.0 * i as f32 / sample_rate as f32).sin())
+//!     .collect();
+//!
+//! let mut tuner = Tuner::new(sample_rate);
+//! let freq = tuner.detect_frequency(&samples).unwrap();
+//! let (note, octave, _cents) = tuner.frequency_to_note(freq, 440.0);
+//! assert_eq!((note.as_str(), octave), ("A", 2));
+//! ```
+//!
+//! [`AudioCapture`] opens the default input device and streams mono `f32`
+//! chunks over a channel, ready to be fed to [`Tuner::detect_frequency`].
+//!
+//! On `wasm32` only the detection, note, and preset code is built, and the
+//! C API in `ffi` becomes the module's exports; `web/guitar_tuner.js` wraps
+//! them for JavaScript.
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod audio;
+pub mod detector;
+#[cfg(any(feature = "ffi", target_arch = "wasm32"))]
+pub mod ffi;
+pub mod metronome;
+pub mod notify;
+pub mod presets;
+#[doc(hidden)]
+pub mod signal;
+pub mod synth;
+pub mod tuner;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use audio::{AudioCapture, AudioPlayback};
+pub use detector::PitchDetector;
+pub use presets::{Preset, PRESETS};
+pub use tuner::{Partial, Tuner};
@@ -0,0 +1,178 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::config::Config;
+use crate::csvlog::utc_timestamp;
+
+const APP_DIR: &str = "rust_tuner";
+const LOG_FILE: &str = "rust_tuner.log";
+/// A file is rotated once it passes this size; `KEEP` older ones are kept
+/// as `rust_tuner.log.1` (newest) to `rust_tuner.log.3`.
+const MAX_SIZE: u64 = 1024 * 1024;
+const KEEP: usize = 3;
+const STATS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Directory for the log file: `$XDG_STATE_HOME/rust_tuner` on Linux
+/// (`~/.local/state/rust_tuner`), `~/Library/Logs/rust_tuner` on macOS, and
+/// `%LOCALAPPDATA%\rust_tuner` on Windows.
+pub fn log_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        std::env::var_os("LOCALAPPDATA").map(|dir| PathBuf::from(dir).join(APP_DIR))
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Logs").join(APP_DIR))
+    } else {
+        std::env::var_os("XDG_STATE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state")))
+            .map(|dir| dir.join(APP_DIR))
+    }
+}
+
+/// Installs the file logger from `[log] level` (`off`, `error`, `warn`,
+/// `info`, `debug`, `trace`; default `warn`) and `[log] file`, or from
+/// `level` when given on the command line.
+pub fn init(config: &Config, level: Option<&str>) -> Result<(), String> {
+    let name = level.or_else(|| config.get_str("log", "level")).unwrap_or("warn");
+    let filter = name
+        .parse::<LevelFilter>()
+        .map_err(|_| format!("Unknown log level: {} (expected off, error, warn, info, debug, or trace)", name))?;
+    if filter == LevelFilter::Off {
+        return Ok(());
+    }
+
+    let path = match config.get_str("log", "file") {
+        Some(path) => PathBuf::from(path),
+        None => log_dir().ok_or("No directory available for the log file")?.join(LOG_FILE),
+    };
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let file = open(&path)?;
+    let size = file.metadata().map_or(0, |m| m.len());
+
+    let logger = FileLogger {
+        filter,
+        output: Mutex::new(Output { path, file, size }),
+    };
+    log::set_boxed_logger(Box::new(logger)).map_err(|e| e.to_string())?;
+    log::set_max_level(filter);
+    Ok(())
+}
+
+fn open(path: &Path) -> Result<File, String> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+}
+
+struct FileLogger {
+    filter: LevelFilter,
+    output: Mutex<Output>,
+}
+
+struct Output {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl Output {
+    /// Shifts `rust_tuner.log.N` up by one, dropping the oldest, and starts a new file.
+    fn rotate(&mut self) -> std::io::Result<()> {
+        let numbered = |n: usize| {
+            let mut name = self.path.as_os_str().to_owned();
+            name.push(format!(".{}", n));
+            PathBuf::from(name)
+        };
+        for n in (1..KEEP).rev() {
+            let _ = fs::rename(numbered(n), numbered(n + 1));
+        }
+        fs::rename(&self.path, numbered(1))?;
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.filter
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!(
+            "{} {:<5} {}: {}\n",
+            utc_timestamp(SystemTime::now()),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+
+        // A logger has nowhere to report its own failures, so they are dropped.
+        let Ok(mut output) = self.output.lock() else {
+            return;
+        };
+        if output.size + line.len() as u64 > MAX_SIZE && output.size > 0 && output.rotate().is_err() {
+            return;
+        }
+        if output.file.write_all(line.as_bytes()).is_ok() {
+            output.size += line.len() as u64;
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut output) = self.output.lock() {
+            let _ = output.file.flush();
+        }
+    }
+}
+
+/// Counts analysis windows and detections and logs a summary at debug level
+/// every `STATS_INTERVAL`.
+pub struct DetectionStats {
+    windows: u32,
+    detections: u32,
+    busy: Duration,
+    since: Instant,
+}
+
+impl DetectionStats {
+    pub fn new() -> Self {
+        DetectionStats {
+            windows: 0,
+            detections: 0,
+            busy: Duration::ZERO,
+            since: Instant::now(),
+        }
+    }
+
+    pub fn record(&mut self, detected: bool, elapsed: Duration) {
+        if !log::log_enabled!(Level::Debug) {
+            return;
+        }
+        self.windows += 1;
+        self.detections += u32::from(detected);
+        self.busy += elapsed;
+
+        if self.since.elapsed() >= STATS_INTERVAL {
+            log::debug!(
+                "{} windows, {} with a pitch, {:.2} ms average analysis",
+                self.windows,
+                self.detections,
+                self.busy.as_secs_f64() * 1000.0 / self.windows as f64
+            );
+            *self = DetectionStats::new();
+        }
+    }
+}
@@ -1,114 +1,1104 @@
-mod audio;
-mod tuner;
+mod analyze;
+mod analysis;
+mod announce;
+mod autolock;
+mod bell;
+mod bigtext;
+mod cli;
+mod clipboard;
+mod coincident;
+mod config;
+mod csvlog;
+mod daemon;
+mod desktop;
+mod drift;
+mod headless;
+mod history;
+mod i18n;
+mod json;
+mod keymap;
+mod logfile;
+mod matching;
+mod midi;
+mod overlay;
+mod practice;
+mod replay;
+mod report;
+mod server;
+mod settings;
+mod shutdown;
+mod snapshot;
+mod sqlite;
+mod stats;
+mod stderr;
+mod theme;
+mod timing;
+mod tone;
 mod ui;
+mod wav;
 
-use audio::AudioCapture;
+use guitar_tuner::{audio, metronome, notify, presets, synth, tuner};
+
+use analysis::{Mode, Setup, Worker};
+use announce::Announcer;
+use bell::Bell;
+use audio::{AudioCapture, AudioPlayback, FrameSender};
+use cli::{Args, Command as CliCommand};
+use config::Config;
 use cpal::SampleRate;
-use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind};
+use csvlog::CsvLog;
+use crossbeam_channel::{select, Receiver, Sender};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
 use crossterm::execute;
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
 use ratatui::backend::CrosstermBackend;
+use keymap::{Action, Keymap};
+use history::History;
+use logfile::DetectionStats;
+use server::{Command, Server};
+use shutdown::Shutdown;
+use snapshot::Snapshots;
+use midi::{MidiIn, MidiOut};
+use notify::Notice;
+use overlay::OverlayFile;
+use report::TuningReport;
 use ratatui::Terminal;
 use std::io;
-use crossbeam_channel;
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
-use tuner::Tuner;
-use ui::{render_ui, UiState};
+use std::time::{Duration, Instant};
+use synth::Synth;
+use theme::Theme;
+use tuner::{Register, Tuner};
+use ui::{render_ui, AnalysisStatus, AudioError, Hit, Hits, Tab, TuningStatus, UiState};
 
 const NOTES: [&str; 12] = ["A", "A#", "B", "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#"];
+const CHIME_HOLD: Duration = Duration::from_millis(800);
+const PIPE_STEP: Duration = Duration::from_secs(2);
+const DEFAULT_REFRESH_RATE: f64 = 30.0;
+const DEFAULT_IDLE_REFRESH_RATE: f64 = 2.0;
+/// Used until an input device is open; the synth follows the output device's rate.
+const DEFAULT_SAMPLE_RATE: u32 = 44_100;
+/// An input stream that delivers nothing for this long is treated as lost.
+const STREAM_TIMEOUT: Duration = Duration::from_secs(3);
+/// How long without signal or input before the display drops to the idle refresh rate.
+const IDLE_AFTER: Duration = Duration::from_secs(2);
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut terminal = init_terminal()?;
+    let args = match Args::from_env() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    if args.help {
+        println!("{}", cli::usage());
+        return Ok(());
+    }
+    if args.version {
+        println!("guitar-tuner {}", env!("CARGO_PKG_VERSION"));
+        return Ok(());
+    }
+
+    let mut config = Config::load()?;
+    presets::add_custom(settings::custom_presets(&config)?)?;
+    args.apply(&mut config)?;
+    if let CliCommand::Tune | CliCommand::Daemon = args.command {
+        logfile::init(&config, args.log_level.as_deref())?;
+        log::info!("guitar-tuner {} starting", env!("CARGO_PKG_VERSION"));
+    }
+    match &args.command {
+        CliCommand::Tune if args.plain => {
+            return headless::run(&config, headless::Output::Plain, recorder(&config, &args)?);
+        }
+        CliCommand::Tune if args.headless => {
+            return headless::run(&config, headless::Output::Json, recorder(&config, &args)?);
+        }
+        CliCommand::Tune => {}
+        CliCommand::Analyze(path) => return analyze::run(&config, Path::new(path), args.format.as_deref()),
+        CliCommand::Devices => {
+            list_devices();
+            return Ok(());
+        }
+        CliCommand::Tone(note) => return tone::run(&config, note),
+        CliCommand::Replay(path) => return replay::run(&config, Path::new(path), args.algorithm.as_deref()),
+        CliCommand::Daemon => return daemon::run(&config, args.socket.as_deref(), recorder(&config, &args)?),
+        CliCommand::History => {
+            let query = history::Query {
+                since: args.since.clone(),
+                until: args.until.clone(),
+                instrument: args.instrument.as_deref().map(cli::find_preset).transpose()?.map(str::to_string),
+                string: args.string.clone(),
+                drift: args.drift,
+            };
+            return history::run(&config, &query, args.format.as_deref());
+        }
+    }
+    let recorder = recorder(&config, &args)?;
+    let remote = args.connect.as_deref().map(daemon::connect).transpose()?;
+    let shutdown = Shutdown::install()?;
+    i18n::init(&config)?;
+    let theme = if Theme::no_color_requested() {
+        Theme::builtin("mono").ok_or("mono theme missing")?
+    } else {
+        Theme::from_config(&config)?
+    };
+    // Config errors are reported before the terminal is taken over, where
+    // they would leave it in raw mode.
+    let mut ui_state = UiState::new(theme);
+    settings::apply_config(&config, &mut ui_state);
+    ui_state.keymap = Keymap::from_config(&config)?;
+    ui_state.practice.sequence.add_custom(settings::custom_sequences(&config)?);
+    // History is optional, so a build without SQLite only warns.
+    let mut history = match History::from_config(&config, &ui_state) {
+        Err(e) if !history::AVAILABLE => {
+            ui_state.push_notice(Notice::warning(e));
+            None
+        }
+        history => history?,
+    };
+    // Checked before any device is opened so a typo is reported, not shown as an audio error.
+    let setup = Setup::from_config(&config);
+    let tuner = setup.tuner(DEFAULT_SAMPLE_RATE, Register::Normal)?;
+
+    let screen_reader = args.screen_reader;
+    let csv_path = args.csv.clone().or_else(|| config.get_str("log", "csv").map(str::to_string));
+    let mut csv_log = csv_path.map(|path| CsvLog::open(Path::new(&path))).transpose()?;
+    let overlay_path = args.overlay.clone().or_else(|| config.get_str("overlay", "file").map(str::to_string));
+    let mut overlay = overlay_path.map(|path| OverlayFile::new(Path::new(&path)));
+    let report_path = args.report.clone().or_else(|| config.get_str("report", "file").map(str::to_string));
+    let mut report = report_path.map(|path| TuningReport::new(Path::new(&path)));
+    let snapshots = Snapshots::from_config(&config);
+    let mut midi_out = if args.midi_out || config.get_bool("midi", "output").unwrap_or(false) {
+        Some(MidiOut::open(&config)?)
+    } else {
+        None
+    };
+    let listen = args.listen.clone().or_else(|| config.get_str("server", "listen").map(str::to_string));
+    let server = listen.map(|spec| Server::start(&spec)).transpose()?;
+    let mut midi_in = if args.midi_in || config.get_bool("midi", "input").unwrap_or(false) {
+        Some(MidiIn::open()?)
+    } else {
+        None
+    };
+    let mut terminal = if screen_reader {
+        enable_raw_mode()?;
+        None
+    } else {
+        Some(init_terminal()?)
+    };
+    let mut announcer = Announcer::new();
+    let mut bell = Bell::from_config(&config);
+
+    let (tx, rx) = audio::frame_channel(audio::FRAME_QUEUE);
+    let (notice_tx, notice_rx) = crossbeam_channel::unbounded();
+    // Opened before any device, since audio libraries print while they start.
+    // Without it their messages may garble the screen, which is no reason to stop.
+    let stderr_capture = match terminal {
+        Some(_) => stderr::Capture::start(args.verbose.then(|| notice_tx.clone()))
+            .map_err(|e| ui_state.push_notice(Notice::warning(e)))
+            .ok(),
+        None => None,
+    };
 
-    let audio_capture = AudioCapture::new()?;
-    let sample_rate = audio_capture.sample_rate();
-    let (tx, rx) = crossbeam_channel::unbounded();
+    let synth = Arc::new(Mutex::new(Synth::new(DEFAULT_SAMPLE_RATE)));
+    let configured_output = config.get_str("audio", "output_device").and_then(|name| {
+        open_output(Some(name), &synth, &notice_tx)
+            .map_err(|e| notice_tx.send(Notice::warning(e)))
+            .ok()
+    });
+    let mut output_stream = configured_output.or_else(|| open_output(None, &synth, &notice_tx).ok());
 
-    let stream = audio_capture.start_capture(SampleRate(sample_rate), tx)?;
+    let (worker, remote_status) = match remote {
+        Some(remote) => {
+            let (worker, status) = remote.spawn(notice_tx.clone());
+            (worker, Some(status))
+        }
+        None => (Worker::spawn(rx, tuner, recorder), None),
+    };
+    ui_state.output_device = output_stream.as_ref().map(|(name, _)| name.clone());
+    let input = Input { setup: &setup, samples: &tx, notices: &notice_tx };
+    let mut stream = match remote_status {
+        Some(status) => {
+            ui_state.analysis = status;
+            None
+        }
+        None => input.start(config.get_str("audio", "input_device"), &worker, &mut ui_state),
+    };
+    let mut last_samples = Instant::now();
+    let mut hits = Hits::new();
+    let mut detection_stats = DetectionStats::new();
+    let mut dropped_frames = 0;
+
+    let frame_interval = refresh_interval(config.get_f64("ui", "refresh_rate"), DEFAULT_REFRESH_RATE);
+    let idle_interval = refresh_interval(config.get_f64("ui", "idle_refresh_rate"), DEFAULT_IDLE_REFRESH_RATE)
+        .max(frame_interval);
+    let mut last_draw: Option<Instant> = None;
+    let events = spawn_event_reader();
+    if let Some(server) = &server {
+        ui_state.push_notice(Notice::info(i18n::trf("notice.server", &[&server.address()])));
+        server.send(server::reading_json(&ui_state));
+    }
+
+    // Errors leave the loop with `break 'ui Err(..)` so the terminal is
+    // restored before they are reported.
+    let outcome: Result<(), Box<dyn std::error::Error>> = 'ui: loop {
+        if shutdown.requested() {
+            log::info!("Stopping on a signal");
+            break Ok(());
+        }
+        for notice in notice_rx.try_iter() {
+            if terminal.is_none() {
+                if let Err(e) = announcer.notice(&notice) {
+                    break 'ui Err(e.into());
+                }
+            }
+            ui_state.push_notice(notice);
+        }
+        ui_state.expire_notices();
+        match midi_in.as_ref().map(MidiIn::poll) {
+            Some(Ok(Some(number))) => {
+                ui_state.set_target_number(number);
+                ui_state.last_activity = Instant::now();
+                last_draw = None;
+            }
+            Some(Err(e)) => {
+                ui_state.push_notice(Notice::error(e));
+                midi_in = None;
+            }
+            _ => {}
+        }
+        let dropped = tx.dropped();
+        if dropped > dropped_frames {
+            log::warn!(target: "audio", "Analysis fell behind; {} audio frames dropped so far", dropped);
+            dropped_frames = dropped;
+            last_draw = None;
+        }
+        ui_state.analysis.dropped_frames = dropped;
+        for command in server.iter().flat_map(Server::commands) {
+            match command {
+                Command::Target(note, octave) => {
+                    ui_state.target_note = note;
+                    ui_state.target_octave = octave;
+                }
+                Command::A4(hz) => ui_state.a4_freq = hz,
+                Command::Preset(index) => {
+                    ui_state.preset = index;
+                    ui_state.pipe_string = 0;
+                    ui_state.pipe_sequence_start = None;
+                }
+            }
+            ui_state.last_activity = Instant::now();
+            last_draw = None;
+            // Answer the next status request with the change even while paused.
+            if let Some(server) = &server {
+                server.send(server::reading_json(&ui_state));
+            }
+        }
+        if stream.is_some() {
+            input.follow_settings(&worker, &mut ui_state);
+        }
+        ui_state.animate_needle();
 
-    let mut tuner = Tuner::new(sample_rate);
-    let mut ui_state = UiState::new();
-    let mut audio_buffer: Vec<f32> = Vec::new();
+        let idle = ui_state.last_activity.elapsed() >= IDLE_AFTER && !ui_state.is_animating();
+        let interval = if idle { idle_interval } else { frame_interval };
+        if last_draw.is_none_or(|at| at.elapsed() >= interval) {
+            match terminal.as_mut() {
+                Some(terminal) => {
+                    let frame = match terminal.draw(|f| hits = render_ui(f, &ui_state)) {
+                        Ok(frame) => frame,
+                        Err(e) => break Err(e.into()),
+                    };
+                    if ui_state.snapshot_requested {
+                        ui_state.snapshot_requested = false;
+                        let notice = match snapshots.save(frame.buffer, ui_state.theme.name == "light") {
+                            Ok(path) => Notice::info(i18n::trf("notice.snapshot", &[&path.display()])),
+                            Err(e) => Notice::error(e),
+                        };
+                        ui_state.push_notice(notice);
+                    }
+                }
+                None => {
+                    if let Err(e) = announcer.update(&ui_state) {
+                        break Err(e.into());
+                    }
+                }
+            }
+            last_draw = Some(Instant::now());
+        }
 
-    loop {
-        terminal.draw(|f| render_ui(f, &ui_state))?;
+        // Sleep until a key or mouse event, audio, or the next frame, whichever comes first.
+        let wait = last_draw.map_or(Duration::ZERO, |at| interval.saturating_sub(at.elapsed())).min(frame_interval);
+        let mut received = None;
+        let mut input_event = None;
+        select! {
+            recv(events) -> event => input_event = Some(event),
+            recv(worker.results()) -> analysis => received = analysis.ok(),
+            default(wait) => {}
+        }
 
-        if event::poll(Duration::from_millis(16))? {
-            if let Event::Key(key) = event::read()? {
+        if let Some(event) = input_event {
+            let event = match event {
+                Ok(Ok(event)) => event,
+                Ok(Err(e)) => break Err(e.into()),
+                Err(_) => break Ok(()),
+            };
+            if is_ctrl_c(&event) {
+                log::info!("Stopping on Ctrl-C");
+                break Ok(());
+            }
+            let waking = ui_state.is_dimmed();
+            ui_state.last_activity = Instant::now();
+            last_draw = None;
+            if waking {
+                // The first key or click only wakes a dimmed display.
+            } else if let Event::Mouse(mouse) = event {
+                handle_mouse(mouse, &hits, &mut output_stream, &synth, &notice_tx, &mut ui_state);
+            } else if let Event::Key(key) = event {
                 if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Esc => break,
-                        KeyCode::Left => {
-                            let current_idx = NOTES
-                                .iter()
-                                .position(|&n| n == ui_state.target_note)
-                                .unwrap_or(0);
-                            let new_idx = (current_idx + 11) % 12;
-                            ui_state.target_note = NOTES[new_idx].to_string();
-                        }
-                        KeyCode::Right => {
-                            let current_idx = NOTES
-                                .iter()
-                                .position(|&n| n == ui_state.target_note)
-                                .unwrap_or(0);
-                            let new_idx = (current_idx + 1) % 12;
-                            ui_state.target_note = NOTES[new_idx].to_string();
-                        }
-                        KeyCode::Up => {
-                            ui_state.target_octave = (ui_state.target_octave + 1).min(8);
-                        }
-                        KeyCode::Down => {
-                            ui_state.target_octave = (ui_state.target_octave - 1).max(0);
-                        }
-                        KeyCode::Char('+') | KeyCode::Char('=') => {
-                            if ui_state.a4_freq < 450.0 {
-                                ui_state.a4_freq = (ui_state.a4_freq + 0.1).min(450.0);
-                            }
-                        }
-                        KeyCode::Char('-') | KeyCode::Char('_') => {
-                            if ui_state.a4_freq > 432.0 {
-                                ui_state.a4_freq = (ui_state.a4_freq - 0.1).max(432.0);
-                            }
+                    ui_state.lock.unlock();
+                }
+                let action = ui_state.keymap.action(key.code);
+                if let (KeyEventKind::Press, Some(error)) = (key.kind, ui_state.audio_error.as_mut()) {
+                    match action {
+                        Some(Action::Quit) => break Ok(()),
+                        Some(Action::OctaveUp) => error.move_cursor(-1),
+                        Some(Action::OctaveDown) => error.move_cursor(1),
+                        Some(Action::Select) => {
+                            let device = error.selected().map(str::to_string);
+                            stream = input.start(device.as_deref(), &worker, &mut ui_state);
+                            last_samples = Instant::now();
                         }
                         _ => {}
                     }
+                } else if key.kind == KeyEventKind::Press && ui_state.show_help {
+                    if matches!(action, Some(Action::Quit | Action::Help)) {
+                        ui_state.show_help = false;
+                    }
+                } else if key.kind == KeyEventKind::Press && ui_state.show_capo {
+                    if matches!(action, Some(Action::Quit | Action::Capo)) {
+                        ui_state.show_capo = false;
+                    }
+                } else if let (KeyEventKind::Press, Some(action)) = (key.kind, action) {
+                    let flow = handle_action(
+                        action,
+                        &mut config,
+                        &mut output_stream,
+                        &synth,
+                        &notice_tx,
+                        &worker,
+                        &mut ui_state,
+                    );
+                    if flow.is_break() {
+                        break Ok(());
+                    }
                 }
             }
         }
 
-        while let Ok(samples) = rx.try_recv() {
-            audio_buffer.extend_from_slice(&samples);
-            if audio_buffer.len() > 4096 {
-                if let Some(freq) = tuner.detect_frequency(&audio_buffer) {
-                    let (note, octave, _deviation_cents) =
-                        tuner.frequency_to_note(freq, ui_state.a4_freq);
-                    let target_freq =
-                        Tuner::note_name_to_frequency(&ui_state.target_note, ui_state.target_octave, ui_state.a4_freq);
-                    let target_deviation = 1200.0 * (freq / target_freq).log2();
-
-                    ui_state.current_freq = Some(freq);
-                    ui_state.current_note = Some(note);
-                    ui_state.current_octave = Some(octave);
-                    ui_state.deviation_cents = Some(target_deviation);
-                } else {
-                    ui_state.current_freq = None;
-                    ui_state.current_note = None;
-                    ui_state.current_octave = None;
-                    ui_state.deviation_cents = None;
+        if stream.is_some() && last_samples.elapsed() >= STREAM_TIMEOUT {
+            let message = i18n::trf("audio_error.stalled", &[&ui_state.analysis.input_device, &STREAM_TIMEOUT.as_secs()]);
+            log::error!(target: "audio", "{}", message);
+            stream = None;
+            ui_state.audio_error = Some(AudioError::new(message));
+            last_draw = None;
+        }
+
+        for analysis in received.into_iter().chain(worker.results().try_iter()) {
+            last_samples = Instant::now();
+            if ui_state.paused_at.is_some() {
+                continue;
+            }
+            ui_state.push_waveform(&analysis.samples);
+            ui_state.spectrum = analysis.spectrum;
+            ui_state.push_spectrogram();
+            let nearest = analysis.freq.map(|freq| Tuner::nearest_note(freq, ui_state.a4_freq));
+            if ui_state.auto_locking() {
+                let lock_after = Duration::from_secs_f32(ui_state.auto_lock);
+                let heard = nearest.as_ref().map(|(note, octave, cents)| (note.as_str(), *octave, *cents));
+                if let Some((note, octave)) = ui_state.lock.update(Instant::now(), lock_after, heard) {
+                    ui_state.target_note = note;
+                    ui_state.target_octave = octave.clamp(0, 8);
                 }
-                audio_buffer.drain(0..audio_buffer.len().saturating_sub(2048));
+            }
+            if let (Some(freq), Some((note, octave, _deviation_cents))) = (analysis.freq, nearest) {
+                let target_freq = ui_state.target_freq();
+                let target_deviation = 1200.0 * (freq / target_freq).log2();
+
+                ui_state.current_freq = Some(freq);
+                ui_state.current_note = Some(note);
+                ui_state.current_octave = Some(octave);
+                ui_state.deviation_cents = Some(target_deviation);
+                ui_state.push_deviation(target_deviation);
+                ui_state.update_needle(Some(target_deviation));
+                ui_state.beat_rate = Some(freq - target_freq);
+                ui_state.partials = analysis.partials;
+                ui_state.last_activity = Instant::now();
+            } else {
+                ui_state.current_freq = None;
+                ui_state.current_note = None;
+                ui_state.current_octave = None;
+                ui_state.deviation_cents = None;
+                ui_state.beat_rate = None;
+                ui_state.partials.clear();
+                ui_state.update_needle(None);
+            }
+            ui_state.track_reading();
+            if ui_state.tab == Tab::Practice {
+                let (target, deviation) = (ui_state.target_number(), ui_state.deviation_cents);
+                let (threshold, a4_freq) = (ui_state.close_cents, ui_state.a4_freq);
+                if let Some(number) =
+                    ui_state.practice.hear(analysis.freq, target, deviation, threshold, Instant::now(), a4_freq)
+                {
+                    ui_state.set_target_number(number);
+                }
+                ui_state.practice.hear_spectrum(&ui_state.spectrum, ui_state.spectrum_bin_hz);
+            }
+            if let Some(report) = report.as_mut() {
+                report.update(&ui_state);
+            }
+            if let Some(Err(e)) = history.as_mut().map(|history| history.record(&ui_state)) {
+                let _ = notice_tx.send(Notice::error(e));
+                history = None;
+            }
+            if let (Some(log), Some(reading)) = (csv_log.as_mut(), ui_state.stable_reading()) {
+                let target = format!("{}{}", ui_state.target_note, ui_state.target_octave);
+                if let Err(e) = log.record(reading, &target) {
+                    let _ = notice_tx.send(Notice::error(e));
+                    csv_log = None;
+                }
+            }
+            if let Some(midi) = midi_out.as_mut() {
+                let note = ui_state.stable_reading().and_then(|r| midi::midi_note(r.freq, ui_state.a4_freq));
+                if let Err(e) = midi.update(note) {
+                    let _ = notice_tx.send(Notice::error(e));
+                    midi_out = None;
+                }
+            }
+            ui_state.update_peak(ui_state.deviation_cents);
+            ui_state.analysis.record(analysis.elapsed);
+            detection_stats.record(analysis.freq.is_some(), analysis.elapsed);
+            if let Some(server) = &server {
+                server.send(server::reading_json(&ui_state));
+            }
+            if let Some(Err(e)) = overlay.as_mut().map(|file| file.update(&ui_state)) {
+                let _ = notice_tx.send(Notice::error(e));
+                overlay = None;
             }
         }
 
-        thread::sleep(Duration::from_millis(16));
-    }
+        let held = update_in_tune_hold(&mut ui_state);
+        let sounding = ui_state.deviation_cents.is_some();
+        if let Err(e) = bell.update(ui_state.bell, ui_state.in_tune_since.is_some(), sounding) {
+            log::warn!(target: "bell", "Failed to ring the terminal bell: {}", e);
+        }
+        update_synth(&synth, &mut ui_state, held);
+        if held && ui_state.desktop_notify {
+            if let Err(e) = notify_in_tune(&ui_state) {
+                ui_state.push_notice(Notice::error(e));
+                ui_state.desktop_notify = false;
+            }
+        }
+        let confirmed_since = ui_state.in_tune_since.filter(|_| ui_state.in_tune_confirmed);
+        let (note, octave) = (&ui_state.target_note, ui_state.target_octave);
+        ui_state.string_timer.update(note, octave, ui_state.deviation_cents, confirmed_since);
+    };
 
+    log::info!("Exiting");
+    drop(output_stream);
     drop(stream);
-    restore_terminal(terminal)?;
+    drop(stderr_capture);
+    match terminal {
+        Some(terminal) => restore_terminal(terminal)?,
+        None => disable_raw_mode()?,
+    }
+    outcome?;
+    if let Some(Err(e)) = history.as_ref().map(|history| history.finish(&ui_state)) {
+        eprintln!("{}", e);
+    }
+    if let Some(report) = report.as_mut() {
+        match report.write(&ui_state) {
+            Ok(path) => println!("Tuning report written to {}", path.display()),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+
+    if config.get_bool("ui", "save_on_exit").unwrap_or(true) {
+        // A game's random target is not the one to come back to.
+        if let Some(number) = ui_state.practice.game.stop().or(ui_state.practice.warmup.stop()) {
+            ui_state.set_target_number(number);
+        }
+        settings::store_config(&mut config, &ui_state);
+        if let Err(e) = config.save() {
+            eprintln!("Could not save settings: {}", e);
+        }
+    }
     Ok(())
 }
 
+fn handle_action(
+    action: Action,
+    config: &mut Config,
+    output_stream: &mut Option<(String, cpal::Stream)>,
+    synth: &Arc<Mutex<Synth>>,
+    notices: &Sender<Notice>,
+    worker: &Worker,
+    ui_state: &mut UiState,
+) -> ControlFlow<()> {
+    match action {
+        Action::Quit => return ControlFlow::Break(()),
+        Action::Help => ui_state.show_help = true,
+        Action::Capo => ui_state.show_capo = true,
+        Action::Pause => {
+            ui_state.toggle_pause();
+            worker.reset();
+        }
+        Action::NextPage => ui_state.tab = ui_state.tab.next(),
+        Action::PreviousPage => ui_state.tab = ui_state.tab.previous(),
+        Action::Page(index) => {
+            if let Some(&tab) = ui::TABS.get(index) {
+                ui_state.tab = tab;
+            }
+        }
+        Action::OctaveUp if ui_state.tab == Tab::Settings => {
+            let count = settings::SETTINGS.len();
+            ui_state.settings_cursor = (ui_state.settings_cursor + count - 1) % count;
+        }
+        Action::OctaveDown if ui_state.tab == Tab::Settings => {
+            ui_state.settings_cursor = (ui_state.settings_cursor + 1) % settings::SETTINGS.len();
+        }
+        Action::NotePrevious | Action::NoteNext | Action::Select if ui_state.tab == Tab::Settings => {
+            let delta = if action == Action::NotePrevious { -1 } else { 1 };
+            let setting = settings::SETTINGS[ui_state.settings_cursor];
+            if setting == settings::Setting::OutputDevice {
+                cycle_output_device(delta, output_stream, synth, notices, ui_state);
+            } else {
+                setting.adjust(ui_state, delta);
+            }
+            ui_state.settings_message = None;
+        }
+        Action::Select if ui_state.tab == Tab::Practice => {
+            if let Some(number) = ui_state.practice.select(ui_state.target_number(), Instant::now()) {
+                ui_state.set_target_number(number);
+            }
+        }
+        Action::OctaveUp | Action::OctaveDown
+            if ui_state.tab == Tab::Practice && ui_state.practice.exercise.adjustable() =>
+        {
+            ui_state.practice.adjust(if action == Action::OctaveUp { 1 } else { -1 });
+        }
+        Action::NotePrevious | Action::NoteNext if ui_state.tab == Tab::Practice => {
+            let delta = if action == Action::NotePrevious { -1 } else { 1 };
+            if let Some(number) = ui_state.practice.cycle(delta) {
+                ui_state.set_target_number(number);
+            }
+        }
+        Action::Save if ui_state.tab == Tab::Settings => {
+            settings::store_config(config, ui_state);
+            ui_state.settings_message = Some(match config.save() {
+                Ok(path) => i18n::trf("settings.saved", &[&path.display()]),
+                Err(e) => e,
+            });
+        }
+        Action::NotePrevious if ui_state.pitch_pipe => {
+            let count = presets::all()[ui_state.preset].strings.len().max(1);
+            ui_state.pipe_string = (ui_state.pipe_string + count - 1) % count;
+            ui_state.pipe_sequence_start = None;
+        }
+        Action::NoteNext if ui_state.pitch_pipe => {
+            let count = presets::all()[ui_state.preset].strings.len().max(1);
+            ui_state.pipe_string = (ui_state.pipe_string + 1) % count;
+            ui_state.pipe_sequence_start = None;
+        }
+        Action::Select if ui_state.pitch_pipe => {
+            ui_state.pipe_playing = !ui_state.pipe_playing;
+            ui_state.pipe_sequence_start = None;
+        }
+        Action::PipeAll if ui_state.pitch_pipe => {
+            ui_state.pipe_playing = true;
+            ui_state.pipe_sequence_start = Some(Instant::now());
+        }
+        Action::NotePrevious => {
+            let current_idx = NOTES
+                .iter()
+                .position(|&n| n == ui_state.target_note)
+                .unwrap_or(0);
+            let new_idx = (current_idx + 11) % 12;
+            ui_state.target_note = NOTES[new_idx].to_string();
+        }
+        Action::NoteNext => {
+            let current_idx = NOTES
+                .iter()
+                .position(|&n| n == ui_state.target_note)
+                .unwrap_or(0);
+            let new_idx = (current_idx + 1) % 12;
+            ui_state.target_note = NOTES[new_idx].to_string();
+        }
+        Action::OctaveUp => {
+            ui_state.target_octave = (ui_state.target_octave + 1).min(8);
+        }
+        Action::OctaveDown => {
+            ui_state.target_octave = (ui_state.target_octave - 1).max(0);
+        }
+        Action::A4Up => {
+            ui_state.a4_freq = (ui_state.a4_freq + 0.1).min(450.0);
+        }
+        Action::A4Down => {
+            ui_state.a4_freq = (ui_state.a4_freq - 0.1).max(432.0);
+        }
+        Action::Beat => {
+            ui_state.beat_mode = !ui_state.beat_mode;
+        }
+        Action::Reference => {
+            let (freq, a4_freq) = (ui_state.stable_reading().map(|reading| reading.freq), ui_state.a4_freq);
+            let notice = match freq.and_then(|freq| Some((ui_state.references.capture(freq, a4_freq)?, freq))) {
+                Some((number, freq)) => {
+                    let (note, octave) = midi::note_name(number);
+                    let name = format!("{}{}", note, octave);
+                    Notice::info(i18n::trf("notice.reference", &[&name, &format!("{:.2}", freq)]))
+                }
+                None => Notice::warning(i18n::tr("notice.reference_none")),
+            };
+            ui_state.push_notice(notice);
+        }
+        Action::ClearReferences => {
+            ui_state.references.clear();
+            ui_state.push_notice(Notice::info(i18n::tr("notice.references_cleared")));
+        }
+        Action::Snapshot => ui_state.snapshot_requested = true,
+        Action::Copy => {
+            let notice = match ui_state.stable_reading().or_else(|| ui_state.held_reading()) {
+                Some(reading) => {
+                    let target = format!("{}{}", ui_state.target_note, ui_state.target_octave);
+                    let text = i18n::trf(
+                        "clipboard.reading",
+                        &[
+                            &format!("{}{}", reading.note, reading.octave),
+                            &format!("{:.2}", reading.freq),
+                            &format!("{:+.1}", reading.deviation_cents),
+                            &target,
+                        ],
+                    );
+                    match clipboard::copy(&text) {
+                        Ok(()) => Notice::info(i18n::trf("notice.copied", &[&text])),
+                        Err(e) => Notice::error(i18n::trf("notice.copy_failed", &[&e])),
+                    }
+                }
+                None => Notice::warning(i18n::tr("notice.copy_none")),
+            };
+            ui_state.push_notice(notice);
+        }
+        Action::Chime => {
+            ui_state.chime_enabled = !ui_state.chime_enabled;
+        }
+        Action::Flash => {
+            ui_state.flash_enabled = !ui_state.flash_enabled;
+        }
+        Action::PitchPipe => {
+            ui_state.pitch_pipe = !ui_state.pitch_pipe;
+            ui_state.pipe_playing = false;
+            ui_state.pipe_sequence_start = None;
+        }
+        Action::Instrument => {
+            ui_state.preset = (ui_state.preset + 1) % presets::all().len();
+            ui_state.pipe_string = 0;
+            ui_state.pipe_sequence_start = None;
+        }
+        Action::Continuous => {
+            ui_state.continuous = !ui_state.continuous;
+        }
+        Action::BigNote => {
+            ui_state.big_note = !ui_state.big_note;
+        }
+        Action::Zen => {
+            ui_state.zen = !ui_state.zen;
+        }
+        Action::Theme => {
+            ui_state.theme = ui_state.theme.next();
+        }
+        Action::MeterRange => {
+            ui_state.cycle_meter_range(1);
+        }
+        Action::VerticalMeter => {
+            ui_state.vertical_meter = !ui_state.vertical_meter;
+        }
+        Action::Damping => {
+            ui_state.damping = ui_state.damping.next();
+        }
+        Action::History => {
+            ui_state.show_history = !ui_state.show_history;
+        }
+        Action::Piano => {
+            ui_state.show_piano = !ui_state.show_piano;
+        }
+        Action::Waveform => {
+            ui_state.show_waveform = !ui_state.show_waveform;
+        }
+        Action::Output => {
+            cycle_output_device(1, output_stream, synth, notices, ui_state);
+        }
+        Action::Metronome => {
+            ui_state.metronome_running = !ui_state.metronome_running;
+        }
+        Action::MetronomePattern => {
+            ui_state.metronome_pattern = (ui_state.metronome_pattern + 1) % metronome::PATTERNS.len();
+        }
+        Action::TempoDown => {
+            ui_state.metronome_bpm = (ui_state.metronome_bpm - 1).max(metronome::MIN_BPM);
+        }
+        Action::TempoUp => {
+            ui_state.metronome_bpm = (ui_state.metronome_bpm + 1).min(metronome::MAX_BPM);
+        }
+        Action::TempoDownCoarse => {
+            ui_state.metronome_bpm = ui_state.metronome_bpm.saturating_sub(10).max(metronome::MIN_BPM);
+        }
+        Action::TempoUpCoarse => {
+            ui_state.metronome_bpm = (ui_state.metronome_bpm + 10).min(metronome::MAX_BPM);
+        }
+        _ => {}
+    }
+    ControlFlow::Continue(())
+}
+
+fn list_devices() {
+    let groups = [
+        ("Input devices:", audio::input_device_names(), audio::default_input_device_name()),
+        ("Output devices:", audio::output_device_names(), audio::default_output_device_name()),
+    ];
+    for (i, (heading, names, default)) in groups.into_iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{}", heading);
+        if names.is_empty() {
+            println!("  (none)");
+        }
+        for name in names {
+            let marker = if default.as_ref() == Some(&name) { " (default)" } else { "" };
+            println!("  {}{}", name, marker);
+        }
+    }
+}
+
+/// Frame interval for a rate in frames per second, limited to 1-120.
+fn refresh_interval(rate: Option<f64>, default: f64) -> Duration {
+    Duration::from_secs_f64(1.0 / rate.unwrap_or(default).clamp(1.0, 120.0))
+}
+
+fn cycle_output_device(
+    delta: i32,
+    output_stream: &mut Option<(String, cpal::Stream)>,
+    synth: &Arc<Mutex<Synth>>,
+    notices: &Sender<Notice>,
+    ui_state: &mut UiState,
+) {
+    let names = audio::output_device_names();
+    if names.is_empty() {
+        return;
+    }
+
+    let count = names.len() as i32;
+    let next_idx = ui_state
+        .output_device
+        .as_ref()
+        .and_then(|current| names.iter().position(|n| n == current))
+        .map_or(0, |idx| (idx as i32 + delta).rem_euclid(count) as usize);
+
+    // The current device keeps playing unless the next one opens.
+    match open_output(Some(&names[next_idx]), synth, notices) {
+        Ok((name, stream)) => {
+            let _ = notices.send(Notice::info(i18n::trf("notice.output", &[&name])));
+            ui_state.output_device = Some(name.clone());
+            *output_stream = Some((name, stream));
+        }
+        Err(e) => {
+            let _ = notices.send(Notice::warning(e));
+        }
+    }
+}
+
+fn handle_mouse(
+    mouse: MouseEvent,
+    hits: &Hits,
+    output_stream: &mut Option<(String, cpal::Stream)>,
+    synth: &Arc<Mutex<Synth>>,
+    notices: &Sender<Notice>,
+    ui_state: &mut UiState,
+) {
+    let delta = match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) | MouseEventKind::ScrollUp => 1,
+        MouseEventKind::Down(MouseButton::Right) | MouseEventKind::ScrollDown => -1,
+        _ => return,
+    };
+    let clicked = matches!(mouse.kind, MouseEventKind::Down(_));
+    let Some(hit) = ui::hit_at(hits, mouse.column, mouse.row) else {
+        return;
+    };
+
+    match hit {
+        Hit::Tab(tab) => {
+            if clicked {
+                ui_state.tab = tab;
+            }
+        }
+        Hit::TargetNote => settings::Setting::TargetNote.adjust(ui_state, delta),
+        Hit::TargetOctave => settings::Setting::TargetOctave.adjust(ui_state, delta),
+        Hit::A4 => settings::Setting::A4.adjust(ui_state, delta),
+        Hit::PipeString(idx) => {
+            if clicked {
+                ui_state.pipe_playing = !(ui_state.pipe_playing && ui_state.pipe_string == idx);
+                ui_state.pipe_string = idx;
+                ui_state.pipe_sequence_start = None;
+            }
+        }
+        Hit::Setting(idx) => {
+            if clicked && ui_state.settings_cursor != idx {
+                ui_state.settings_cursor = idx;
+                return;
+            }
+            ui_state.settings_cursor = idx;
+            let setting = settings::SETTINGS[idx];
+            if setting == settings::Setting::OutputDevice {
+                cycle_output_device(delta, output_stream, synth, notices, ui_state);
+            } else {
+                setting.adjust(ui_state, delta);
+            }
+            ui_state.settings_message = None;
+        }
+    }
+}
+
+/// Reads terminal events on their own thread so the main loop can wait on
+/// them together with audio. The thread ends after the first read error.
+fn spawn_event_reader() -> Receiver<io::Result<Event>> {
+    let (tx, rx) = crossbeam_channel::bounded(64);
+    thread::spawn(move || loop {
+        let event = event::read();
+        let failed = event.is_err();
+        if tx.send(event).is_err() || failed {
+            break;
+        }
+    });
+    rx
+}
+
+/// The sample recorder asked for with `--record`.
+fn recorder(config: &Config, args: &Args) -> Result<Option<replay::Recorder>, String> {
+    let algorithm = config.get_str("tuner", "algorithm").unwrap_or("fft");
+    args.record.as_ref().map(|path| replay::Recorder::create(Path::new(path), algorithm)).transpose()
+}
+
+/// What opening an input device needs besides the device name.
+struct Input<'a> {
+    setup: &'a Setup,
+    samples: &'a FrameSender,
+    notices: &'a Sender<Notice>,
+}
+
+impl Input<'_> {
+    /// Opens `device_name` (or the default input) and hands the worker a tuner
+    /// for its sample rate. On failure the error screen is shown instead.
+    fn start(&self, device_name: Option<&str>, worker: &Worker, ui_state: &mut UiState) -> Option<cpal::Stream> {
+        match self.open(device_name, worker, ui_state.register(), ui_state.mode) {
+            Ok((stream, status, bin_hz)) => {
+                log::info!(
+                    target: "audio",
+                    "Input device: {} at {} Hz, {}",
+                    status.input_device,
+                    status.sample_rate,
+                    status.algorithm
+                );
+                ui_state.analysis = status;
+                ui_state.spectrum_bin_hz = bin_hz;
+                ui_state.audio_error = None;
+                Some(stream)
+            }
+            Err(e) => {
+                let _ = self.notices.send(Notice::error(e.clone()));
+                ui_state.audio_error = Some(AudioError::new(e));
+                None
+            }
+        }
+    }
+
+    /// Hands the worker a tuner for another register or analysis mode when
+    /// the settings or the preset call for one.
+    fn follow_settings(&self, worker: &Worker, ui_state: &mut UiState) {
+        let (register, mode) = (ui_state.register(), ui_state.mode);
+        if register == ui_state.analysis.register && mode == ui_state.analysis.mode {
+            return;
+        }
+        match self.tuner(ui_state.analysis.sample_rate, register, mode) {
+            Ok(tuner) => {
+                log::info!(target: "audio", "Analysing the {} register in {} mode", register.name(), mode.name());
+                ui_state.analysis.window = tuner.window();
+                ui_state.analysis.register = register;
+                ui_state.analysis.mode = mode;
+                ui_state.spectrum_bin_hz = tuner.bin_hz();
+                worker.replace_tuner(tuner);
+            }
+            Err(e) => {
+                let _ = self.notices.send(Notice::error(e));
+                ui_state.register = Some(ui_state.analysis.register);
+                ui_state.mode = ui_state.analysis.mode;
+            }
+        }
+    }
+
+    fn tuner(&self, sample_rate: u32, register: Register, mode: Mode) -> Result<Tuner, String> {
+        Setup { mode, ..self.setup.clone() }.tuner(sample_rate, register)
+    }
+
+    /// The stream, its status line, and the spectrum's bin width.
+    fn open(
+        &self,
+        device_name: Option<&str>,
+        worker: &Worker,
+        register: Register,
+        mode: Mode,
+    ) -> Result<(cpal::Stream, AnalysisStatus, f32), String> {
+        let capture = match device_name {
+            Some(name) => AudioCapture::with_device(name)?,
+            None => AudioCapture::new()?,
+        };
+        let sample_rate = capture.sample_rate();
+        let tuner = self.tuner(sample_rate, register, mode)?;
+        let mut status = AnalysisStatus::new(capture.device_name(), sample_rate, tuner.algorithm(), tuner.window());
+        status.register = register;
+        status.mode = mode;
+        let bin_hz = tuner.bin_hz();
+        // Sent before the stream starts so no sample reaches a tuner for the wrong rate.
+        worker.replace_tuner(tuner);
+        let stream = capture.start_capture(SampleRate(sample_rate), self.samples.clone(), self.notices.clone())?;
+        Ok((stream, status, bin_hz))
+    }
+}
+
+fn open_output(
+    device_name: Option<&str>,
+    synth: &Arc<Mutex<Synth>>,
+    notices: &Sender<Notice>,
+) -> Result<(String, cpal::Stream), String> {
+    let playback = match device_name {
+        Some(name) => AudioPlayback::with_device(name),
+        None => AudioPlayback::new(),
+    }?;
+
+    // Started before the synth is replaced, so a device that fails to open
+    // leaves the current one's synth alone.
+    let stream = playback.start_playback(Arc::clone(synth), notices.clone())?;
+    match synth.lock() {
+        Ok(mut synth) => *synth = Synth::new(playback.sample_rate()),
+        Err(poisoned) => *poisoned.into_inner() = Synth::new(playback.sample_rate()),
+    }
+    log::info!(target: "audio", "Output device: {} at {} Hz", playback.device_name(), playback.sample_rate());
+    Ok((playback.device_name(), stream))
+}
+
+/// `held` is set on the update where the target has just held in tune, to chime.
+fn update_synth(synth: &Mutex<Synth>, ui_state: &mut UiState, held: bool) {
+    let mut synth = match synth.lock() {
+        Ok(synth) => synth,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    if held && ui_state.chime_enabled {
+        synth.trigger_chime();
+    }
+
+    // The drill only runs, and sounds, while its page is shown.
+    let mut reference = None;
+    if ui_state.tab == Tab::Practice {
+        if let Some(number) = ui_state.practice.tick(Instant::now()) {
+            ui_state.set_target_number(number);
+        }
+        reference = ui_state.practice.tone(Instant::now(), ui_state.a4_freq);
+    }
+
+    if let Some(reference) = reference {
+        synth.set_voice(0, Some(reference));
+        synth.set_voice(1, None);
+    } else if let Some(pipe_freq) = pitch_pipe_frequency(ui_state) {
+        synth.set_voice(0, Some(pipe_freq));
+        synth.set_voice(1, None);
+    } else if ui_state.beat_mode {
+        synth.set_voice(0, Some(ui_state.target_freq()));
+        synth.set_voice(1, ui_state.current_freq);
+    } else {
+        synth.silence();
+    }
+
+    synth.metronome.set_bpm(ui_state.metronome_bpm);
+    synth.metronome.set_pattern(ui_state.metronome_pattern);
+    synth.metronome.set_running(ui_state.metronome_running);
+
+    let beats_played = synth.metronome.beats_played();
+    if beats_played != ui_state.metronome_beats_played {
+        ui_state.metronome_beats_played = beats_played;
+        ui_state.metronome_beat = synth.metronome.current_beat();
+        ui_state.metronome_flash_at = Some(Instant::now());
+    }
+}
+
+fn pitch_pipe_frequency(ui_state: &mut UiState) -> Option<f32> {
+    if !ui_state.pitch_pipe || !ui_state.pipe_playing {
+        return None;
+    }
+
+    let strings = presets::all()[ui_state.preset].strings;
+    if let Some(start) = ui_state.pipe_sequence_start {
+        let step = (start.elapsed().as_secs_f32() / PIPE_STEP.as_secs_f32()) as usize;
+        if step >= strings.len() {
+            ui_state.pipe_playing = false;
+            ui_state.pipe_sequence_start = None;
+            return None;
+        }
+        ui_state.pipe_string = step;
+    }
+
+    let &(note, octave) = strings.get(ui_state.pipe_string)?;
+    Some(Tuner::note_name_to_frequency(note, octave, ui_state.a4_freq))
+}
+
+fn update_in_tune_hold(ui_state: &mut UiState) -> bool {
+    if !matches!(ui_state.get_tuning_status(), TuningStatus::Perfect) {
+        ui_state.in_tune_since = None;
+        ui_state.in_tune_confirmed = false;
+        return false;
+    }
+
+    let since = *ui_state.in_tune_since.get_or_insert_with(Instant::now);
+    if !ui_state.in_tune_confirmed && since.elapsed() >= CHIME_HOLD {
+        ui_state.in_tune_confirmed = true;
+        ui_state.flash_at = Some(Instant::now());
+        return true;
+    }
+
+    false
+}
+
+fn notify_in_tune(ui_state: &UiState) -> Result<(), String> {
+    let target = format!("{}{}", ui_state.target_note, ui_state.target_octave);
+    let body = match (ui_state.current_freq, ui_state.deviation_cents) {
+        (Some(freq), Some(cents)) => i18n::trf("desktop.body", &[&format!("{:+.1}", cents), &format!("{:.2}", freq)]),
+        _ => String::new(),
+    };
+    desktop::notify(&i18n::trf("desktop.title", &[&target]), &body)
+}
+
+/// Raw mode turns Ctrl-C into a key press; it always quits, whatever `c` is bound to.
+fn is_ctrl_c(event: &Event) -> bool {
+    matches!(event, Event::Key(key) if key.kind == KeyEventKind::Press
+        && key.code == KeyCode::Char('c')
+        && key.modifiers.contains(KeyModifiers::CONTROL))
+}
+
 fn init_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>, Box<dyn std::error::Error>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -0,0 +1,38 @@
+use std::collections::BTreeMap;
+
+use crate::midi;
+
+/// Notes captured from a reference instrument, such as a piano that is not at
+/// A4 = 440 Hz, so another instrument can be tuned to it instead of to A4.
+/// Play a note on one instrument and capture it, then play the other.
+#[derive(Default)]
+pub struct References {
+    /// Captured frequency by MIDI note number.
+    notes: BTreeMap<u8, f32>,
+}
+
+impl References {
+    /// Stores `freq` as the reference for its nearest note, replacing any
+    /// earlier capture of it, and returns that note.
+    pub fn capture(&mut self, freq: f32, a4_freq: f32) -> Option<u8> {
+        let (note, _) = midi::midi_note(freq, a4_freq)?;
+        self.notes.insert(note, freq);
+        Some(note)
+    }
+
+    pub fn clear(&mut self) {
+        self.notes.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.notes.len()
+    }
+
+    /// Frequency of `note` on the reference instrument: its own capture, or
+    /// the nearest captured note moved by equal-tempered semitones. Also
+    /// returns the captured note it came from.
+    pub fn frequency(&self, note: u8) -> Option<(u8, f32)> {
+        let (&source, &freq) = self.notes.iter().min_by_key(|(&captured, _)| captured.abs_diff(note))?;
+        Some((source, freq * 2f32.powf((f32::from(note) - f32::from(source)) / 12.0)))
+    }
+}
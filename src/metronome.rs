@@ -0,0 +1,102 @@
+use std::f32::consts::PI;
+
+pub const MIN_BPM: u32 = 30;
+pub const MAX_BPM: u32 = 300;
+
+pub const PATTERNS: [(&str, &[bool]); 5] = [
+    ("1/4", &[false]),
+    ("2/4", &[true, false]),
+    ("3/4", &[true, false, false]),
+    ("4/4", &[true, false, false, false]),
+    ("6/8", &[true, false, false, true, false, false]),
+];
+
+const CLICK_SECONDS: f32 = 0.03;
+const CLICK_FREQ: f32 = 1000.0;
+const ACCENT_FREQ: f32 = 1500.0;
+const CLICK_GAIN: f32 = 0.5;
+
+pub struct Metronome {
+    sample_rate: f32,
+    bpm: u32,
+    pattern: usize,
+    running: bool,
+    samples_to_next: f32,
+    click_pos: Option<usize>,
+    click_accent: bool,
+    beat: usize,
+    next_beat: usize,
+    beats_played: u64,
+}
+
+impl Metronome {
+    pub fn new(sample_rate: u32) -> Self {
+        Metronome {
+            sample_rate: sample_rate as f32,
+            bpm: 120,
+            pattern: 3,
+            running: false,
+            samples_to_next: 0.0,
+            click_pos: None,
+            click_accent: false,
+            beat: 0,
+            next_beat: 0,
+            beats_played: 0,
+        }
+    }
+
+    pub fn set_running(&mut self, running: bool) {
+        if running && !self.running {
+            self.samples_to_next = 0.0;
+            self.next_beat = 0;
+        }
+        self.running = running;
+    }
+
+    pub fn set_bpm(&mut self, bpm: u32) {
+        self.bpm = bpm.clamp(MIN_BPM, MAX_BPM);
+    }
+
+    pub fn set_pattern(&mut self, pattern: usize) {
+        self.pattern = pattern % PATTERNS.len();
+        self.next_beat %= PATTERNS[self.pattern].1.len();
+    }
+
+    pub fn beats_played(&self) -> u64 {
+        self.beats_played
+    }
+
+    pub fn current_beat(&self) -> usize {
+        self.beat
+    }
+
+    pub fn next_sample(&mut self) -> f32 {
+        if self.running {
+            if self.samples_to_next <= 0.0 {
+                let accents = PATTERNS[self.pattern].1;
+                self.beat = self.next_beat % accents.len();
+                self.next_beat = self.beat + 1;
+                self.click_accent = accents[self.beat];
+                self.click_pos = Some(0);
+                self.beats_played += 1;
+                self.samples_to_next += self.sample_rate * 60.0 / self.bpm as f32;
+            }
+            self.samples_to_next -= 1.0;
+        }
+
+        let Some(pos) = self.click_pos else {
+            return 0.0;
+        };
+
+        let t = pos as f32 / self.sample_rate;
+        if t >= CLICK_SECONDS {
+            self.click_pos = None;
+            return 0.0;
+        }
+        self.click_pos = Some(pos + 1);
+
+        let freq = if self.click_accent { ACCENT_FREQ } else { CLICK_FREQ };
+        let envelope = (-t / (CLICK_SECONDS / 5.0)).exp();
+        CLICK_GAIN * envelope * (2.0 * PI * freq * t).sin()
+    }
+}
@@ -0,0 +1,203 @@
+#[cfg(target_os = "linux")]
+use alsa::seq::{EvCtrl, EvNote, Event, EventType, PortCap, PortType, Seq};
+#[cfg(target_os = "linux")]
+use std::ffi::CString;
+
+use crate::config::Config;
+
+const VELOCITY: u8 = 100;
+const NOTE_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+const PITCH_BEND_MAX: f32 = 8192.0;
+
+/// MIDI note number and cents from it for `freq`, or `None` outside 0-127.
+pub fn midi_note(freq: f32, a4_freq: f32) -> Option<(u8, f32)> {
+    let exact = 69.0 + 12.0 * (freq / a4_freq).log2();
+    let note = exact.round();
+    (0.0..=127.0).contains(&note).then_some((note as u8, (exact - note) * 100.0))
+}
+
+/// Name and octave of MIDI note `number`, where 60 is C4 and 69 is A4.
+pub fn note_name(number: u8) -> (&'static str, i32) {
+    (NOTE_NAMES[number as usize % 12], number as i32 / 12 - 1)
+}
+
+/// A virtual MIDI output port that sounds the detected note, with optional
+/// pitch bend for its deviation in cents.
+pub struct MidiOut {
+    port: Port,
+    channel: u8,
+    pitch_bend: bool,
+    bend_range: f32,
+    sounding: Option<u8>,
+    bend: i32,
+}
+
+impl MidiOut {
+    /// Settings come from `[midi]`: `channel` (1-16), `pitch_bend`, and
+    /// `bend_range` in semitones, which must match the receiving synth.
+    pub fn open(config: &Config) -> Result<Self, String> {
+        let channel = config.get_f64("midi", "channel").unwrap_or(1.0);
+        if !(1.0..=16.0).contains(&channel) {
+            return Err(format!("midi.channel must be between 1 and 16, got {}", channel));
+        }
+        let bend_range = config.get_f64("midi", "bend_range").unwrap_or(2.0) as f32;
+        if bend_range <= 0.0 {
+            return Err("midi.bend_range must be positive".to_string());
+        }
+
+        Ok(MidiOut {
+            port: Port::open("rust_tuner", "tuner out", Direction::Out)?,
+            channel: channel as u8 - 1,
+            pitch_bend: config.get_bool("midi", "pitch_bend").unwrap_or(true),
+            bend_range,
+            sounding: None,
+            bend: 0,
+        })
+    }
+
+    /// Sounds `note` (number and cents), releasing the previous note when it
+    /// changes; `None` releases whatever is sounding.
+    pub fn update(&mut self, note: Option<(u8, f32)>) -> Result<(), String> {
+        let number = note.map(|(number, _)| number);
+        if number != self.sounding {
+            if let Some(old) = self.sounding.take() {
+                self.port.note(self.channel, old, 0)?;
+            }
+        }
+
+        if let Some((number, cents)) = note {
+            if self.pitch_bend {
+                let bend = (cents / (self.bend_range * 100.0) * PITCH_BEND_MAX)
+                    .round()
+                    .clamp(-PITCH_BEND_MAX, PITCH_BEND_MAX - 1.0) as i32;
+                if bend != self.bend {
+                    self.port.pitch_bend(self.channel, bend)?;
+                    self.bend = bend;
+                }
+            }
+            if self.sounding.is_none() {
+                self.port.note(self.channel, number, VELOCITY)?;
+                self.sounding = Some(number);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MidiOut {
+    fn drop(&mut self) {
+        let _ = self.update(None);
+    }
+}
+
+/// A virtual MIDI input port; keys pressed on a connected controller are read
+/// without blocking.
+pub struct MidiIn {
+    port: Port,
+}
+
+impl MidiIn {
+    pub fn open() -> Result<Self, String> {
+        Ok(MidiIn {
+            port: Port::open("rust_tuner input", "tuner in", Direction::In)?,
+        })
+    }
+
+    /// The most recent key pressed since the last call, if any.
+    pub fn poll(&self) -> Result<Option<u8>, String> {
+        self.port.last_note_on()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Direction {
+    In,
+    Out,
+}
+
+/// A port of an ALSA sequencer client that other MIDI software can connect to.
+#[cfg(target_os = "linux")]
+struct Port {
+    seq: Seq,
+    port: i32,
+}
+
+#[cfg(target_os = "linux")]
+impl Port {
+    fn open(client: &str, name: &str, direction: Direction) -> Result<Self, String> {
+        let nonblock = direction == Direction::In;
+        let seq = Seq::open(None, None, nonblock).map_err(|e| format!("Failed to open the ALSA sequencer: {}", e))?;
+        let client = CString::new(client).map_err(|e| e.to_string())?;
+        seq.set_client_name(&client)
+            .map_err(|e| format!("Failed to name the MIDI client: {}", e))?;
+
+        let name = CString::new(name).map_err(|e| e.to_string())?;
+        let caps = match direction {
+            Direction::In => PortCap::WRITE | PortCap::SUBS_WRITE,
+            Direction::Out => PortCap::READ | PortCap::SUBS_READ,
+        };
+        let port = seq
+            .create_simple_port(&name, caps, PortType::MIDI_GENERIC | PortType::APPLICATION)
+            .map_err(|e| format!("Failed to create MIDI port: {}", e))?;
+        Ok(Port { seq, port })
+    }
+
+    /// Note-on, or note-off when `velocity` is 0.
+    fn note(&self, channel: u8, note: u8, velocity: u8) -> Result<(), String> {
+        let event_type = if velocity > 0 { EventType::Noteon } else { EventType::Noteoff };
+        let data = EvNote { channel, note, velocity, off_velocity: 0, duration: 0 };
+        self.send(Event::new(event_type, &data))
+    }
+
+    fn pitch_bend(&self, channel: u8, value: i32) -> Result<(), String> {
+        let data = EvCtrl { channel, param: 0, value };
+        self.send(Event::new(EventType::Pitchbend, &data))
+    }
+
+    fn last_note_on(&self) -> Result<Option<u8>, String> {
+        let mut input = self.seq.input();
+        let mut pressed = None;
+        while input.event_input_pending(true).map_err(|e| format!("MIDI input failed: {}", e))? > 0 {
+            let event = input.event_input().map_err(|e| format!("MIDI input failed: {}", e))?;
+            // Many controllers send a note-on with velocity 0 instead of a note-off.
+            if let (EventType::Noteon, Some(note)) = (event.get_type(), event.get_data::<EvNote>()) {
+                if note.velocity > 0 {
+                    pressed = Some(note.note);
+                }
+            }
+        }
+        Ok(pressed)
+    }
+
+    fn send(&self, mut event: Event) -> Result<(), String> {
+        event.set_source(self.port);
+        event.set_subs();
+        event.set_direct();
+        self.seq
+            .event_output_direct(&mut event)
+            .map(|_| ())
+            .map_err(|e| format!("MIDI output failed: {}", e))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+struct Port;
+
+#[cfg(not(target_os = "linux"))]
+impl Port {
+    fn open(_client: &str, _name: &str, _direction: Direction) -> Result<Self, String> {
+        Err("MIDI needs the ALSA sequencer and is only available on Linux".to_string())
+    }
+
+    fn note(&self, _channel: u8, _note: u8, _velocity: u8) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn pitch_bend(&self, _channel: u8, _value: i32) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn last_note_on(&self) -> Result<Option<u8>, String> {
+        Ok(None)
+    }
+}
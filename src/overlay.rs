@@ -0,0 +1,61 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::server;
+use crate::ui::UiState;
+
+/// Fast enough for an overlay, slow enough not to churn the disk.
+const MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Keeps a small file with the current reading up to date for OBS text or
+/// browser sources: JSON when the path ends in `.json`, one line of text
+/// such as `A2 +3.4¢` otherwise.
+pub struct OverlayFile {
+    path: PathBuf,
+    temp: PathBuf,
+    json: bool,
+    written: String,
+    written_at: Option<Instant>,
+}
+
+impl OverlayFile {
+    pub fn new(path: &Path) -> Self {
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        OverlayFile {
+            path: path.to_path_buf(),
+            temp: PathBuf::from(temp),
+            json: path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")),
+            written: String::new(),
+            written_at: None,
+        }
+    }
+
+    /// Rewrites the file when the reading has changed, at most every `MIN_INTERVAL`.
+    pub fn update(&mut self, state: &UiState) -> Result<(), String> {
+        if self.written_at.is_some_and(|at| at.elapsed() < MIN_INTERVAL) {
+            return Ok(());
+        }
+
+        let contents = if self.json { server::reading_json(state) } else { text(state) };
+        if contents == self.written {
+            return Ok(());
+        }
+
+        // Written beside the target and renamed over it, so readers never see half a file.
+        fs::write(&self.temp, format!("{}\n", contents))
+            .and_then(|_| fs::rename(&self.temp, &self.path))
+            .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))?;
+        self.written = contents;
+        self.written_at = Some(Instant::now());
+        Ok(())
+    }
+}
+
+fn text(state: &UiState) -> String {
+    match (&state.current_note, state.current_octave, state.deviation_cents) {
+        (Some(note), Some(octave), Some(cents)) => format!("{}{} {:+.1}¢", note, octave, cents),
+        _ => "-".to_string(),
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::VecDeque;
+
+use crate::tuner::PARTIALS;
+
+/// Just ratios from a unison to an octave; the index is the size in semitones.
+pub const JUST_RATIOS: [(u32, u32); 13] =
+    [(1, 1), (16, 15), (9, 8), (6, 5), (5, 4), (4, 3), (45, 32), (3, 2), (8, 5), (5, 3), (9, 5), (15, 8), (2, 1)];
+/// Range searched for the two fundamentals.
+const MIN_FREQ: f32 = 60.0;
+const MAX_FREQ: f32 = 2000.0;
+/// Peaks quieter than this share of the loudest are ignored.
+const PEAK_FLOOR: f32 = 0.1;
+/// How far a peak may sit from a harmonic and still belong to it.
+const HARMONIC_TOLERANCE: f32 = 0.03;
+/// Windows averaged, and readings this far from their average start a new interval.
+const SMOOTHING: usize = 8;
+const NEW_INTERVAL_CENTS: f32 = 50.0;
+/// Windows without two notes before the reading is cleared.
+const HOLD_WINDOWS: u32 = 10;
+
+/// The two notes heard and the interval between them, averaged over recent windows.
+pub struct Reading {
+    pub low: f32,
+    pub high: f32,
+    pub cents: f32,
+}
+
+impl Reading {
+    /// Nearest interval in semitones.
+    pub fn semitones(&self) -> u32 {
+        (self.cents / 100.0).round().max(0.0) as u32
+    }
+
+    /// Size of the nearest equal-tempered interval in cents.
+    pub fn equal(&self) -> f32 {
+        100.0 * self.semitones() as f32
+    }
+
+    pub fn equal_deviation(&self) -> f32 {
+        self.cents - self.equal()
+    }
+
+    /// The just ratio of the nearest interval within one octave, and its size
+    /// in cents including any whole octaves above it.
+    pub fn just(&self) -> ((u32, u32), f32) {
+        let semitones = self.semitones();
+        let (octaves, within) = match semitones {
+            0 => (0, 0),
+            _ => ((semitones - 1) / 12, (semitones - 1) % 12 + 1),
+        };
+        let (numerator, denominator) = JUST_RATIOS[within as usize];
+        let cents = 1200.0 * (numerator as f32 / denominator as f32).log2() + 1200.0 * octaves as f32;
+        ((numerator, denominator), cents)
+    }
+
+    pub fn just_deviation(&self) -> f32 {
+        self.cents - self.just().1
+    }
+}
+
+/// Reports the size of an interval played as two simultaneous notes, a
+/// double stop or a note against a drone, against its equal-tempered and
+/// just values.
+pub struct IntervalAnalyzer {
+    pub reading: Option<Reading>,
+    /// Recent low and high frequencies.
+    recent: VecDeque<(f32, f32)>,
+    missed: u32,
+}
+
+impl IntervalAnalyzer {
+    pub fn new() -> Self {
+        IntervalAnalyzer {
+            reading: None,
+            recent: VecDeque::new(),
+            missed: 0,
+        }
+    }
+
+    /// Takes the spectrum of one analysis window.
+    pub fn hear(&mut self, spectrum: &[f32], bin_hz: f32) {
+        let Some((low, high)) = two_notes(spectrum, bin_hz) else {
+            self.missed += 1;
+            if self.missed >= HOLD_WINDOWS {
+                self.recent.clear();
+                self.reading = None;
+            }
+            return;
+        };
+        self.missed = 0;
+        let cents = 1200.0 * (high / low).log2();
+        if self.reading.as_ref().is_some_and(|reading| (reading.cents - cents).abs() > NEW_INTERVAL_CENTS) {
+            self.recent.clear();
+        }
+        self.recent.push_back((low, high));
+        if self.recent.len() > SMOOTHING {
+            self.recent.pop_front();
+        }
+
+        let count = self.recent.len() as f32;
+        let low = self.recent.iter().map(|(low, _)| low).sum::<f32>() / count;
+        let high = self.recent.iter().map(|(_, high)| high).sum::<f32>() / count;
+        let cents = self.recent.iter().map(|(low, high)| 1200.0 * (high / low).log2()).sum::<f32>() / count;
+        self.reading = Some(Reading { low, high, cents });
+    }
+}
+
+/// Finds the fundamentals of two notes sounding together, lower first.
+///
+/// The spectral peak whose harmonics carry the most energy is taken as one
+/// note; its harmonics are set aside and the strongest series among the
+/// remaining peaks is the other. An octave hides entirely in the lower
+/// note's harmonics, so it is reported as a single note.
+pub fn two_notes(spectrum: &[f32], bin_hz: f32) -> Option<(f32, f32)> {
+    let peaks = peaks(spectrum, bin_hz);
+    let first = strongest(&peaks, &peaks)?;
+    let rest: Vec<(f32, f32)> = peaks.iter().copied().filter(|&(freq, _)| !is_harmonic(freq, first)).collect();
+    let second = strongest(&rest, &peaks)?;
+    Some((first.min(second), first.max(second)))
+}
+
+/// Local maxima between `MIN_FREQ` and `MAX_FREQ` above the floor, as
+/// interpolated frequencies and magnitudes.
+fn peaks(spectrum: &[f32], bin_hz: f32) -> Vec<(f32, f32)> {
+    if bin_hz <= 0.0 || spectrum.len() < 3 {
+        return Vec::new();
+    }
+    let first = ((MIN_FREQ / bin_hz) as usize).max(1);
+    let last = ((MAX_FREQ / bin_hz) as usize).min(spectrum.len() - 2);
+    if first >= last {
+        return Vec::new();
+    }
+    let loudest = spectrum[first..=last].iter().fold(0.0f32, |a, &m| a.max(m));
+    if loudest < 0.01 {
+        return Vec::new();
+    }
+
+    (first..=last)
+        .filter(|&bin| {
+            let magnitude = spectrum[bin];
+            magnitude >= loudest * PEAK_FLOOR && magnitude > spectrum[bin - 1] && magnitude >= spectrum[bin + 1]
+        })
+        .map(|bin| {
+            // Parabolic interpolation on log magnitudes, as for the partials.
+            let (prev, curr, next) =
+                (spectrum[bin - 1].max(1e-9).ln(), spectrum[bin].ln(), spectrum[bin + 1].max(1e-9).ln());
+            let denom = prev - 2.0 * curr + next;
+            let offset = if denom.abs() > 1e-9 { 0.5 * (prev - next) / denom } else { 0.0 };
+            ((bin as f32 + offset.clamp(-0.5, 0.5)) * bin_hz, spectrum[bin])
+        })
+        .collect()
+}
+
+/// The candidate in `candidates` whose first harmonics, looked up among
+/// `peaks`, have the largest summed magnitude.
+fn strongest(candidates: &[(f32, f32)], peaks: &[(f32, f32)]) -> Option<f32> {
+    let score = |fundamental: f32| -> f32 {
+        (1..=PARTIALS)
+            .map(|number| {
+                let ideal = fundamental * number as f32;
+                peaks
+                    .iter()
+                    .filter(|&&(freq, _)| (freq / ideal - 1.0).abs() <= HARMONIC_TOLERANCE)
+                    .fold(0.0f32, |a, &(_, magnitude)| a.max(magnitude))
+            })
+            .sum()
+    };
+    candidates.iter().map(|&(freq, _)| (freq, score(freq))).max_by(|a, b| a.1.total_cmp(&b.1)).map(|(freq, _)| freq)
+}
+
+fn is_harmonic(freq: f32, fundamental: f32) -> bool {
+    let number = (freq / fundamental).round().max(1.0);
+    (freq / (fundamental * number) - 1.0).abs() <= HARMONIC_TOLERANCE
+}
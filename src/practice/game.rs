@@ -0,0 +1,174 @@
+use std::time::{Duration, Instant};
+
+use super::Random;
+
+/// Targets in one game.
+pub const GAME_ROUNDS: usize = 10;
+/// How long a note must stay inside the threshold to count as a hit.
+const HOLD: Duration = Duration::from_secs(1);
+const TARGET_TIME: Duration = Duration::from_secs(8);
+/// Targets fall within this many semitones of the note the game started on.
+const RANGE: u8 = 7;
+
+pub enum GamePhase {
+    Idle,
+    Playing {
+        since: Instant,
+        hold_since: Option<Instant>,
+        held: Vec<f32>,
+    },
+    Over,
+}
+
+/// The outcome of one target: points and mean absolute cents while held,
+/// or `None` for a miss.
+pub struct Hit {
+    pub target: u8,
+    pub result: Option<(u32, f32)>,
+}
+
+/// Shows random target notes around the tuner's target and scores each one
+/// that is hit and held: up to 100 points for accuracy and 50 for speed.
+pub struct PitchGame {
+    pub phase: GamePhase,
+    pub target: u8,
+    /// Targets scored so far.
+    pub played: usize,
+    pub score: u32,
+    pub hits: usize,
+    pub streak: usize,
+    pub best_streak: usize,
+    pub last: Option<Hit>,
+    home: u8,
+    hit_cents: f32,
+    random: Random,
+}
+
+impl PitchGame {
+    pub fn new() -> Self {
+        PitchGame {
+            phase: GamePhase::Idle,
+            target: 69,
+            played: 0,
+            score: 0,
+            hits: 0,
+            streak: 0,
+            best_streak: 0,
+            last: None,
+            home: 69,
+            hit_cents: 0.0,
+            random: Random::new(),
+        }
+    }
+
+    /// Starts a game around `home`, returning the first target; during a
+    /// game, ends it early and returns `home` again.
+    pub fn select(&mut self, home: u8, now: Instant) -> u8 {
+        if let Some(home) = self.stop() {
+            return home;
+        }
+        *self = PitchGame {
+            home,
+            target: home,
+            random: Random(self.random.0),
+            ..PitchGame::new()
+        };
+        self.next_target(now)
+    }
+
+    /// Ends a running game, returning the note it started from.
+    pub fn stop(&mut self) -> Option<u8> {
+        matches!(self.phase, GamePhase::Playing { .. }).then(|| {
+            self.phase = GamePhase::Over;
+            self.home
+        })
+    }
+
+    /// Counts a target that ran out of time as a miss.
+    pub fn tick(&mut self, now: Instant) -> Option<u8> {
+        match &self.phase {
+            GamePhase::Playing { since, .. } if now.duration_since(*since) >= TARGET_TIME => {
+                self.score_round(None, now)
+            }
+            _ => None,
+        }
+    }
+
+    /// Takes the cents from the current target for one analysis window,
+    /// returning the next target once this one is scored.
+    pub fn hear(&mut self, deviation: Option<f32>, threshold: f32, now: Instant) -> Option<u8> {
+        let GamePhase::Playing { since, hold_since, held } = &mut self.phase else {
+            return None;
+        };
+        match deviation.map(f32::abs).filter(|cents| *cents < threshold) {
+            Some(cents) => {
+                let start = *hold_since.get_or_insert(now);
+                held.push(cents);
+                if now.duration_since(start) < HOLD {
+                    return None;
+                }
+            }
+            None => {
+                *hold_since = None;
+                held.clear();
+                return None;
+            }
+        }
+        let mean = held.iter().sum::<f32>() / held.len() as f32;
+        let accuracy = 50.0 + 50.0 * (1.0 - mean / threshold);
+        let speed = 50.0 * (1.0 - now.duration_since(*since).as_secs_f32() / TARGET_TIME.as_secs_f32()).max(0.0);
+        self.score_round(Some(((accuracy + speed).round() as u32, mean)), now)
+    }
+
+    /// Share of the targets so far that were hit, in percent.
+    pub fn accuracy(&self) -> Option<f32> {
+        (self.played > 0).then(|| 100.0 * self.hits as f32 / self.played as f32)
+    }
+
+    /// Mean absolute cents over the hits.
+    pub fn average_cents(&self) -> Option<f32> {
+        (self.hits > 0).then(|| self.hit_cents / self.hits as f32)
+    }
+
+    /// Time left on the current target and how far through the hold it is, from 0 to 1.
+    pub fn progress(&self, now: Instant) -> Option<(Duration, f32)> {
+        let GamePhase::Playing { since, hold_since, .. } = &self.phase else {
+            return None;
+        };
+        let left = TARGET_TIME.saturating_sub(now.duration_since(*since));
+        let hold = hold_since.map_or(0.0, |start| now.duration_since(start).as_secs_f32() / HOLD.as_secs_f32());
+        Some((left, hold.min(1.0)))
+    }
+
+    fn score_round(&mut self, result: Option<(u32, f32)>, now: Instant) -> Option<u8> {
+        match result {
+            Some((points, cents)) => {
+                self.score += points;
+                self.hits += 1;
+                self.hit_cents += cents;
+                self.streak += 1;
+                self.best_streak = self.best_streak.max(self.streak);
+            }
+            None => self.streak = 0,
+        }
+        self.last = Some(Hit { target: self.target, result });
+        self.played += 1;
+        if self.played == GAME_ROUNDS {
+            self.phase = GamePhase::Over;
+            return Some(self.home);
+        }
+        Some(self.next_target(now))
+    }
+
+    fn next_target(&mut self, now: Instant) -> u8 {
+        let low = self.home.saturating_sub(RANGE);
+        let high = self.home.saturating_add(RANGE).min(127);
+        let mut target = self.target;
+        while target == self.target {
+            target = low + self.random.below(u32::from(high - low) + 1) as u8;
+        }
+        self.target = target;
+        self.phase = GamePhase::Playing { since: now, hold_since: None, held: Vec::new() };
+        target
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::midi_frequency;
+use crate::midi;
+
+/// Lengths of long tone to choose from, in seconds.
+pub const HOLD_LENGTHS: [u64; 6] = [5, 10, 15, 20, 30, 60];
+/// A gap in the pitch longer than this, or a move of more than
+/// `MAX_WANDER` cents, ends the tone early.
+const MAX_GAP: Duration = Duration::from_millis(500);
+const MAX_WANDER: f32 = 100.0;
+const HISTORY: usize = 5;
+
+pub enum HoldPhase {
+    Idle,
+    /// Armed and waiting for the first pitched window.
+    Waiting,
+    Holding {
+        note: u8,
+        since: Instant,
+        last_pitched: Instant,
+        /// Seconds since the start and cents from `note`.
+        samples: Vec<(f32, f32)>,
+    },
+    Done,
+}
+
+/// Statistics of one long tone.
+pub struct HoldResult {
+    pub note: u8,
+    pub held: Duration,
+    pub complete: bool,
+    /// Mean cents from the note.
+    pub offset: f32,
+    /// Standard deviation in cents.
+    pub spread: f32,
+    /// Change in cents from start to end along a fitted line.
+    pub drift: f32,
+}
+
+impl HoldResult {
+    fn new(note: u8, held: Duration, complete: bool, samples: &[(f32, f32)]) -> Self {
+        let count = samples.len() as f32;
+        let mean_t = samples.iter().map(|(t, _)| t).sum::<f32>() / count;
+        let offset = samples.iter().map(|(_, c)| c).sum::<f32>() / count;
+        let spread = (samples.iter().map(|(_, c)| (c - offset).powi(2)).sum::<f32>() / count).sqrt();
+        let covariance: f32 = samples.iter().map(|(t, c)| (t - mean_t) * (c - offset)).sum();
+        let variance: f32 = samples.iter().map(|(t, _)| (t - mean_t).powi(2)).sum();
+        let slope = if variance > 0.0 { covariance / variance } else { 0.0 };
+        HoldResult {
+            note,
+            held,
+            complete,
+            offset,
+            spread,
+            drift: slope * held.as_secs_f32(),
+        }
+    }
+
+    /// A to D, or F, from the larger of the spread and half the drift;
+    /// a tone that broke off early drops one grade.
+    pub fn grade(&self) -> char {
+        let wobble = self.spread.max(self.drift.abs() / 2.0);
+        let grade = [3.0, 6.0, 10.0, 15.0].iter().position(|&limit| wobble <= limit).unwrap_or(4);
+        let grade = if self.complete { grade } else { (grade + 1).min(4) };
+        ['A', 'B', 'C', 'D', 'F'][grade]
+    }
+}
+
+/// Measures how steadily one pitch is held for a chosen number of seconds,
+/// for the long tones brass and wind players practise. The note is whatever
+/// is played first, so the target does not need to be set.
+pub struct LongTone {
+    pub phase: HoldPhase,
+    pub length: usize,
+    pub results: VecDeque<HoldResult>,
+}
+
+impl LongTone {
+    pub fn new() -> Self {
+        LongTone {
+            phase: HoldPhase::Idle,
+            length: 1,
+            results: VecDeque::new(),
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(HOLD_LENGTHS[self.length])
+    }
+
+    /// Picks a longer or shorter tone.
+    pub fn adjust(&mut self, delta: i32) {
+        self.length = (self.length as i32 + delta).clamp(0, HOLD_LENGTHS.len() as i32 - 1) as usize;
+    }
+
+    /// Arms a new tone, or ends the one being held.
+    pub fn select(&mut self, now: Instant) {
+        match self.phase {
+            HoldPhase::Holding { .. } => self.finish(now, false),
+            HoldPhase::Waiting => self.phase = HoldPhase::Idle,
+            _ => self.phase = HoldPhase::Waiting,
+        }
+    }
+
+    /// Ends the tone once its time is up or the pitch has gone.
+    pub fn tick(&mut self, now: Instant) {
+        if let HoldPhase::Holding { since, last_pitched, .. } = self.phase {
+            if now.duration_since(since) >= self.duration() {
+                self.finish(now, true);
+            } else if now.duration_since(last_pitched) > MAX_GAP {
+                self.finish(last_pitched, false);
+            }
+        }
+    }
+
+    pub fn hear(&mut self, freq: Option<f32>, now: Instant, a4_freq: f32) {
+        let Some(freq) = freq else {
+            return;
+        };
+        match &mut self.phase {
+            HoldPhase::Waiting => {
+                if let Some((note, cents)) = midi::midi_note(freq, a4_freq) {
+                    let samples = vec![(0.0, cents)];
+                    self.phase = HoldPhase::Holding { note, since: now, last_pitched: now, samples };
+                }
+            }
+            HoldPhase::Holding { note, since, last_pitched, samples } => {
+                let cents = 1200.0 * (freq / midi_frequency(*note, a4_freq)).log2();
+                if cents.abs() > MAX_WANDER {
+                    self.finish(now, false);
+                    return;
+                }
+                *last_pitched = now;
+                samples.push((now.duration_since(*since).as_secs_f32(), cents));
+            }
+            _ => {}
+        }
+    }
+
+    /// Seconds held so far and the statistics up to now.
+    pub fn live(&self, now: Instant) -> Option<(Duration, HoldResult)> {
+        let HoldPhase::Holding { note, since, samples, .. } = &self.phase else {
+            return None;
+        };
+        let held = now.duration_since(*since);
+        Some((held, HoldResult::new(*note, held, false, samples)))
+    }
+
+    fn finish(&mut self, end: Instant, complete: bool) {
+        if let HoldPhase::Holding { note, since, samples, .. } = &self.phase {
+            let held = end.duration_since(*since).min(self.duration());
+            self.results.push_front(HoldResult::new(*note, held, complete, samples));
+            self.results.truncate(HISTORY);
+        }
+        self.phase = HoldPhase::Done;
+    }
+}
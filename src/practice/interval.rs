@@ -0,0 +1,150 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::{midi_frequency, Random};
+use crate::midi;
+
+/// Translation keys of the intervals from a minor second to an octave; the
+/// index plus one is the size in semitones.
+pub const INTERVALS: [&str; 12] = [
+    "interval.m2",
+    "interval.M2",
+    "interval.m3",
+    "interval.M3",
+    "interval.P4",
+    "interval.TT",
+    "interval.P5",
+    "interval.m6",
+    "interval.M6",
+    "interval.m7",
+    "interval.M7",
+    "interval.P8",
+];
+const REFERENCE_TIME: Duration = Duration::from_millis(1500);
+/// Listening starts this long after the reference stops, so its tail in
+/// the room is not taken for the answer.
+const LISTEN_AFTER: Duration = Duration::from_millis(300);
+/// Consecutive pitched windows (about half a second) that make an answer.
+const ANSWER_WINDOWS: usize = 10;
+const ANSWER_TIMEOUT: Duration = Duration::from_secs(10);
+/// Roots from A2 to A3 keep every answer within reach of a voice or guitar.
+const LOWEST_ROOT: u8 = 45;
+const HIGHEST_ROOT: u8 = 57;
+const HISTORY: usize = 8;
+
+pub enum Phase {
+    Idle,
+    Reference { until: Instant },
+    Listening { since: Instant, freqs: Vec<f32> },
+    /// Cents from the interval, or `None` when nothing was heard in time.
+    Scored(Option<f32>),
+}
+
+/// A finished question.
+pub struct Round {
+    pub root: u8,
+    pub semitones: u8,
+    pub cents: Option<f32>,
+}
+
+/// Plays a root note, then listens for a given interval above it and
+/// scores how many cents off it lands. Answers in another octave count,
+/// since voices and instruments differ in range.
+pub struct IntervalDrill {
+    pub phase: Phase,
+    pub root: u8,
+    pub semitones: u8,
+    pub rounds: VecDeque<Round>,
+    random: Random,
+}
+
+impl IntervalDrill {
+    pub fn new() -> Self {
+        IntervalDrill {
+            phase: Phase::Idle,
+            root: LOWEST_ROOT,
+            semitones: 7,
+            rounds: VecDeque::new(),
+            random: Random::new(),
+        }
+    }
+
+    /// Asks a new question, or replays the root while one is open.
+    pub fn select(&mut self, now: Instant) {
+        if matches!(self.phase, Phase::Idle | Phase::Scored(_)) {
+            self.root = LOWEST_ROOT + self.random.below(u32::from(HIGHEST_ROOT - LOWEST_ROOT + 1)) as u8;
+            self.semitones = 1 + self.random.below(INTERVALS.len() as u32) as u8;
+        }
+        self.phase = Phase::Reference { until: now + REFERENCE_TIME };
+    }
+
+    /// Translation key of the interval asked for.
+    pub fn interval(&self) -> &'static str {
+        INTERVALS[self.semitones as usize - 1]
+    }
+
+    pub fn root_name(&self) -> String {
+        let (note, octave) = midi::note_name(self.root);
+        format!("{}{}", note, octave)
+    }
+
+    /// The reference frequency while it should sound.
+    pub fn tone(&self, a4_freq: f32) -> Option<f32> {
+        matches!(self.phase, Phase::Reference { .. }).then(|| midi_frequency(self.root, a4_freq))
+    }
+
+    /// Moves on from the reference and gives up on an answer that never comes.
+    pub fn tick(&mut self, now: Instant) {
+        match &self.phase {
+            Phase::Reference { until } if now >= *until => {
+                self.phase = Phase::Listening { since: now, freqs: Vec::new() };
+            }
+            Phase::Listening { since, freqs } if freqs.is_empty() && now.duration_since(*since) >= ANSWER_TIMEOUT => {
+                self.finish(None);
+            }
+            _ => {}
+        }
+    }
+
+    /// Takes one analysis window; the median of a run of pitched windows is the answer.
+    pub fn hear(&mut self, freq: Option<f32>, now: Instant, a4_freq: f32) {
+        let Phase::Listening { since, freqs } = &mut self.phase else {
+            return;
+        };
+        if now.duration_since(*since) < LISTEN_AFTER {
+            return;
+        }
+        match freq {
+            Some(freq) => freqs.push(freq),
+            None => freqs.clear(),
+        }
+        if freqs.len() < ANSWER_WINDOWS {
+            return;
+        }
+        freqs.sort_by(f32::total_cmp);
+        let answer = freqs[freqs.len() / 2];
+        self.finish(Some(self.deviation(answer, a4_freq)));
+    }
+
+    /// Cents of `freq` from the expected note, folded into one octave.
+    pub fn deviation(&self, freq: f32, a4_freq: f32) -> f32 {
+        let cents = 1200.0 * (freq / self.expected(a4_freq)).log2();
+        cents - 1200.0 * (cents / 1200.0).round()
+    }
+
+    /// Mean absolute error over the answered rounds shown.
+    pub fn average(&self) -> Option<f32> {
+        let answered: Vec<f32> = self.rounds.iter().filter_map(|r| r.cents).map(f32::abs).collect();
+        (!answered.is_empty()).then(|| answered.iter().sum::<f32>() / answered.len() as f32)
+    }
+
+    fn expected(&self, a4_freq: f32) -> f32 {
+        midi_frequency(self.root + self.semitones, a4_freq)
+    }
+
+    fn finish(&mut self, cents: Option<f32>) {
+        self.phase = Phase::Scored(cents);
+        self.rounds.push_front(Round { root: self.root, semitones: self.semitones, cents });
+        self.rounds.truncate(HISTORY);
+    }
+}
@@ -0,0 +1,222 @@
+mod double_stop;
+mod game;
+mod hold;
+mod interval;
+mod scale;
+mod sequence;
+mod warmup;
+
+pub use double_stop::{IntervalAnalyzer, Reading, JUST_RATIOS};
+pub use game::{GamePhase, PitchGame, GAME_ROUNDS};
+pub use hold::{HoldPhase, HoldResult, LongTone};
+pub use interval::{IntervalDrill, Phase, INTERVALS};
+pub use scale::{ScalePractice, SCALES};
+pub use sequence::{Sequence, SequenceDrill, SequencePhase};
+pub use warmup::{Advance, WarmUp, WarmUpPhase, PATTERNS};
+
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Exercise {
+    Intervals,
+    Game,
+    LongTone,
+    WarmUp,
+    Scale,
+    Sequence,
+    DoubleStop,
+}
+
+pub const EXERCISES: [Exercise; 7] = [
+    Exercise::Intervals,
+    Exercise::Game,
+    Exercise::LongTone,
+    Exercise::WarmUp,
+    Exercise::Scale,
+    Exercise::Sequence,
+    Exercise::DoubleStop,
+];
+
+impl Exercise {
+    /// Translation key of the exercise name.
+    pub fn title(self) -> &'static str {
+        match self {
+            Exercise::Intervals => "exercise.intervals",
+            Exercise::Game => "exercise.game",
+            Exercise::LongTone => "exercise.long_tone",
+            Exercise::WarmUp => "exercise.warmup",
+            Exercise::Scale => "exercise.scale",
+            Exercise::Sequence => "exercise.sequence",
+            Exercise::DoubleStop => "exercise.double_stop",
+        }
+    }
+
+    /// Whether the exercise has options to step through with `Practice::adjust`.
+    pub fn adjustable(self) -> bool {
+        matches!(self, Exercise::LongTone | Exercise::WarmUp | Exercise::Scale | Exercise::Sequence)
+    }
+}
+
+/// The exercises on the Practice page, of which one is shown at a time.
+pub struct Practice {
+    pub exercise: Exercise,
+    pub intervals: IntervalDrill,
+    pub game: PitchGame,
+    pub long_tone: LongTone,
+    pub warmup: WarmUp,
+    pub scale: ScalePractice,
+    pub sequence: SequenceDrill,
+    pub double_stop: IntervalAnalyzer,
+}
+
+impl Practice {
+    pub fn new() -> Self {
+        Practice {
+            exercise: Exercise::Intervals,
+            intervals: IntervalDrill::new(),
+            game: PitchGame::new(),
+            long_tone: LongTone::new(),
+            warmup: WarmUp::new(),
+            scale: ScalePractice::new(),
+            sequence: SequenceDrill::new(),
+            double_stop: IntervalAnalyzer::new(),
+        }
+    }
+
+    /// Switches to the next or previous exercise, stopping the current one.
+    /// Returns the tuner target to go back to, if the old exercise moved it.
+    pub fn cycle(&mut self, delta: i32) -> Option<u8> {
+        let index = EXERCISES.iter().position(|&e| e == self.exercise).unwrap_or(0) as i32;
+        let count = EXERCISES.len() as i32;
+        self.exercise = EXERCISES[(index + delta).rem_euclid(count) as usize];
+        self.intervals.phase = Phase::Idle;
+        if !matches!(self.long_tone.phase, HoldPhase::Done) {
+            self.long_tone.phase = HoldPhase::Idle;
+        }
+        self.game.stop().or(self.warmup.stop())
+    }
+
+    pub fn adjust(&mut self, delta: i32) {
+        match self.exercise {
+            Exercise::LongTone => self.long_tone.adjust(delta),
+            Exercise::WarmUp => self.warmup.adjust(delta),
+            Exercise::Scale => self.scale.adjust(delta),
+            Exercise::Sequence => self.sequence.adjust(delta),
+            Exercise::Intervals | Exercise::Game | Exercise::DoubleStop => {}
+        }
+    }
+
+    /// Starts, restarts, or replays the current exercise. `target` is the
+    /// tuner's current target note; the result is a new one to set.
+    pub fn select(&mut self, target: u8, now: Instant) -> Option<u8> {
+        match self.exercise {
+            Exercise::Intervals => {
+                self.intervals.select(now);
+                None
+            }
+            Exercise::Game => Some(self.game.select(target, now)),
+            Exercise::LongTone => {
+                self.long_tone.select(now);
+                None
+            }
+            Exercise::WarmUp => Some(self.warmup.select(target, now)),
+            Exercise::Scale => {
+                self.scale.select();
+                None
+            }
+            Exercise::Sequence => {
+                self.sequence.select(target);
+                None
+            }
+            Exercise::DoubleStop => None,
+        }
+    }
+
+    pub fn tick(&mut self, now: Instant) -> Option<u8> {
+        match self.exercise {
+            Exercise::Intervals => {
+                self.intervals.tick(now);
+                None
+            }
+            Exercise::Game => self.game.tick(now),
+            Exercise::LongTone => {
+                self.long_tone.tick(now);
+                None
+            }
+            Exercise::WarmUp => self.warmup.tick(now),
+            Exercise::Scale | Exercise::Sequence | Exercise::DoubleStop => None,
+        }
+    }
+
+    /// Takes one analysis window. `deviation` is the cents from the tuner
+    /// target, `target` its note, and `threshold` the widest deviation that scores.
+    pub fn hear(
+        &mut self,
+        freq: Option<f32>,
+        target: u8,
+        deviation: Option<f32>,
+        threshold: f32,
+        now: Instant,
+        a4_freq: f32,
+    ) -> Option<u8> {
+        match self.exercise {
+            Exercise::Intervals => {
+                self.intervals.hear(freq, now, a4_freq);
+                None
+            }
+            Exercise::Game => self.game.hear(deviation, threshold, now),
+            Exercise::LongTone => {
+                self.long_tone.hear(freq, now, a4_freq);
+                None
+            }
+            Exercise::WarmUp => self.warmup.hear(deviation, threshold, now),
+            Exercise::Scale => {
+                self.scale.hear(freq, target, a4_freq);
+                None
+            }
+            Exercise::Sequence => {
+                self.sequence.hear(freq, a4_freq);
+                None
+            }
+            Exercise::DoubleStop => None,
+        }
+    }
+
+    /// Takes the spectrum of one analysis window, for exercises that look
+    /// at more than the detected pitch.
+    pub fn hear_spectrum(&mut self, spectrum: &[f32], bin_hz: f32) {
+        if self.exercise == Exercise::DoubleStop {
+            self.double_stop.hear(spectrum, bin_hz);
+        }
+    }
+
+    /// A reference tone the current exercise wants played.
+    pub fn tone(&self, now: Instant, a4_freq: f32) -> Option<f32> {
+        match self.exercise {
+            Exercise::Intervals => self.intervals.tone(a4_freq),
+            Exercise::WarmUp => self.warmup.tone(now, a4_freq),
+            Exercise::Game | Exercise::LongTone | Exercise::Scale | Exercise::Sequence | Exercise::DoubleStop => None,
+        }
+    }
+}
+
+pub fn midi_frequency(number: u8, a4_freq: f32) -> f32 {
+    a4_freq * 2f32.powf((f32::from(number) - 69.0) / 12.0)
+}
+
+/// Xorshift seeded from the clock; good enough to vary the questions.
+struct Random(u32);
+
+impl Random {
+    fn new() -> Self {
+        Random(SystemTime::now().duration_since(UNIX_EPOCH).map_or(1, |d| d.subsec_nanos()) | 1)
+    }
+
+    /// A number in `0..bound`.
+    fn below(&mut self, bound: u32) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0 % bound
+    }
+}
@@ -0,0 +1,132 @@
+use crate::midi;
+
+/// Scales: a translation key and the degrees in semitones above the key note.
+pub const SCALES: [(&str, &[u8]); 7] = [
+    ("scale.major", &[0, 2, 4, 5, 7, 9, 11]),
+    ("scale.natural_minor", &[0, 2, 3, 5, 7, 8, 10]),
+    ("scale.harmonic_minor", &[0, 2, 3, 5, 7, 8, 11]),
+    ("scale.melodic_minor", &[0, 2, 3, 5, 7, 9, 11]),
+    ("scale.dorian", &[0, 2, 3, 5, 7, 9, 10]),
+    ("scale.major_pentatonic", &[0, 2, 4, 7, 9]),
+    ("scale.minor_pentatonic", &[0, 3, 5, 7, 10]),
+];
+/// Consecutive windows on one note before it counts as played.
+const STABLE_WINDOWS: u32 = 4;
+
+/// The note being played, its degree in the scale if it has one, and its
+/// cents from equal temperament.
+pub struct Heard {
+    pub note: u8,
+    pub degree: Option<usize>,
+    pub cents: f32,
+}
+
+/// Shows which degree of a key and scale is being played and how well in
+/// tune, keeps each degree's average, and can follow a run up and down the
+/// scale with a cursor on the next expected note. The key is the pitch
+/// class of the tuner's target.
+pub struct ScalePractice {
+    pub scale: usize,
+    pub key: u8,
+    /// Position in the run up and down the scale, when following it.
+    pub cursor: Option<usize>,
+    pub heard: Option<Heard>,
+    /// Sum of the cents and number of notes played on each degree.
+    degrees: Vec<(f32, u32)>,
+    /// The note of the current run of windows, their count and summed cents.
+    run: Option<(u8, u32, f32)>,
+}
+
+impl ScalePractice {
+    pub fn new() -> Self {
+        ScalePractice {
+            scale: 0,
+            key: 0,
+            cursor: None,
+            heard: None,
+            degrees: vec![(0.0, 0); SCALES[0].1.len()],
+            run: None,
+        }
+    }
+
+    pub fn degrees(&self) -> &'static [u8] {
+        SCALES[self.scale].1
+    }
+
+    pub fn adjust(&mut self, delta: i32) {
+        self.scale = (self.scale as i32 + delta).rem_euclid(SCALES.len() as i32) as usize;
+        self.reset();
+    }
+
+    /// Starts or stops following the scale from its first degree.
+    pub fn select(&mut self) {
+        self.cursor = match self.cursor {
+            Some(_) => None,
+            None => Some(0),
+        };
+    }
+
+    /// The degree the cursor expects next; the scale's length is the octave.
+    pub fn expected(&self) -> Option<usize> {
+        let len = self.degrees().len();
+        self.cursor.map(|position| if position <= len { position } else { 2 * len - position })
+    }
+
+    /// Average cents of each degree played so far.
+    pub fn averages(&self) -> Vec<Option<f32>> {
+        self.degrees.iter().map(|&(sum, count)| (count > 0).then(|| sum / count as f32)).collect()
+    }
+
+    /// Takes one analysis window; `key` is the tuner's target note.
+    pub fn hear(&mut self, freq: Option<f32>, key: u8, a4_freq: f32) {
+        if key % 12 != self.key {
+            self.key = key % 12;
+            self.reset();
+        }
+        let Some((note, cents)) = freq.and_then(|freq| midi::midi_note(freq, a4_freq)) else {
+            self.end_run();
+            self.heard = None;
+            return;
+        };
+        let degree = self.degree_of(note);
+        self.heard = Some(Heard { note, degree, cents });
+
+        match &mut self.run {
+            Some((run_note, count, sum)) if *run_note == note => {
+                *count += 1;
+                *sum += cents;
+            }
+            _ => {
+                self.end_run();
+                self.run = Some((note, 1, cents));
+            }
+        }
+        if self.run.is_some_and(|(_, count, _)| count == STABLE_WINDOWS) && degree.is_some() {
+            let len = self.degrees().len();
+            if self.expected().map(|expected| expected % len) == degree {
+                self.cursor = self.cursor.map(|position| (position + 1) % (2 * len));
+            }
+        }
+    }
+
+    fn degree_of(&self, note: u8) -> Option<usize> {
+        let interval = (note + 12 - self.key) % 12;
+        self.degrees().iter().position(|&step| step == interval)
+    }
+
+    fn end_run(&mut self) {
+        if let Some((note, count, sum)) = self.run.take() {
+            if let (true, Some(degree)) = (count >= STABLE_WINDOWS, self.degree_of(note)) {
+                self.degrees[degree].0 += sum / count as f32;
+                self.degrees[degree].1 += 1;
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.degrees = vec![(0.0, 0); self.degrees().len()];
+        self.run = None;
+        self.heard = None;
+        self.cursor = self.cursor.map(|_| 0);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::midi;
+
+/// Built-in arpeggios: a translation key and the notes in semitones above
+/// the tuner's target.
+const ARPEGGIOS: [(&str, &[u8]); 4] = [
+    ("sequence.major", &[0, 4, 7, 12, 16, 19, 24, 19, 16, 12, 7, 4, 0]),
+    ("sequence.minor", &[0, 3, 7, 12, 15, 19, 24, 19, 15, 12, 7, 3, 0]),
+    ("sequence.dominant", &[0, 4, 7, 10, 12, 10, 7, 4, 0]),
+    ("sequence.diminished", &[0, 3, 6, 9, 12, 9, 6, 3, 0]),
+];
+/// Consecutive windows on the expected note before it counts as played.
+const STABLE_WINDOWS: u32 = 4;
+
+pub enum Sequence {
+    /// A translation key and semitones above the target.
+    Arpeggio(&'static str, &'static [u8]),
+    /// A name and MIDI note numbers from the configuration.
+    Custom(String, Vec<u8>),
+}
+
+/// A note of the sequence and its mean cents, or `None` if it was skipped.
+pub struct NoteResult {
+    pub note: u8,
+    pub cents: Option<f32>,
+}
+
+pub enum SequencePhase {
+    Idle,
+    Playing { position: usize, notes: Vec<u8> },
+    Done,
+}
+
+/// Presents an arpeggio or user-defined sequence and records the
+/// intonation of each note as it is played through, for a chart at the end.
+pub struct SequenceDrill {
+    pub phase: SequencePhase,
+    pub sequences: Vec<Sequence>,
+    pub selected: usize,
+    pub results: Vec<NoteResult>,
+    /// Windows on the expected note so far and their summed cents.
+    run: Option<(u32, f32)>,
+}
+
+impl SequenceDrill {
+    pub fn new() -> Self {
+        SequenceDrill {
+            phase: SequencePhase::Idle,
+            sequences: ARPEGGIOS.iter().map(|&(name, steps)| Sequence::Arpeggio(name, steps)).collect(),
+            selected: 0,
+            results: Vec::new(),
+            run: None,
+        }
+    }
+
+    /// Adds sequences from `[sequences]` after the built-in ones.
+    pub fn add_custom(&mut self, sequences: Vec<(String, Vec<u8>)>) {
+        self.sequences.extend(sequences.into_iter().map(|(name, notes)| Sequence::Custom(name, notes)));
+    }
+
+    pub fn adjust(&mut self, delta: i32) {
+        if !matches!(self.phase, SequencePhase::Playing { .. }) {
+            self.selected = (self.selected as i32 + delta).rem_euclid(self.sequences.len() as i32) as usize;
+        }
+    }
+
+    /// The notes of the selected sequence, with built-in ones starting on `target`.
+    pub fn notes(&self, target: u8) -> Vec<u8> {
+        match &self.sequences[self.selected] {
+            Sequence::Arpeggio(_, steps) => steps.iter().map(|&step| target.saturating_add(step).min(127)).collect(),
+            Sequence::Custom(_, notes) => notes.clone(),
+        }
+    }
+
+    /// Starts the selected sequence, or stops the one being played and
+    /// marks the rest as skipped.
+    pub fn select(&mut self, target: u8) {
+        if let SequencePhase::Playing { position, notes } = &self.phase {
+            self.results.extend(notes[*position..].iter().map(|&note| NoteResult { note, cents: None }));
+            self.phase = SequencePhase::Done;
+            return;
+        }
+        self.results.clear();
+        self.run = None;
+        self.phase = SequencePhase::Playing { position: 0, notes: self.notes(target) };
+    }
+
+    /// Takes one analysis window. A note counts once it has been held for a
+    /// few windows, and is scored when it ends; other notes are ignored.
+    pub fn hear(&mut self, freq: Option<f32>, a4_freq: f32) {
+        let SequencePhase::Playing { position, notes } = &mut self.phase else {
+            return;
+        };
+        let expected = notes[*position];
+        match freq.and_then(|freq| midi::midi_note(freq, a4_freq)) {
+            Some((note, cents)) if note == expected => {
+                let (count, sum) = self.run.get_or_insert((0, 0.0));
+                *count += 1;
+                *sum += cents;
+                return;
+            }
+            _ => {}
+        }
+        let Some((count, sum)) = self.run.take() else {
+            return;
+        };
+        if count < STABLE_WINDOWS {
+            return;
+        }
+        self.results.push(NoteResult { note: expected, cents: Some(sum / count as f32) });
+        *position += 1;
+        if *position == notes.len() {
+            self.phase = SequencePhase::Done;
+        }
+    }
+}
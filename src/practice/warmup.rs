@@ -0,0 +1,186 @@
+use std::time::{Duration, Instant};
+
+use super::midi_frequency;
+
+/// Warm-up patterns: a translation key and the steps in semitones above
+/// the starting note.
+pub const PATTERNS: [(&str, &[u8]); 3] = [
+    ("warmup.chromatic", &[0, 1, 2, 3, 4, 5, 4, 3, 2, 1, 0]),
+    ("warmup.five_note", &[0, 2, 4, 5, 7, 5, 4, 2, 0]),
+    ("warmup.arpeggio", &[0, 4, 7, 12, 7, 4, 0]),
+];
+/// The pattern is sung this many times, each a semitone higher.
+const PASSES: usize = 3;
+/// Each step starts with its note played this long as a guide, and
+/// listening starts a little after it stops.
+const GUIDE: Duration = Duration::from_millis(700);
+const LISTEN_AFTER: Duration = Duration::from_millis(1000);
+const STEP_TIME: Duration = Duration::from_millis(2500);
+const MATCH_HOLD: Duration = Duration::from_millis(500);
+const MATCH_TIMEOUT: Duration = Duration::from_secs(8);
+/// Readings further than this from the step are taken to be something else.
+const MAX_CENTS: f32 = 100.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Advance {
+    /// Move on after a fixed time.
+    Timer,
+    /// Move on once the note has been held in tune.
+    Match,
+}
+
+/// One step sung: mean cents from the note, and for `Advance::Match` how
+/// long it took to match. `None` when it was never heard.
+pub struct Step {
+    pub target: u8,
+    pub cents: Option<f32>,
+    pub took: Option<Duration>,
+}
+
+pub enum WarmUpPhase {
+    Idle,
+    Running {
+        step: usize,
+        since: Instant,
+        hold_since: Option<Instant>,
+        cents: Vec<f32>,
+    },
+    Done,
+}
+
+/// A guided warm-up for singers: the target moves through a pattern from
+/// the tuner's target, rising a semitone each pass, and each step's
+/// intonation is logged.
+pub struct WarmUp {
+    pub phase: WarmUpPhase,
+    pub pattern: usize,
+    pub advance: Advance,
+    pub steps: Vec<Step>,
+    home: u8,
+}
+
+impl WarmUp {
+    pub fn new() -> Self {
+        WarmUp {
+            phase: WarmUpPhase::Idle,
+            pattern: 0,
+            advance: Advance::Timer,
+            steps: Vec::new(),
+            home: 57,
+        }
+    }
+
+    /// Steps through every pattern, first timed and then matched.
+    pub fn adjust(&mut self, delta: i32) {
+        if matches!(self.phase, WarmUpPhase::Running { .. }) {
+            return;
+        }
+        let count = PATTERNS.len() as i32 * 2;
+        let index = self.pattern as i32 * 2 + i32::from(self.advance == Advance::Match);
+        let index = (index + delta).rem_euclid(count);
+        self.pattern = index as usize / 2;
+        self.advance = if index % 2 == 0 { Advance::Timer } else { Advance::Match };
+    }
+
+    pub fn total_steps(&self) -> usize {
+        PATTERNS[self.pattern].1.len() * PASSES
+    }
+
+    /// Starts from `home`, returning the first target; while running, stops
+    /// and returns `home` again.
+    pub fn select(&mut self, home: u8, now: Instant) -> u8 {
+        if let Some(home) = self.stop() {
+            return home;
+        }
+        self.home = home;
+        self.steps.clear();
+        self.start_step(0, now)
+    }
+
+    /// Ends a running warm-up, returning the note it started from.
+    pub fn stop(&mut self) -> Option<u8> {
+        matches!(self.phase, WarmUpPhase::Running { .. }).then(|| {
+            self.phase = WarmUpPhase::Done;
+            self.home
+        })
+    }
+
+    /// The guide tone at the start of each step.
+    pub fn tone(&self, now: Instant, a4_freq: f32) -> Option<f32> {
+        match self.phase {
+            WarmUpPhase::Running { step, since, .. } if now.duration_since(since) < GUIDE => {
+                Some(midi_frequency(self.note(step), a4_freq))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn tick(&mut self, now: Instant) -> Option<u8> {
+        let WarmUpPhase::Running { since, .. } = self.phase else {
+            return None;
+        };
+        let limit = match self.advance {
+            Advance::Timer => STEP_TIME,
+            Advance::Match => MATCH_TIMEOUT,
+        };
+        (now.duration_since(since) >= limit).then(|| self.finish_step(None, now)).flatten()
+    }
+
+    /// Takes the cents from the current step for one analysis window,
+    /// returning the next target when the step ends.
+    pub fn hear(&mut self, deviation: Option<f32>, threshold: f32, now: Instant) -> Option<u8> {
+        let advance = self.advance;
+        let WarmUpPhase::Running { since, hold_since, cents, .. } = &mut self.phase else {
+            return None;
+        };
+        let since = *since;
+        if now.duration_since(since) < LISTEN_AFTER {
+            return None;
+        }
+        let Some(deviation) = deviation.filter(|cents| cents.abs() <= MAX_CENTS) else {
+            *hold_since = None;
+            return None;
+        };
+        cents.push(deviation);
+        if advance == Advance::Timer {
+            return None;
+        }
+        if deviation.abs() >= threshold {
+            *hold_since = None;
+            return None;
+        }
+        let start = *hold_since.get_or_insert(now);
+        (now.duration_since(start) >= MATCH_HOLD).then(|| self.finish_step(Some(now - since), now)).flatten()
+    }
+
+    /// Mean absolute cents over the steps that were heard.
+    pub fn average(&self) -> Option<f32> {
+        let heard: Vec<f32> = self.steps.iter().filter_map(|s| s.cents).map(f32::abs).collect();
+        (!heard.is_empty()).then(|| heard.iter().sum::<f32>() / heard.len() as f32)
+    }
+
+    fn note(&self, step: usize) -> u8 {
+        let pattern = PATTERNS[self.pattern].1;
+        let pass = (step / pattern.len()) as u8;
+        self.home.saturating_add(pass + pattern[step % pattern.len()]).min(127)
+    }
+
+    fn start_step(&mut self, step: usize, now: Instant) -> u8 {
+        self.phase = WarmUpPhase::Running { step, since: now, hold_since: None, cents: Vec::new() };
+        self.note(step)
+    }
+
+    fn finish_step(&mut self, took: Option<Duration>, now: Instant) -> Option<u8> {
+        let WarmUpPhase::Running { step, cents, .. } = &self.phase else {
+            return None;
+        };
+        let step = *step;
+        let mean = (!cents.is_empty()).then(|| cents.iter().sum::<f32>() / cents.len() as f32);
+        self.steps.push(Step { target: self.note(step), cents: mean, took });
+        if step + 1 == self.total_steps() {
+            self.phase = WarmUpPhase::Done;
+            return Some(self.home);
+        }
+        Some(self.start_step(step + 1, now))
+    }
+}
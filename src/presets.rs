@@ -0,0 +1,127 @@
+use std::sync::OnceLock;
+
+use crate::tuner::{Register, Tuner};
+
+#[derive(Clone)]
+pub struct Preset {
+    pub name: &'static str,
+    pub strings: &'static [(&'static str, i32)],
+    /// Translation keys for what to tell the player when the pitch is flat or sharp.
+    pub raise: &'static str,
+    pub lower: &'static str,
+}
+
+const STRING_RAISE: &str = "guide.tighten_string";
+const STRING_LOWER: &str = "guide.loosen_string";
+const BOWED_RAISE: &str = "guide.tighten_bowed";
+const BOWED_LOWER: &str = "guide.loosen_bowed";
+/// Presets with a string below this, such as B0 at 31 Hz, get the
+/// low-register analysis by default.
+const LOW_REGISTER_HZ: f32 = 38.0;
+
+static ALL: OnceLock<Vec<Preset>> = OnceLock::new();
+
+impl Preset {
+    /// A plucked-string preset, e.g. one defined in a config file.
+    pub fn custom(name: &'static str, strings: &'static [(&'static str, i32)]) -> Self {
+        Preset {
+            name,
+            strings,
+            raise: STRING_RAISE,
+            lower: STRING_LOWER,
+        }
+    }
+
+    /// The register analysed when none is chosen: low for a preset with a string
+    /// as low as a bass's low B, otherwise normal.
+    pub fn register(&self) -> Register {
+        let lowest = self.strings.iter().map(|&(note, octave)| Tuner::note_name_to_frequency(note, octave, 440.0));
+        if lowest.fold(f32::INFINITY, f32::min) < LOW_REGISTER_HZ {
+            Register::Low
+        } else {
+            Register::Normal
+        }
+    }
+}
+
+/// The built-in presets followed by any added with `add_custom`.
+pub fn all() -> &'static [Preset] {
+    ALL.get_or_init(|| PRESETS.to_vec())
+}
+
+/// Appends `custom` to the presets returned by `all`. Only works before the
+/// first call to `all`.
+pub fn add_custom(custom: Vec<Preset>) -> Result<(), String> {
+    ALL.set(PRESETS.iter().cloned().chain(custom).collect())
+        .map_err(|_| "Presets are already in use".to_string())
+}
+
+pub const PRESETS: [Preset; 11] = [
+    Preset {
+        name: "Guitar (Standard)",
+        strings: &[("E", 2), ("A", 2), ("D", 3), ("G", 3), ("B", 3), ("E", 4)],
+        raise: STRING_RAISE,
+        lower: STRING_LOWER,
+    },
+    Preset {
+        name: "Guitar (Drop D)",
+        strings: &[("D", 2), ("A", 2), ("D", 3), ("G", 3), ("B", 3), ("E", 4)],
+        raise: STRING_RAISE,
+        lower: STRING_LOWER,
+    },
+    Preset {
+        name: "Bass (4-string)",
+        strings: &[("E", 1), ("A", 1), ("D", 2), ("G", 2)],
+        raise: STRING_RAISE,
+        lower: STRING_LOWER,
+    },
+    Preset {
+        name: "Bass (5-string)",
+        strings: &[("B", 0), ("E", 1), ("A", 1), ("D", 2), ("G", 2)],
+        raise: STRING_RAISE,
+        lower: STRING_LOWER,
+    },
+    Preset {
+        name: "Bass (6-string)",
+        strings: &[("B", 0), ("E", 1), ("A", 1), ("D", 2), ("G", 2), ("C", 3)],
+        raise: STRING_RAISE,
+        lower: STRING_LOWER,
+    },
+    Preset {
+        name: "Ukulele",
+        strings: &[("G", 4), ("C", 4), ("E", 4), ("A", 4)],
+        raise: STRING_RAISE,
+        lower: STRING_LOWER,
+    },
+    Preset {
+        name: "Mandolin",
+        strings: &[("G", 3), ("D", 4), ("A", 4), ("E", 5)],
+        raise: STRING_RAISE,
+        lower: STRING_LOWER,
+    },
+    Preset {
+        name: "Violin",
+        strings: &[("G", 3), ("D", 4), ("A", 4), ("E", 5)],
+        raise: BOWED_RAISE,
+        lower: BOWED_LOWER,
+    },
+    Preset {
+        name: "Viola",
+        strings: &[("C", 3), ("G", 3), ("D", 4), ("A", 4)],
+        raise: BOWED_RAISE,
+        lower: BOWED_LOWER,
+    },
+    Preset {
+        name: "Cello",
+        strings: &[("C", 2), ("G", 2), ("D", 3), ("A", 3)],
+        raise: BOWED_RAISE,
+        lower: BOWED_LOWER,
+    },
+    // No strings: any note can be the target, and auto-lock may follow one.
+    Preset {
+        name: "Chromatic",
+        strings: &[],
+        raise: STRING_RAISE,
+        lower: STRING_LOWER,
+    },
+];
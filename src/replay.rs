@@ -0,0 +1,162 @@
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::analysis::{Setup, Windows};
+use crate::config::Config;
+use crate::tuner::{Register, Tuner};
+
+const MAGIC: &[u8; 8] = b"RTSAMPLE";
+const VERSION: u8 = 1;
+const START: u8 = b'S';
+const FRAME: u8 = b'F';
+const RESET: u8 = b'R';
+
+/// What the analysis thread received, in order.
+pub enum Event {
+    /// A new tuner at this sample rate; later frames are analysed at it.
+    Start(u32),
+    Frame(Vec<f32>),
+    /// Buffered audio was discarded, e.g. on pause.
+    Reset,
+}
+
+/// Writes every frame handed to the detector, exactly as received, so a
+/// wrong reading can be replayed. The file is an 8-byte magic, a version
+/// byte, the algorithm name (length byte, then UTF-8), and then records: `S`
+/// with a little-endian `u32` sample rate, `F` with a `u32` sample count and
+/// that many `f32` samples, or `R`.
+pub struct Recorder {
+    output: BufWriter<File>,
+}
+
+impl Recorder {
+    pub fn create(path: &Path, algorithm: &str) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let mut recorder = Recorder { output: BufWriter::new(file) };
+        let name = algorithm.as_bytes();
+        let header = [&MAGIC[..], &[VERSION, name.len() as u8], name].concat();
+        recorder.write(&header).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(recorder)
+    }
+
+    /// Flushes after each record so the file is complete up to the last
+    /// frame even if the app is killed.
+    pub fn record(&mut self, event: &Event) -> std::io::Result<()> {
+        match event {
+            Event::Start(sample_rate) => {
+                self.output.write_all(&[START])?;
+                self.write(&sample_rate.to_le_bytes())
+            }
+            Event::Frame(samples) => {
+                self.output.write_all(&[FRAME])?;
+                self.output.write_all(&(samples.len() as u32).to_le_bytes())?;
+                for sample in samples {
+                    self.output.write_all(&sample.to_le_bytes())?;
+                }
+                self.output.flush()
+            }
+            Event::Reset => self.write(&[RESET]),
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        self.output.write_all(bytes)?;
+        self.output.flush()
+    }
+}
+
+/// A recording: the algorithm it was made with and its events.
+pub struct Recording {
+    pub algorithm: String,
+    pub events: Vec<Event>,
+}
+
+pub fn read(path: &Path) -> Result<Recording, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse(&bytes).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn parse(bytes: &[u8]) -> Result<Recording, String> {
+    if bytes.len() < MAGIC.len() + 2 || &bytes[..MAGIC.len()] != MAGIC {
+        return Err("not a sample recording".to_string());
+    }
+    if bytes[MAGIC.len()] != VERSION {
+        return Err(format!("unsupported recording version {}", bytes[MAGIC.len()]));
+    }
+    let name_len = bytes[MAGIC.len() + 1] as usize;
+    let mut pos = MAGIC.len() + 2;
+    let name = bytes.get(pos..pos + name_len).ok_or("truncated header")?;
+    let algorithm = String::from_utf8(name.to_vec()).map_err(|_| "invalid algorithm name")?;
+    pos += name_len;
+
+    let mut events = Vec::new();
+    let u32_at = |pos: usize| bytes.get(pos..pos + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+    while pos < bytes.len() {
+        let tag = bytes[pos];
+        pos += 1;
+        match tag {
+            START => {
+                events.push(Event::Start(u32_at(pos).ok_or("truncated sample rate")?));
+                pos += 4;
+            }
+            FRAME => {
+                let count = u32_at(pos).ok_or("truncated frame")? as usize;
+                pos += 4;
+                let body = bytes.get(pos..pos + count * 4).ok_or("truncated frame")?;
+                let samples = body.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+                events.push(Event::Frame(samples));
+                pos += count * 4;
+            }
+            RESET => events.push(Event::Reset),
+            _ => return Err(format!("unknown record {:#04x} at byte {}", tag, pos - 1)),
+        }
+    }
+    Ok(Recording { algorithm, events })
+}
+
+/// Feeds a recording through the detector the same way the live analysis
+/// thread does and prints every window, so the output is the same on every
+/// run. `algorithm` overrides the one the recording was made with.
+pub fn run(config: &Config, path: &Path, algorithm: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let a4_freq = config.get_f64("tuner", "a4").map_or(440.0, |a4| (a4 as f32).clamp(432.0, 450.0));
+    let recording = read(path)?;
+    let algorithm = algorithm.unwrap_or(&recording.algorithm);
+    let setup = Setup { algorithm: Some(algorithm.to_string()), ..Setup::from_config(config) };
+
+    let mut tuner = None;
+    let mut windows = Windows::new();
+    let mut consumed = 0usize;
+    let mut count = 0usize;
+    for event in recording.events {
+        match event {
+            Event::Start(sample_rate) => {
+                println!("# {} Hz, {}", sample_rate, algorithm);
+                tuner = Some(setup.tuner(sample_rate, Register::Normal)?);
+                windows.clear();
+                consumed = 0;
+            }
+            Event::Reset => {
+                println!("# reset");
+                windows.clear();
+            }
+            Event::Frame(samples) => {
+                let tuner = tuner.as_mut().ok_or("recording has a frame before its sample rate")?;
+                consumed += samples.len();
+                for analysis in windows.push(tuner, &samples) {
+                    count += 1;
+                    let at = consumed as f32 / tuner.sample_rate() as f32;
+                    match analysis.freq {
+                        Some(freq) => {
+                            let (note, octave, cents) = Tuner::nearest_note(freq, a4_freq);
+                            let reading = format!("{:>2}{:<2} {:8.3} Hz {:+6.2}¢", note, octave, freq, cents);
+                            println!("{:6} {:8.3}s {}", count, at, reading);
+                        }
+                        None => println!("{:6} {:8.3}s -", count, at),
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::csvlog::utc_timestamp;
+use crate::json::json_escape;
+use crate::presets;
+use crate::tuner::Tuner;
+use crate::ui::UiState;
+
+/// Stable readings further than this from the target are taken to be a
+/// different string and left out.
+const MAX_CENTS: f32 = 100.0;
+
+/// Each string's first and last stable reading while it was the target.
+struct StringResult {
+    note: String,
+    octave: i32,
+    initial_cents: f32,
+    final_cents: f32,
+    first_at: SystemTime,
+    last_at: SystemTime,
+}
+
+/// Follows a tuning session and writes a summary on exit: Markdown, or
+/// JSON when the path ends in `.json`.
+pub struct TuningReport {
+    path: PathBuf,
+    started: SystemTime,
+    strings: Vec<StringResult>,
+}
+
+impl TuningReport {
+    pub fn new(path: &Path) -> Self {
+        TuningReport {
+            path: path.to_path_buf(),
+            started: SystemTime::now(),
+            strings: Vec::new(),
+        }
+    }
+
+    /// Call after each analysis; only stable readings near the target count.
+    pub fn update(&mut self, state: &UiState) {
+        let Some(reading) = state.stable_reading() else {
+            return;
+        };
+        if reading.deviation_cents.abs() > MAX_CENTS {
+            return;
+        }
+        let now = SystemTime::now();
+        let (note, octave) = (&state.target_note, state.target_octave);
+        match self.strings.iter_mut().find(|s| s.note == *note && s.octave == octave) {
+            Some(string) => {
+                string.final_cents = reading.deviation_cents;
+                string.last_at = now;
+            }
+            None => self.strings.push(StringResult {
+                note: note.clone(),
+                octave,
+                initial_cents: reading.deviation_cents,
+                final_cents: reading.deviation_cents,
+                first_at: now,
+                last_at: now,
+            }),
+        }
+    }
+
+    pub fn write(&mut self, state: &UiState) -> Result<&Path, String> {
+        // The instrument's strings first, in its order, then any other targets.
+        let strings = presets::all()[state.preset].strings;
+        self.strings.sort_by_key(|s| {
+            strings.iter().position(|&(note, octave)| note == s.note && octave == s.octave).unwrap_or(usize::MAX)
+        });
+        let finished = SystemTime::now();
+        let body = if self.path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+            self.json(state, finished)
+        } else {
+            self.markdown(state, finished)
+        };
+        fs::write(&self.path, body).map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))?;
+        Ok(&self.path)
+    }
+
+    fn json(&self, state: &UiState, finished: SystemTime) -> String {
+        let strings: Vec<String> = self
+            .strings
+            .iter()
+            .map(|s| {
+                let seconds = state.string_timer.time_for(&s.note, s.octave);
+                format!(
+                    "    {{\"note\":\"{}\",\"octave\":{},\"target_hz\":{:.3},\"initial_cents\":{:.2},\"final_cents\":{:.2},\"in_tune\":{},\"seconds_to_tune\":{},\"first_reading\":\"{}\",\"last_reading\":\"{}\"}}",
+                    s.note,
+                    s.octave,
+                    Tuner::note_name_to_frequency(&s.note, s.octave, state.a4_freq),
+                    s.initial_cents,
+                    s.final_cents,
+                    s.final_cents.abs() < state.in_tune_cents,
+                    seconds.map_or("null".to_string(), |time| format!("{:.1}", time.as_secs_f32())),
+                    utc_timestamp(s.first_at),
+                    utc_timestamp(s.last_at)
+                )
+            })
+            .collect();
+        format!(
+            "{{\n  \"instrument\":\"{}\",\n  \"a4\":{:.1},\n  \"temperament\":\"equal\",\n  \"in_tune_cents\":{:.1},\n  \"started\":\"{}\",\n  \"finished\":\"{}\",\n  \"strings\":[\n{}\n  ]\n}}\n",
+            json_escape(presets::all()[state.preset].name),
+            state.a4_freq,
+            state.in_tune_cents,
+            utc_timestamp(self.started),
+            utc_timestamp(finished),
+            strings.join(",\n")
+        )
+    }
+
+    fn markdown(&self, state: &UiState, finished: SystemTime) -> String {
+        let mut out = format!(
+            "# Tuning report\n\n\
+             - Instrument: {}\n\
+             - Reference: A4 = {:.1} Hz, equal temperament\n\
+             - In tune within: ±{:.1} cents\n\
+             - Started: {}\n\
+             - Finished: {}\n\n",
+            presets::all()[state.preset].name,
+            state.a4_freq,
+            state.in_tune_cents,
+            utc_timestamp(self.started),
+            utc_timestamp(finished)
+        );
+        if self.strings.is_empty() {
+            out.push_str("No strings were measured.\n");
+            return out;
+        }
+        out.push_str("| String | Target | Initial | Final | In tune | Time to tune | Last reading |\n");
+        out.push_str("|---|---|---|---|---|---|---|\n");
+        for s in &self.strings {
+            let seconds = state.string_timer.time_for(&s.note, s.octave);
+            out.push_str(&format!(
+                "| {}{} | {:.2} Hz | {:+.1}¢ | {:+.1}¢ | {} | {} | {} |\n",
+                s.note,
+                s.octave,
+                Tuner::note_name_to_frequency(&s.note, s.octave, state.a4_freq),
+                s.initial_cents,
+                s.final_cents,
+                if s.final_cents.abs() < state.in_tune_cents { "yes" } else { "no" },
+                seconds.map_or("-".to_string(), |time| format!("{:.1} s", time.as_secs_f32())),
+                utc_timestamp(s.last_at)
+            ));
+        }
+        out
+    }
+}
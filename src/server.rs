@@ -0,0 +1,634 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::json::json_escape;
+use crate::presets;
+use crate::ui::{TuningStatus, UiState};
+
+const DEFAULT_PORT: u16 = 8765;
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a slow client may hold up a broadcast before it is dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_millis(200);
+const MAX_HEADER_LINES: usize = 64;
+/// The most a request line and headers may take; requests have no body.
+const MAX_REQUEST_BYTES: u64 = 16 * 1024;
+/// Open connections, WebSocket clients included, beyond which new ones are
+/// closed straight away.
+const MAX_CONNECTIONS: usize = 32;
+/// The largest frame a WebSocket client may send; the viewer sends none.
+const MAX_FRAME_BYTES: u64 = 1024;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+const NOTES: [&str; 12] = ["A", "A#", "B", "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#"];
+
+/// A minimal remote view that follows the WebSocket feed.
+const VIEWER: &str = r#"<!DOCTYPE html>
+<html><head><meta charset="utf-8"><meta name="viewport" content="width=device-width">
+<title>rust_tuner</title>
+<style>
+body{margin:0;height:100vh;display:flex;flex-direction:column;align-items:center;justify-content:center;
+background:#111;color:#eee;font-family:sans-serif}
+#note{font-size:30vmin;font-weight:bold}#cents{font-size:8vmin}
+.perfect{color:#4c4}.close{color:#dd4}.far{color:#d44}.none{color:#666}
+</style></head>
+<body><div id="note" class="none">-</div><div id="cents">&nbsp;</div>
+<script>
+function connect(){
+  const ws=new WebSocket("ws://"+location.host+"/ws");
+  ws.onmessage=e=>{
+    const r=JSON.parse(e.data),note=document.getElementById("note"),cents=document.getElementById("cents");
+    note.className=r.status;
+    note.textContent=r.note?r.note+r.octave:"-";
+    cents.textContent=r.cents===null?" ":(r.cents>0?"+":"")+r.cents.toFixed(1)+" cents";
+  };
+  ws.onclose=()=>setTimeout(connect,1000);
+}
+connect();
+</script></body></html>
+"#;
+
+/// A settings change requested through the HTTP API, already validated.
+pub enum Command {
+    Target(String, i32),
+    A4(f32),
+    Preset(usize),
+}
+
+/// Serves a viewer page on `/`, broadcasts readings as JSON to WebSocket
+/// clients on `/ws`, and answers the HTTP API under `/api`.
+pub struct Server {
+    address: SocketAddr,
+    messages: Sender<String>,
+    latest: Arc<Mutex<String>>,
+    commands: Receiver<Command>,
+}
+
+/// What connection threads share with the server.
+struct Shared {
+    /// The address listened on.
+    address: SocketAddr,
+    /// WebSocket clients, by peer address.
+    clients: Mutex<Vec<(SocketAddr, TcpStream)>>,
+    connections: AtomicUsize,
+    latest: Arc<Mutex<String>>,
+    commands: Sender<Command>,
+}
+
+impl Server {
+    /// Listens on `spec`: `host:port`, a bare port on 127.0.0.1, or empty
+    /// for the default port. Other machines can connect only when given a
+    /// host such as `0.0.0.0:8765`.
+    pub fn start(spec: &str) -> Result<Self, String> {
+        let spec = match spec {
+            "" => format!("127.0.0.1:{}", DEFAULT_PORT),
+            port if port.parse::<u16>().is_ok() => format!("127.0.0.1:{}", port),
+            address => address.to_string(),
+        };
+        let listener = TcpListener::bind(&spec).map_err(|e| format!("Failed to listen on {}: {}", spec, e))?;
+        let address = listener.local_addr().map_err(|e| e.to_string())?;
+
+        let latest = Arc::new(Mutex::new(String::from("{}")));
+        let (messages, queue) = crossbeam_channel::unbounded();
+        let (command_tx, commands) = crossbeam_channel::unbounded();
+        let shared = Arc::new(Shared {
+            address,
+            clients: Mutex::new(Vec::new()),
+            connections: AtomicUsize::new(0),
+            latest: Arc::clone(&latest),
+            commands: command_tx,
+        });
+
+        let accepted = Arc::clone(&shared);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if accepted.connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                    accepted.connections.fetch_sub(1, Ordering::SeqCst);
+                    continue;
+                }
+                let shared = Arc::clone(&accepted);
+                thread::spawn(move || {
+                    handle_connection(stream, &shared);
+                    shared.connections.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+        thread::spawn(move || broadcast(&queue, &shared.clients));
+
+        Ok(Server {
+            address,
+            messages,
+            latest,
+            commands,
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Queues `message` for every connected WebSocket client and keeps it as
+    /// the answer to `GET /api/status`.
+    pub fn send(&self, message: String) {
+        if let Ok(mut latest) = self.latest.lock() {
+            latest.clone_from(&message);
+        }
+        let _ = self.messages.send(message);
+    }
+
+    /// Changes requested since the last call.
+    pub fn commands(&self) -> impl Iterator<Item = Command> + '_ {
+        self.commands.try_iter()
+    }
+}
+
+/// The current reading as a JSON object; `note`, `octave`, `freq`, and
+/// `cents` are `null` without a signal.
+pub fn reading_json(state: &UiState) -> String {
+    let status = match state.status_for(state.deviation_cents) {
+        TuningStatus::Perfect => "perfect",
+        TuningStatus::Close => "close",
+        TuningStatus::Far => "far",
+        TuningStatus::NoSignal => "none",
+    };
+    let note = state.current_note.as_ref().map_or("null".to_string(), |note| format!("\"{}\"", note));
+    format!(
+        "{{\"note\":{},\"octave\":{},\"freq\":{},\"cents\":{},\"target\":\"{}{}\",\"a4\":{:.1},\"preset\":\"{}\",\"status\":\"{}\"}}",
+        note,
+        json_number(state.current_octave.map(|o| o as f32), 0),
+        json_number(state.current_freq, 3),
+        json_number(state.deviation_cents, 2),
+        state.target_note,
+        state.target_octave,
+        state.a4_freq,
+        json_escape(presets::all()[state.preset].name),
+        status
+    )
+}
+
+fn json_number(value: Option<f32>, decimals: usize) -> String {
+    value.map_or("null".to_string(), |v| format!("{:.*}", decimals, v))
+}
+
+fn handle_connection(stream: TcpStream, shared: &Shared) {
+    let _ = stream.set_read_timeout(Some(REQUEST_TIMEOUT));
+    let (Ok(mut writer), Ok(peer)) = (stream.try_clone(), stream.peer_addr()) else {
+        return;
+    };
+    let mut reader = BufReader::new(stream.take(MAX_REQUEST_BYTES));
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let (mut websocket_key, mut host, mut origin) = (None, None, None);
+    for _ in 0..MAX_HEADER_LINES {
+        let mut line = String::new();
+        if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = Some(value.trim().to_string());
+            match name.trim().to_ascii_lowercase().as_str() {
+                "sec-websocket-key" => websocket_key = value,
+                "host" => host = value,
+                "origin" => origin = value,
+                _ => {}
+            }
+        }
+    }
+    if reader.get_ref().limit() == 0 {
+        respond(&mut writer, "431 Request Header Fields Too Large", "text/plain", "Request too large\n");
+        return;
+    }
+
+    // Browsers send `Origin` with cross-site requests, so a page elsewhere
+    // cannot retarget the tuner or read its feed. Tools like curl send none.
+    // A page on a hostname rebound to 127.0.0.1 sends a matching `Origin`,
+    // but its `Host` is not a loopback name.
+    let cross_origin = origin.is_some_and(|origin| {
+        let authority = origin.split_once("://").map_or(origin.as_str(), |(_, authority)| authority);
+        host.as_deref() != Some(authority)
+    });
+    let rebound = shared.address.ip().is_loopback() && host.is_some_and(|host| !loopback_host(&host, shared.address));
+    if cross_origin || rebound {
+        respond(&mut writer, "403 Forbidden", "text/plain", "Cross-origin requests are not allowed\n");
+        return;
+    }
+
+    match (method, path, websocket_key) {
+        ("GET", "/ws", Some(key)) => {
+            let accept = base64(&sha1(format!("{}{}", key, WEBSOCKET_GUID).as_bytes()));
+            let response = format!(
+                "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+                accept
+            );
+            if writer.write_all(response.as_bytes()).is_err() {
+                return;
+            }
+            let _ = writer.set_write_timeout(Some(WRITE_TIMEOUT));
+            let Ok(stream) = writer.try_clone() else {
+                return;
+            };
+            if let Ok(mut clients) = shared.clients.lock() {
+                clients.push((peer, writer));
+            }
+            read_frames(stream, peer, shared);
+        }
+        ("GET", "/", _) => respond(&mut writer, "200 OK", "text/html; charset=utf-8", VIEWER),
+        ("GET", "/api/status", _) => {
+            let status = shared.latest.lock().map(|latest| latest.clone()).unwrap_or_default();
+            respond(&mut writer, "200 OK", "application/json", &status);
+        }
+        ("GET", "/api/presets", _) => {
+            let names: Vec<String> = presets::all().iter().map(|p| format!("\"{}\"", json_escape(p.name))).collect();
+            respond(&mut writer, "200 OK", "application/json", &format!("[{}]", names.join(",")));
+        }
+        ("POST", "/api/target" | "/api/a4" | "/api/preset", _) => match parse_command(path, query) {
+            Ok(command) => {
+                let _ = shared.commands.send(command);
+                respond(&mut writer, "202 Accepted", "application/json", "{\"ok\":true}");
+            }
+            Err(e) => {
+                let body = format!("{{\"ok\":false,\"error\":\"{}\"}}", json_escape(&e));
+                respond(&mut writer, "400 Bad Request", "application/json", &body);
+            }
+        },
+        _ => respond(&mut writer, "404 Not Found", "text/plain", "Not found\n"),
+    }
+}
+
+/// Whether a `Host` header names this loopback listener: `localhost`,
+/// `127.0.0.1`, or `[::1]`, with its port or, on port 80, without one.
+fn loopback_host(host: &str, address: SocketAddr) -> bool {
+    let (name, port) = match host.rsplit_once(':') {
+        Some((name, port)) if !port.contains(']') => (name, port.parse().ok()),
+        _ => (host, Some(80)),
+    };
+    let known = ["localhost", "127.0.0.1", "[::1]"].iter().any(|known| name.eq_ignore_ascii_case(known));
+    known && port == Some(address.port())
+}
+
+/// Reads `POST /api/target?note=E&octave=2`, `/api/a4?hz=442`, or
+/// `/api/preset?name=Violin`.
+fn parse_command(path: &str, query: &str) -> Result<Command, String> {
+    let params: Vec<(String, String)> = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (percent_decode(key), percent_decode(value)))
+        .collect();
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+            .ok_or_else(|| format!("missing `{}`", name))
+    };
+
+    match path {
+        "/api/target" => {
+            let note = param("note")?;
+            let note = NOTES
+                .iter()
+                .find(|n| n.eq_ignore_ascii_case(note))
+                .ok_or_else(|| format!("unknown note `{}`", note))?;
+            let octave = match param("octave") {
+                Ok(octave) => octave.parse::<i32>().ok().filter(|o| (0..=8).contains(o)).ok_or("octave must be 0-8")?,
+                Err(_) => 4,
+            };
+            Ok(Command::Target(note.to_string(), octave))
+        }
+        "/api/a4" => {
+            let hz = param("hz")?
+                .parse::<f32>()
+                .ok()
+                .filter(|hz| (432.0..=450.0).contains(hz))
+                .ok_or("hz must be between 432 and 450")?;
+            Ok(Command::A4(hz))
+        }
+        _ => {
+            let name = param("name")?;
+            presets::all()
+                .iter()
+                .position(|p| p.name.eq_ignore_ascii_case(name))
+                .map(Command::Preset)
+                .ok_or_else(|| format!("unknown preset `{}`", name))
+        }
+    }
+}
+
+/// Decodes `%XX` escapes and `+` as a space.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 2;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+/// Writes each queued message to every client, skipping straight to the newest
+/// when readings arrive faster than they can be sent.
+fn broadcast(queue: &Receiver<String>, clients: &Mutex<Vec<(SocketAddr, TcpStream)>>) {
+    while let Ok(message) = queue.recv() {
+        let message = queue.try_iter().last().unwrap_or(message);
+        let frame = frame(OPCODE_TEXT, message.as_bytes());
+        if let Ok(mut clients) = clients.lock() {
+            clients.retain_mut(|(_, client)| {
+                let sent = client.write_all(&frame).is_ok();
+                if !sent {
+                    // Wakes the client's reader so its thread ends too.
+                    let _ = client.shutdown(Shutdown::Both);
+                }
+                sent
+            });
+        }
+    }
+}
+
+/// Answers a WebSocket client's pings and close until it goes away, then
+/// stops broadcasting to it. Data frames are read and ignored.
+fn read_frames(mut stream: TcpStream, peer: SocketAddr, shared: &Shared) {
+    let _ = stream.set_read_timeout(None);
+    while let Ok((opcode, payload)) = read_frame(&mut stream) {
+        let reply = match opcode {
+            OPCODE_CLOSE => frame(OPCODE_CLOSE, &payload),
+            OPCODE_PING => frame(OPCODE_PONG, &payload),
+            _ => continue,
+        };
+        // Holding the lock keeps the reply from splitting a broadcast frame.
+        if let Ok(_clients) = shared.clients.lock() {
+            let _ = stream.write_all(&reply);
+        }
+        if opcode == OPCODE_CLOSE {
+            break;
+        }
+    }
+    if let Ok(mut clients) = shared.clients.lock() {
+        clients.retain(|(addr, _)| *addr != peer);
+    }
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+/// One client frame: its opcode and unmasked payload. Oversized frames are
+/// an error, which drops the client.
+fn read_frame(stream: &mut impl Read) -> std::io::Result<(u8, Vec<u8>)> {
+    let mut header = [0; 2];
+    stream.read_exact(&mut header)?;
+    let len = match header[1] & 0x7F {
+        126 => {
+            let mut len = [0; 2];
+            stream.read_exact(&mut len)?;
+            u16::from_be_bytes(len) as u64
+        }
+        127 => {
+            let mut len = [0; 8];
+            stream.read_exact(&mut len)?;
+            u64::from_be_bytes(len)
+        }
+        len => len as u64,
+    };
+    if len > MAX_FRAME_BYTES {
+        return Err(std::io::ErrorKind::InvalidData.into());
+    }
+    let mut mask = [0; 4];
+    if header[1] & 0x80 != 0 {
+        stream.read_exact(&mut mask)?;
+    }
+    let mut payload = vec![0; len as usize];
+    stream.read_exact(&mut payload)?;
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+    Ok((header[0] & 0x0F, payload))
+}
+
+/// An unmasked, unfragmented WebSocket frame.
+fn frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        len @ 0..=125 => frame.push(len as u8),
+        len @ 126..=0xFFFF => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// SHA-1, needed only for the `Sec-WebSocket-Accept` handshake header.
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, &word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (state, value) in h.iter_mut().zip([a, b, c, d, e]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (chunk, word) in digest.chunks_mut(4).zip(h) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::new();
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        base64, frame, loopback_host, parse_command, percent_decode, read_frame, sha1, Command, MAX_FRAME_BYTES,
+        OPCODE_CLOSE, OPCODE_PING, OPCODE_TEXT, WEBSOCKET_GUID,
+    };
+    use std::io::Cursor;
+
+    /// `frame` as a client sends it: masked with `mask`.
+    fn masked(opcode: u8, payload: &[u8], mask: [u8; 4]) -> Vec<u8> {
+        let mut frame = frame(opcode, payload);
+        let start = frame.len() - payload.len();
+        frame[1] |= 0x80;
+        for (i, byte) in frame[start..].iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+        frame.splice(start..start, mask);
+        frame
+    }
+
+    #[test]
+    fn handshake_accepts_the_rfc_6455_key() {
+        // The example in section 1.3 of RFC 6455.
+        let key = "dGhlIHNhbXBsZSBub25jZQ==";
+        assert_eq!(base64(&sha1(format!("{}{}", key, WEBSOCKET_GUID).as_bytes())), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+    }
+
+    #[test]
+    fn sha1_and_base64_match_known_vectors() {
+        let hex: String = sha1(b"abc").iter().map(|byte| format!("{:02x}", byte)).collect();
+        assert_eq!(hex, "a9993e364706816aba3e25717850c26c9cd0d89d");
+        let hex: String = sha1(b"").iter().map(|byte| format!("{:02x}", byte)).collect();
+        assert_eq!(hex, "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn frames_round_trip_at_each_length_class() {
+        for len in [0, 125, 126, 1000] {
+            let payload: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let encoded = frame(OPCODE_TEXT, &payload);
+            assert_eq!(encoded[0], 0x81);
+            assert_eq!(encoded[1], if len < 126 { len as u8 } else { 126 });
+            assert_eq!(read_frame(&mut Cursor::new(&encoded)).unwrap(), (OPCODE_TEXT, payload.clone()));
+
+            let client = masked(OPCODE_PING, &payload, [0x37, 0xfa, 0x21, 0x3d]);
+            assert_eq!(read_frame(&mut Cursor::new(&client)).unwrap(), (OPCODE_PING, payload));
+        }
+
+        // The 64-bit length class encodes, but clients may not send one.
+        let payload = vec![7; 70_000];
+        let encoded = frame(OPCODE_TEXT, &payload);
+        assert_eq!(encoded[1], 127);
+        assert_eq!(&encoded[2..10], &70_000u64.to_be_bytes());
+        assert_eq!(encoded.len(), 10 + payload.len());
+        assert!(read_frame(&mut Cursor::new(&encoded)).is_err());
+        let too_long = frame(OPCODE_TEXT, &vec![0; MAX_FRAME_BYTES as usize + 1]);
+        assert!(read_frame(&mut Cursor::new(&too_long)).is_err());
+    }
+
+    #[test]
+    fn client_close_frames_unmask_and_truncated_frames_fail() {
+        let close = masked(OPCODE_CLOSE, &[0x03, 0xe8], [1, 2, 3, 4]);
+        assert_eq!(read_frame(&mut Cursor::new(&close)).unwrap(), (OPCODE_CLOSE, vec![0x03, 0xe8]));
+        assert!(read_frame(&mut Cursor::new(&close[..close.len() - 1])).is_err());
+        assert!(read_frame(&mut Cursor::new(&[0x81])).is_err());
+    }
+
+    #[test]
+    fn percent_decoding() {
+        assert_eq!(percent_decode("C%23"), "C#");
+        assert_eq!(percent_decode("Guitar+%28Drop+D%29"), "Guitar (Drop D)");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+        assert_eq!(percent_decode("%C3%A9"), "é");
+    }
+
+    #[test]
+    fn commands_are_validated() {
+        assert!(matches!(
+            parse_command("/api/target", "note=c%23&octave=3"),
+            Ok(Command::Target(note, 3)) if note == "C#"
+        ));
+        assert!(matches!(parse_command("/api/target", "note=E"), Ok(Command::Target(_, 4))));
+        assert!(parse_command("/api/target", "note=H").is_err());
+        assert!(parse_command("/api/target", "note=E&octave=9").is_err());
+        assert!(parse_command("/api/target", "").is_err());
+        assert!(matches!(parse_command("/api/a4", "hz=442"), Ok(Command::A4(hz)) if hz == 442.0));
+        assert!(parse_command("/api/a4", "hz=500").is_err());
+        assert!(parse_command("/api/a4", "hz=high").is_err());
+        assert!(matches!(parse_command("/api/preset", "name=violin"), Ok(Command::Preset(_))));
+        assert!(parse_command("/api/preset", "name=Kazoo").is_err());
+    }
+
+    #[test]
+    fn only_loopback_names_reach_a_loopback_listener() {
+        let address = "127.0.0.1:8765".parse().unwrap();
+        for host in ["localhost:8765", "127.0.0.1:8765", "[::1]:8765", "LocalHost:8765"] {
+            assert!(loopback_host(host, address), "{}", host);
+        }
+        for host in ["evil.example:8765", "localhost:8766", "localhost", "127.0.0.1.evil.example:8765"] {
+            assert!(!loopback_host(host, address), "{}", host);
+        }
+        assert!(loopback_host("localhost", "127.0.0.1:80".parse().unwrap()));
+    }
+}
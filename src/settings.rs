@@ -0,0 +1,427 @@
+use crate::analysis::Mode;
+use crate::autolock::LOCK_TIMES;
+use crate::bell::BellMode;
+use crate::config::{Config, Value};
+use crate::i18n::{tr, trf};
+use crate::metronome;
+use crate::presets::{self, Preset};
+use crate::timing::COUNTDOWNS;
+use crate::tuner::Register;
+use crate::ui::{Damping, UiState, METER_RANGES};
+use std::time::Duration;
+
+pub const MIN_IN_TUNE_CENTS: f32 = 1.0;
+pub const MAX_CLOSE_CENTS: f32 = 50.0;
+
+const NOTES: [&str; 12] = ["A", "A#", "B", "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#"];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Setting {
+    A4,
+    InTuneCents,
+    CloseCents,
+    TargetNote,
+    TargetOctave,
+    AutoLock,
+    Instrument,
+    Damping,
+    Register,
+    Mode,
+    MeterRange,
+    VerticalMeter,
+    DeadZone,
+    Theme,
+    OutputDevice,
+    Chime,
+    Flash,
+    OffNote,
+    Notify,
+    Bell,
+    Countdown,
+    MetronomeBpm,
+    MetronomePattern,
+}
+
+pub const SETTINGS: [Setting; 23] = [
+    Setting::A4,
+    Setting::InTuneCents,
+    Setting::CloseCents,
+    Setting::TargetNote,
+    Setting::TargetOctave,
+    Setting::AutoLock,
+    Setting::Instrument,
+    Setting::Damping,
+    Setting::Register,
+    Setting::Mode,
+    Setting::MeterRange,
+    Setting::VerticalMeter,
+    Setting::DeadZone,
+    Setting::Theme,
+    Setting::OutputDevice,
+    Setting::Chime,
+    Setting::Flash,
+    Setting::OffNote,
+    Setting::Notify,
+    Setting::Bell,
+    Setting::Countdown,
+    Setting::MetronomeBpm,
+    Setting::MetronomePattern,
+];
+
+impl Setting {
+    pub fn label(self) -> &'static str {
+        match self {
+            Setting::A4 => tr("setting.a4"),
+            Setting::InTuneCents => tr("setting.in_tune"),
+            Setting::CloseCents => tr("setting.close"),
+            Setting::TargetNote => tr("setting.target_note"),
+            Setting::TargetOctave => tr("setting.target_octave"),
+            Setting::AutoLock => tr("setting.auto_lock"),
+            Setting::Instrument => tr("setting.instrument"),
+            Setting::Damping => tr("setting.damping"),
+            Setting::Register => tr("setting.register"),
+            Setting::Mode => tr("setting.mode"),
+            Setting::MeterRange => tr("setting.meter_range"),
+            Setting::VerticalMeter => tr("setting.vertical_meter"),
+            Setting::DeadZone => tr("setting.dead_zone"),
+            Setting::Theme => tr("setting.theme"),
+            Setting::OutputDevice => tr("setting.output"),
+            Setting::Chime => tr("setting.chime"),
+            Setting::Flash => tr("setting.flash"),
+            Setting::OffNote => tr("setting.off_note"),
+            Setting::Notify => tr("setting.notify"),
+            Setting::Bell => tr("setting.bell"),
+            Setting::Countdown => tr("setting.countdown"),
+            Setting::MetronomeBpm => tr("setting.bpm"),
+            Setting::MetronomePattern => tr("setting.pattern"),
+        }
+    }
+
+    pub fn value(self, state: &UiState) -> String {
+        match self {
+            Setting::A4 => format!("{:.1} Hz", state.a4_freq),
+            Setting::InTuneCents => trf("fmt.cents", &[&format!("±{}", state.in_tune_cents)]),
+            Setting::CloseCents => trf("fmt.cents", &[&format!("±{}", state.close_cents)]),
+            Setting::TargetNote => state.target_note.clone(),
+            Setting::TargetOctave => state.target_octave.to_string(),
+            Setting::AutoLock if state.auto_lock == 0.0 => on_off(false).to_string(),
+            Setting::AutoLock => format!("{} s", state.auto_lock),
+            Setting::Instrument => presets::all()[state.preset].name.to_string(),
+            Setting::Damping => state.damping.label().to_string(),
+            Setting::Register => match state.register {
+                Some(register) => register_label(register).to_string(),
+                None => trf("register.auto", &[&register_label(state.register())]),
+            },
+            Setting::Mode => state.mode.label().to_string(),
+            Setting::MeterRange => trf("fmt.cents", &[&format!("±{}", state.meter_range)]),
+            Setting::VerticalMeter => on_off(state.vertical_meter).to_string(),
+            Setting::DeadZone => on_off(state.dead_zone).to_string(),
+            Setting::Theme => state.theme.name.clone(),
+            Setting::OutputDevice => state.output_device.clone().unwrap_or_else(|| tr("common.none").to_string()),
+            Setting::Chime => on_off(state.chime_enabled).to_string(),
+            Setting::Flash => on_off(state.flash_enabled).to_string(),
+            Setting::OffNote => on_off(state.off_note_alarm).to_string(),
+            Setting::Notify => on_off(state.desktop_notify).to_string(),
+            Setting::Bell => state.bell.label().to_string(),
+            Setting::Countdown if state.countdown == 0 => on_off(false).to_string(),
+            Setting::Countdown => format!("{} s", state.countdown),
+            Setting::MetronomeBpm => format!("{} BPM", state.metronome_bpm),
+            Setting::MetronomePattern => metronome::PATTERNS[state.metronome_pattern].0.to_string(),
+        }
+    }
+
+    pub fn adjust(self, state: &mut UiState, delta: i32) {
+        match self {
+            Setting::A4 => {
+                state.a4_freq = (state.a4_freq + 0.1 * delta as f32).clamp(432.0, 450.0);
+            }
+            Setting::InTuneCents => {
+                let max = state.close_cents - 1.0;
+                state.in_tune_cents = (state.in_tune_cents + delta as f32).clamp(MIN_IN_TUNE_CENTS, max);
+            }
+            Setting::CloseCents => {
+                let min = state.in_tune_cents + 1.0;
+                state.close_cents = (state.close_cents + delta as f32).clamp(min, MAX_CLOSE_CENTS);
+            }
+            Setting::TargetNote => {
+                let idx = NOTES.iter().position(|&n| n == state.target_note).unwrap_or(0) as i32;
+                state.target_note = NOTES[(idx + delta).rem_euclid(12) as usize].to_string();
+            }
+            Setting::TargetOctave => {
+                state.target_octave = (state.target_octave + delta).clamp(0, 8);
+            }
+            Setting::AutoLock => {
+                let idx = LOCK_TIMES.iter().position(|&seconds| seconds == state.auto_lock).unwrap_or(0) as i32;
+                state.auto_lock = LOCK_TIMES[(idx + delta).rem_euclid(LOCK_TIMES.len() as i32) as usize];
+                state.lock.unlock();
+            }
+            Setting::Instrument => {
+                let count = presets::all().len() as i32;
+                state.preset = (state.preset as i32 + delta).rem_euclid(count) as usize;
+                state.pipe_string = 0;
+            }
+            Setting::Damping => {
+                state.damping = if delta < 0 { state.damping.previous() } else { state.damping.next() };
+            }
+            Setting::Register => {
+                let modes = [None, Some(Register::Normal), Some(Register::Low), Some(Register::High)];
+                let idx = modes.iter().position(|&mode| mode == state.register).unwrap_or(0) as i32;
+                state.register = modes[(idx + delta).rem_euclid(modes.len() as i32) as usize];
+            }
+            Setting::Mode => state.mode = state.mode.toggle(),
+            Setting::MeterRange => state.cycle_meter_range(delta),
+            Setting::VerticalMeter => state.vertical_meter = !state.vertical_meter,
+            Setting::DeadZone => state.dead_zone = !state.dead_zone,
+            Setting::Theme => {
+                state.theme = if delta < 0 { state.theme.previous() } else { state.theme.next() };
+            }
+            Setting::OutputDevice => {}
+            Setting::Chime => state.chime_enabled = !state.chime_enabled,
+            Setting::Flash => state.flash_enabled = !state.flash_enabled,
+            Setting::OffNote => state.off_note_alarm = !state.off_note_alarm,
+            Setting::Notify => state.desktop_notify = !state.desktop_notify,
+            Setting::Bell => state.bell = state.bell.cycle(delta),
+            Setting::Countdown => {
+                let idx = COUNTDOWNS.iter().position(|&seconds| seconds == state.countdown).unwrap_or(0) as i32;
+                state.countdown = COUNTDOWNS[(idx + delta).rem_euclid(COUNTDOWNS.len() as i32) as usize];
+            }
+            Setting::MetronomeBpm => {
+                state.metronome_bpm = (state.metronome_bpm as i32 + delta)
+                    .clamp(metronome::MIN_BPM as i32, metronome::MAX_BPM as i32) as u32;
+            }
+            Setting::MetronomePattern => {
+                let count = metronome::PATTERNS.len() as i32;
+                state.metronome_pattern = (state.metronome_pattern as i32 + delta).rem_euclid(count) as usize;
+            }
+        }
+    }
+}
+
+fn register_label(register: Register) -> &'static str {
+    match register {
+        Register::Normal => tr("register.normal"),
+        Register::Low => tr("register.low"),
+        Register::High => tr("register.high"),
+    }
+}
+
+pub fn on_off(enabled: bool) -> &'static str {
+    tr(if enabled { "common.on" } else { "common.off" })
+}
+
+/// Instruments from `[presets]`, e.g. `"Banjo (Open G)" = ["G4", "D3", "G3", "B3", "D4"]`.
+/// Flats are accepted and stored as sharps.
+pub fn custom_presets(config: &Config) -> Result<Vec<Preset>, String> {
+    let mut custom = Vec::new();
+    for (name, value) in config.section("presets") {
+        if presets::PRESETS.iter().any(|p| p.name == name) {
+            return Err(format!("presets.{}: a built-in preset already has this name", name));
+        }
+        let Value::Array(items) = value else {
+            return Err(format!("presets.{} must be a list of notes such as \"E2\"", name));
+        };
+        let strings = items
+            .iter()
+            .map(|item| match item {
+                Value::String(text) => parse_note(text).ok_or_else(|| format!("presets.{}: invalid note `{}`", name, text)),
+                _ => Err(format!("presets.{} must be a list of notes such as \"E2\"", name)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if strings.is_empty() {
+            return Err(format!("presets.{} has no strings", name));
+        }
+
+        let name: &'static str = Box::leak(name.clone().into_boxed_str());
+        custom.push(Preset::custom(name, Box::leak(strings.into_boxed_slice())));
+    }
+    Ok(custom)
+}
+
+/// Note sequences from `[sequences]` for the Practice page, e.g.
+/// `"Open strings" = ["E2", "A2", "D3", "G3", "B3", "E4"]`, as MIDI note numbers.
+pub fn custom_sequences(config: &Config) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let mut sequences = Vec::new();
+    for (name, value) in config.section("sequences") {
+        let invalid = || format!("sequences.{} must be a list of notes such as \"E2\"", name);
+        let Value::Array(items) = value else {
+            return Err(invalid());
+        };
+        let notes = items
+            .iter()
+            .map(|item| match item {
+                Value::String(text) => parse_note(text)
+                    .map(|(note, octave)| note_number(note, octave))
+                    .ok_or_else(|| format!("sequences.{}: invalid note `{}`", name, text)),
+                _ => Err(invalid()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if notes.is_empty() {
+            return Err(format!("sequences.{} has no notes", name));
+        }
+        sequences.push((name.clone(), notes));
+    }
+    Ok(sequences)
+}
+
+/// MIDI note number of a note name from `NOTES` and an octave from 0 to 8.
+pub fn note_number(note: &str, octave: i32) -> u8 {
+    let index = NOTES.iter().position(|&n| n == note).unwrap_or(0) as i32;
+    // NOTES starts on A, which is above C within each octave.
+    let from_c = (index + 9) % 12;
+    (12 * (octave + 1) + from_c) as u8
+}
+
+/// A note with octave such as `C#4` or `Bb1`.
+pub fn parse_note(text: &str) -> Option<(&'static str, i32)> {
+    let split = text.find(|c: char| c.is_ascii_digit() || c == '-')?;
+    let (name, octave) = text.split_at(split);
+    let octave = octave.parse::<i32>().ok()?;
+
+    let mut chars = name.chars();
+    let letter = chars.next()?.to_ascii_uppercase().to_string();
+    let natural = NOTES.iter().position(|&n| n == letter)? as i32;
+    let accidental = match chars.as_str() {
+        "" => 0,
+        "#" => 1,
+        "b" => -1,
+        _ => return None,
+    };
+
+    // Counted in semitones from C0, so Cb4 becomes B3 and B#3 becomes C4.
+    let from_c0 = octave * 12 + (natural + 9) % 12 + accidental;
+    let octave = from_c0.div_euclid(12);
+    (0..=8)
+        .contains(&octave)
+        .then(|| (NOTES[(from_c0.rem_euclid(12) as usize + 3) % 12], octave))
+}
+
+pub fn apply_config(config: &Config, state: &mut UiState) {
+    if let Some(seconds) = config.get_f64("ui", "dim_after") {
+        state.dim_after = (seconds > 0.0).then(|| Duration::from_secs_f64(seconds));
+    }
+    if let Some(a4) = config.get_f64("tuner", "a4") {
+        state.a4_freq = (a4 as f32).clamp(432.0, 450.0);
+    }
+    if let Some(close) = config.get_f64("tuner", "close_cents") {
+        state.close_cents = (close as f32).clamp(MIN_IN_TUNE_CENTS + 1.0, MAX_CLOSE_CENTS);
+    }
+    if let Some(in_tune) = config.get_f64("tuner", "in_tune_cents") {
+        state.in_tune_cents = (in_tune as f32).clamp(MIN_IN_TUNE_CENTS, state.close_cents - 1.0);
+    }
+    if let Some(note) = config.get_str("tuner", "target_note") {
+        if NOTES.contains(&note) {
+            state.target_note = note.to_string();
+        }
+    }
+    if let Some(octave) = config.get_f64("tuner", "target_octave") {
+        state.target_octave = (octave as i32).clamp(0, 8);
+    }
+    if let Some(name) = config.get_str("tuner", "instrument") {
+        if let Some(idx) = presets::all().iter().position(|p| p.name == name) {
+            state.preset = idx;
+        }
+    }
+    // Any other value, such as "auto", follows the preset.
+    if let Some(register) = config.get_str("tuner", "register").and_then(Register::from_name) {
+        state.register = Some(register);
+    }
+    if let Some(mode) = config.get_str("tuner", "mode").and_then(Mode::from_name) {
+        state.mode = mode;
+    }
+    if let Some(seconds) = config.get_f64("tuner", "auto_lock") {
+        if LOCK_TIMES.contains(&(seconds as f32)) {
+            state.auto_lock = seconds as f32;
+        }
+    }
+    if let Some(damping) = config.get_str("display", "damping").and_then(Damping::from_name) {
+        state.damping = damping;
+    }
+    if let Some(range) = config.get_f64("display", "meter_range") {
+        if METER_RANGES.contains(&(range as f32)) {
+            state.meter_range = range as f32;
+        }
+    }
+    if let Some(vertical) = config.get_bool("display", "vertical_meter") {
+        state.vertical_meter = vertical;
+    }
+    if let Some(dead_zone) = config.get_bool("display", "dead_zone") {
+        state.dead_zone = dead_zone;
+    }
+    if let Some(continuous) = config.get_bool("display", "continuous") {
+        state.continuous = continuous;
+    }
+    if let Some(waveform) = config.get_bool("display", "waveform") {
+        state.show_waveform = waveform;
+    }
+    if let Some(history) = config.get_bool("display", "history") {
+        state.show_history = history;
+    }
+    if let Some(keyboard) = config.get_bool("display", "keyboard") {
+        state.show_piano = keyboard;
+    }
+    if let Some(chime) = config.get_bool("feedback", "chime") {
+        state.chime_enabled = chime;
+    }
+    if let Some(flash) = config.get_bool("feedback", "flash") {
+        state.flash_enabled = flash;
+    }
+    if let Some(alarm) = config.get_bool("feedback", "off_note_alarm") {
+        state.off_note_alarm = alarm;
+    }
+    if let Some(notify) = config.get_bool("feedback", "notify") {
+        state.desktop_notify = notify;
+    }
+    if let Some(bell) = config.get_str("feedback", "bell").and_then(BellMode::from_name) {
+        state.bell = bell;
+    }
+    if let Some(seconds) = config.get_f64("feedback", "countdown") {
+        if COUNTDOWNS.contains(&(seconds as u64)) {
+            state.countdown = seconds as u64;
+        }
+    }
+    if let Some(bpm) = config.get_f64("metronome", "bpm") {
+        state.metronome_bpm = (bpm as u32).clamp(metronome::MIN_BPM, metronome::MAX_BPM);
+    }
+    if let Some(pattern) = config.get_str("metronome", "pattern") {
+        if let Some(idx) = metronome::PATTERNS.iter().position(|(name, _)| *name == pattern) {
+            state.metronome_pattern = idx;
+        }
+    }
+}
+
+pub fn store_config(config: &mut Config, state: &UiState) {
+    config.set("tuner", "a4", Value::Number((state.a4_freq as f64 * 10.0).round() / 10.0));
+    config.set("tuner", "in_tune_cents", Value::Number(state.in_tune_cents as f64));
+    config.set("tuner", "close_cents", Value::Number(state.close_cents as f64));
+    config.set("tuner", "target_note", Value::String(state.target_note.clone()));
+    config.set("tuner", "target_octave", Value::Number(state.target_octave as f64));
+    config.set("tuner", "instrument", Value::String(presets::all()[state.preset].name.to_string()));
+    let register = state.register.map_or("auto", Register::name);
+    config.set("tuner", "register", Value::String(register.to_string()));
+    config.set("tuner", "mode", Value::String(state.mode.name().to_string()));
+    config.set("tuner", "auto_lock", Value::Number(state.auto_lock as f64));
+    config.set("display", "damping", Value::String(state.damping.name().to_string()));
+    config.set("display", "meter_range", Value::Number(state.meter_range as f64));
+    config.set("display", "vertical_meter", Value::Bool(state.vertical_meter));
+    config.set("display", "dead_zone", Value::Bool(state.dead_zone));
+    config.set("display", "continuous", Value::Bool(state.continuous));
+    config.set("display", "waveform", Value::Bool(state.show_waveform));
+    config.set("display", "history", Value::Bool(state.show_history));
+    config.set("display", "keyboard", Value::Bool(state.show_piano));
+    config.set("theme", "name", Value::String(state.theme.name.clone()));
+    if let Some(device) = &state.output_device {
+        config.set("audio", "output_device", Value::String(device.clone()));
+    }
+    config.set("feedback", "chime", Value::Bool(state.chime_enabled));
+    config.set("feedback", "flash", Value::Bool(state.flash_enabled));
+    config.set("feedback", "off_note_alarm", Value::Bool(state.off_note_alarm));
+    config.set("feedback", "notify", Value::Bool(state.desktop_notify));
+    config.set("feedback", "bell", Value::String(state.bell.name().to_string()));
+    config.set("feedback", "countdown", Value::Number(state.countdown as f64));
+    config.set("metronome", "bpm", Value::Number(state.metronome_bpm as f64));
+    config.set(
+        "metronome",
+        "pattern",
+        Value::String(metronome::PATTERNS[state.metronome_pattern].0.to_string()),
+    );
+}
@@ -0,0 +1,74 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Set when the process is asked to stop, so the main loop can exit the
+/// normal way: closing the streams, restoring the terminal, and saving
+/// settings. Covers SIGINT, SIGTERM, and SIGHUP on Unix and console close,
+/// logoff, and shutdown events on Windows. In raw mode Ctrl-C arrives as a
+/// key press instead and is handled with the other keys.
+pub struct Shutdown {
+    requested: Arc<AtomicBool>,
+}
+
+impl Shutdown {
+    pub fn install() -> Result<Self, String> {
+        let requested = Arc::new(AtomicBool::new(false));
+        register(&requested)?;
+        Ok(Shutdown { requested })
+    }
+
+    pub fn requested(&self) -> bool {
+        self.requested.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(unix)]
+fn register(flag: &Arc<AtomicBool>) -> Result<(), String> {
+    use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+
+    for signal in [SIGINT, SIGTERM, SIGHUP] {
+        signal_hook::flag::register(signal, Arc::clone(flag))
+            .map_err(|e| format!("Failed to handle signal {}: {}", signal, e))?;
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+mod console {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, OnceLock};
+    use std::thread;
+    use std::time::Duration;
+
+    const CTRL_CLOSE_EVENT: u32 = 2;
+    /// Windows ends the process once the handler returns from a close
+    /// event, so it waits this long for the main loop to finish.
+    const CLOSE_GRACE: Duration = Duration::from_secs(2);
+
+    pub static FLAG: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        pub fn SetConsoleCtrlHandler(handler: Option<unsafe extern "system" fn(u32) -> i32>, add: i32) -> i32;
+    }
+
+    pub unsafe extern "system" fn handler(event: u32) -> i32 {
+        if let Some(flag) = FLAG.get() {
+            flag.store(true, Ordering::Relaxed);
+        }
+        if event >= CTRL_CLOSE_EVENT {
+            thread::sleep(CLOSE_GRACE);
+        }
+        1
+    }
+}
+
+#[cfg(windows)]
+fn register(flag: &Arc<AtomicBool>) -> Result<(), String> {
+    let _ = console::FLAG.set(Arc::clone(flag));
+    // SAFETY: the handler only touches a static set above and lives for the whole process.
+    if unsafe { console::SetConsoleCtrlHandler(Some(console::handler), 1) } == 0 {
+        return Err(format!("Failed to handle console events: {}", std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! Synthetic test signals: sines, plucked strings, noise, and vibrato at
+//! known frequencies, for checking and benchmarking the detectors. Every
+//! generator is deterministic, so results repeat exactly between runs.
+
+use std::f32::consts::TAU;
+
+/// `len` samples of a sine at `freq` with peak `amplitude`.
+pub fn sine(freq: f32, amplitude: f32, sample_rate: u32, len: usize) -> Vec<f32> {
+    (0..len)
+        .map(|i| amplitude * (TAU * freq * i as f32 / sample_rate as f32).sin())
+        .collect()
+}
+
+/// A plucked string: the first `harmonics` partials, each at `1/n` of the
+/// fundamental's level, decaying exponentially with time constant `decay`
+/// seconds. Higher partials decay faster, as on a real string.
+pub fn pluck(freq: f32, harmonics: usize, decay: f32, sample_rate: u32, len: usize) -> Vec<f32> {
+    let norm: f32 = (1..=harmonics).map(|n| 1.0 / n as f32).sum();
+    (0..len)
+        .map(|i| {
+            let t = i as f32 / sample_rate as f32;
+            let sum: f32 = (1..=harmonics)
+                .map(|n| {
+                    let n = n as f32;
+                    (TAU * freq * n * t).sin() * (-t * n / decay).exp() / n
+                })
+                .sum();
+            0.8 * sum / norm
+        })
+        .collect()
+}
+
+/// A steady tone with partial `n` at `levels[n - 1]` times `amplitude`,
+/// such as a bowed low string whose fundamental is weaker than its overtones.
+pub fn tone(freq: f32, levels: &[f32], amplitude: f32, sample_rate: u32, len: usize) -> Vec<f32> {
+    (0..len)
+        .map(|i| {
+            let t = i as f32 / sample_rate as f32;
+            let sum: f32 = levels
+                .iter()
+                .enumerate()
+                .map(|(n, level)| level * (TAU * freq * (n + 1) as f32 * t).sin())
+                .sum();
+            amplitude * sum
+        })
+        .collect()
+}
+
+/// Uniform white noise in `-amplitude..amplitude`, from a xorshift generator
+/// seeded with `seed`.
+pub fn noise(amplitude: f32, seed: u32, len: usize) -> Vec<f32> {
+    let mut state = seed.max(1);
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            amplitude * (state as f32 / u32::MAX as f32 * 2.0 - 1.0)
+        })
+        .collect()
+}
+
+/// A sine whose pitch swings `depth` cents either side of `freq`, `rate`
+/// times per second.
+pub fn vibrato(freq: f32, depth: f32, rate: f32, sample_rate: u32, len: usize) -> Vec<f32> {
+    let mut phase = 0.0f32;
+    (0..len)
+        .map(|i| {
+            let t = i as f32 / sample_rate as f32;
+            let cents = depth * (TAU * rate * t).sin();
+            phase = (phase + TAU * freq * 2f32.powf(cents / 1200.0) / sample_rate as f32) % TAU;
+            0.5 * phase.sin()
+        })
+        .collect()
+}
+
+/// Adds `other` to `signal` sample by sample, up to the shorter length.
+pub fn mix(mut signal: Vec<f32>, other: &[f32]) -> Vec<f32> {
+    for (sample, extra) in signal.iter_mut().zip(other) {
+        *sample += extra;
+    }
+    signal
+}
+
+/// Deviation of `detected` from `expected` in cents.
+pub fn cents_off(detected: f32, expected: f32) -> f32 {
+    1200.0 * (detected / expected).log2()
+}
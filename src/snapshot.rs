@@ -0,0 +1,204 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+use ratatui::text::Span;
+
+use crate::config::Config;
+use crate::csvlog::utc_timestamp;
+use crate::theme;
+
+/// Dumps the frame on screen to files for bug reports and for showing a
+/// student their result: plain text, the same with ANSI colours for `cat`
+/// or `less -R`, and optionally an HTML page.
+pub struct Snapshots {
+    dir: PathBuf,
+    html: bool,
+}
+
+impl Snapshots {
+    /// `[snapshot] dir` (the working directory by default) and `html`.
+    pub fn from_config(config: &Config) -> Self {
+        Snapshots {
+            dir: PathBuf::from(config.get_str("snapshot", "dir").unwrap_or(".")),
+            html: config.get_bool("snapshot", "html").unwrap_or(false),
+        }
+    }
+
+    /// Writes `buffer` as `snapshot-<UTC time>.txt` and `.ans`, and `.html`
+    /// if enabled, and returns the path of the text file. `light` gives the
+    /// HTML page a white background for the light theme.
+    pub fn save(&self, buffer: &Buffer, light: bool) -> Result<PathBuf, String> {
+        fs::create_dir_all(&self.dir).map_err(|e| format!("Failed to create {}: {}", self.dir.display(), e))?;
+        let name = format!("snapshot-{}", utc_timestamp(SystemTime::now()).replace(':', ""));
+        let path = self.dir.join(&name);
+        write(&path.with_extension("txt"), &text(buffer))?;
+        write(&path.with_extension("ans"), &ansi(buffer))?;
+        if self.html {
+            write(&path.with_extension("html"), &html(buffer, light))?;
+        }
+        Ok(path.with_extension("txt"))
+    }
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Each row's cells, leaving out those hidden under a wide symbol to their left.
+fn rows(buffer: &Buffer) -> impl Iterator<Item = Vec<&Cell>> {
+    let area = buffer.area;
+    (area.top()..area.bottom()).map(move |y| {
+        let mut cells = Vec::new();
+        let mut hidden = 0;
+        for x in area.left()..area.right() {
+            let cell = buffer.get(x, y);
+            if hidden > 0 {
+                hidden -= 1;
+                continue;
+            }
+            hidden = Span::raw(cell.symbol()).width().saturating_sub(1);
+            cells.push(cell);
+        }
+        cells
+    })
+}
+
+fn text(buffer: &Buffer) -> String {
+    rows(buffer)
+        .map(|cells| {
+            let line: String = cells.iter().map(|cell| cell.symbol()).collect();
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+fn ansi(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for cells in rows(buffer) {
+        let mut current = None;
+        for cell in cells {
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if current != Some(style) {
+                out.push_str(&sgr(cell));
+                current = Some(style);
+            }
+            out.push_str(cell.symbol());
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// The escape sequence that resets the style and then sets the cell's.
+fn sgr(cell: &Cell) -> String {
+    let mut codes = vec!["0".to_string()];
+    for (modifier, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+    ] {
+        if cell.modifier.contains(modifier) {
+            codes.push(code.to_string());
+        }
+    }
+    codes.extend(ansi_color(cell.fg, 30));
+    codes.extend(ansi_color(cell.bg, 40));
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// SGR parameters for `color`, with `base` 30 for foreground or 40 for background.
+fn ansi_color(color: Color, base: u8) -> Option<String> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some(format!("{};2;{};{};{}", base + 8, r, g, b)),
+        Color::Indexed(index) => Some(format!("{};5;{}", base + 8, index)),
+        named => {
+            let index = theme::named_index(named)?;
+            Some(if index < 8 { format!("{}", base + index) } else { format!("{}", base + 60 + index - 8) })
+        }
+    }
+}
+
+/// `color` as a CSS hex colour, or None for the terminal's default.
+fn css_color(color: Color) -> Option<String> {
+    let (r, g, b) = theme::rgb(color)?;
+    Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+}
+
+fn html(buffer: &Buffer, light: bool) -> String {
+    let (background, foreground) = if light { ("#ffffff", "#000000") } else { ("#000000", "#e5e5e5") };
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Guitar Tuner</title>\n</head>\n\
+         <body style=\"background:{0}\">\n<pre style=\"color:{1};background:{0};font-family:monospace;\
+         line-height:1.2\">\n",
+        background, foreground
+    );
+    for cells in rows(buffer) {
+        // Runs of cells with the same style share one span.
+        let mut run: Option<(String, String)> = None;
+        for cell in cells {
+            let style = css_style(cell, background, foreground);
+            let symbol = match cell.symbol() {
+                "<" => "&lt;",
+                ">" => "&gt;",
+                "&" => "&amp;",
+                symbol => symbol,
+            };
+            match &mut run {
+                Some((current, text)) if *current == style => text.push_str(symbol),
+                _ => {
+                    push_run(&mut out, run.take());
+                    run = Some((style, symbol.to_string()));
+                }
+            }
+        }
+        push_run(&mut out, run);
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+fn push_run(out: &mut String, run: Option<(String, String)>) {
+    match run {
+        Some((style, text)) if style.is_empty() => out.push_str(&text),
+        Some((style, text)) => {
+            let _ = write!(out, "<span style=\"{}\">{}</span>", style, text);
+        }
+        None => {}
+    }
+}
+
+fn css_style(cell: &Cell, background: &str, foreground: &str) -> String {
+    let (mut fg, mut bg) = (css_color(cell.fg), css_color(cell.bg));
+    if cell.modifier.contains(Modifier::REVERSED) {
+        (fg, bg) = (
+            Some(bg.unwrap_or_else(|| background.to_string())),
+            Some(fg.unwrap_or_else(|| foreground.to_string())),
+        );
+    }
+    let mut style = String::new();
+    if let Some(fg) = fg {
+        let _ = write!(style, "color:{};", fg);
+    }
+    if let Some(bg) = bg {
+        let _ = write!(style, "background:{};", bg);
+    }
+    for (modifier, css) in [
+        (Modifier::BOLD, "font-weight:bold;"),
+        (Modifier::DIM, "opacity:0.6;"),
+        (Modifier::ITALIC, "font-style:italic;"),
+        (Modifier::UNDERLINED, "text-decoration:underline;"),
+    ] {
+        if cell.modifier.contains(modifier) {
+            style.push_str(css);
+        }
+    }
+    style
+}
@@ -0,0 +1,244 @@
+//! The little of SQLite the session history needs, bound straight to the
+//! system `libsqlite3`. Without the `history` feature nothing is linked and
+//! opening a database reports how to enable it.
+
+use std::path::Path;
+
+/// A value bound to a `?` parameter.
+#[derive(Clone, Copy)]
+#[cfg_attr(not(feature = "history"), allow(dead_code))]
+pub enum Param<'a> {
+    Int(i64),
+    Real(f64),
+    Text(&'a str),
+    Null,
+}
+
+/// A column of a result row.
+#[cfg_attr(not(feature = "history"), allow(dead_code))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Column {
+    Null,
+    Int(i64),
+    Real(f64),
+    Text(String),
+}
+
+impl Column {
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Column::Int(value) => Some(value as f64),
+            Column::Real(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Column::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "history")]
+mod ffi {
+    use std::ffi::{c_char, c_double, c_int, c_void};
+
+    pub enum Sqlite3 {}
+    pub enum Stmt {}
+
+    pub const OK: c_int = 0;
+    pub const ROW: c_int = 100;
+    pub const DONE: c_int = 101;
+    pub const OPEN_READWRITE: c_int = 0x02;
+    pub const OPEN_CREATE: c_int = 0x04;
+    pub const INTEGER: c_int = 1;
+    pub const FLOAT: c_int = 2;
+    pub const TEXT: c_int = 3;
+    /// `SQLITE_TRANSIENT`: SQLite copies bound text before the call returns.
+    pub const TRANSIENT: isize = -1;
+
+    #[link(name = "sqlite3")]
+    extern "C" {
+        pub fn sqlite3_open_v2(name: *const c_char, db: *mut *mut Sqlite3, flags: c_int, vfs: *const c_char) -> c_int;
+        pub fn sqlite3_close(db: *mut Sqlite3) -> c_int;
+        pub fn sqlite3_errmsg(db: *mut Sqlite3) -> *const c_char;
+        pub fn sqlite3_busy_timeout(db: *mut Sqlite3, ms: c_int) -> c_int;
+        pub fn sqlite3_prepare_v2(
+            db: *mut Sqlite3,
+            sql: *const c_char,
+            bytes: c_int,
+            stmt: *mut *mut Stmt,
+            tail: *mut *const c_char,
+        ) -> c_int;
+        pub fn sqlite3_bind_int64(stmt: *mut Stmt, index: c_int, value: i64) -> c_int;
+        pub fn sqlite3_bind_double(stmt: *mut Stmt, index: c_int, value: c_double) -> c_int;
+        pub fn sqlite3_bind_null(stmt: *mut Stmt, index: c_int) -> c_int;
+        pub fn sqlite3_bind_text(stmt: *mut Stmt, index: c_int, text: *const c_char, bytes: c_int, free: isize)
+            -> c_int;
+        pub fn sqlite3_step(stmt: *mut Stmt) -> c_int;
+        pub fn sqlite3_column_count(stmt: *mut Stmt) -> c_int;
+        pub fn sqlite3_column_type(stmt: *mut Stmt, column: c_int) -> c_int;
+        pub fn sqlite3_column_int64(stmt: *mut Stmt, column: c_int) -> i64;
+        pub fn sqlite3_column_double(stmt: *mut Stmt, column: c_int) -> c_double;
+        pub fn sqlite3_column_text(stmt: *mut Stmt, column: c_int) -> *const c_void;
+        pub fn sqlite3_column_bytes(stmt: *mut Stmt, column: c_int) -> c_int;
+        pub fn sqlite3_finalize(stmt: *mut Stmt) -> c_int;
+        pub fn sqlite3_last_insert_rowid(db: *mut Sqlite3) -> i64;
+    }
+}
+
+#[cfg(feature = "history")]
+pub struct Connection {
+    db: *mut ffi::Sqlite3,
+}
+
+#[cfg(feature = "history")]
+impl Connection {
+    /// Opens `path`, creating the file if needed.
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let name = std::ffi::CString::new(path.to_string_lossy().as_bytes())
+            .map_err(|_| format!("Invalid database path: {}", path.display()))?;
+        let mut db = std::ptr::null_mut();
+        let flags = ffi::OPEN_READWRITE | ffi::OPEN_CREATE;
+        // SAFETY: `name` is a valid C string; on failure SQLite still returns a handle to close.
+        let code = unsafe { ffi::sqlite3_open_v2(name.as_ptr(), &mut db, flags, std::ptr::null()) };
+        let connection = Connection { db };
+        if code != ffi::OK {
+            return Err(format!("Failed to open {}: {}", path.display(), connection.error()));
+        }
+        // SAFETY: `db` is open.
+        unsafe { ffi::sqlite3_busy_timeout(db, 1000) };
+        Ok(connection)
+    }
+
+    /// Runs `sql`, one statement, to completion.
+    pub fn execute(&self, sql: &str, params: &[Param]) -> Result<(), String> {
+        self.query(sql, params).map(|_| ())
+    }
+
+    /// Runs `sql`, one statement, and returns every row it produces.
+    pub fn query(&self, sql: &str, params: &[Param]) -> Result<Vec<Vec<Column>>, String> {
+        let statement = Statement::prepare(self, sql)?;
+        for (index, param) in params.iter().enumerate() {
+            statement.bind(index as i32 + 1, param).map_err(|_| self.error())?;
+        }
+        let mut rows = Vec::new();
+        loop {
+            // SAFETY: the statement is prepared and its parameters bound.
+            match unsafe { ffi::sqlite3_step(statement.0) } {
+                ffi::ROW => rows.push(statement.row()),
+                ffi::DONE => return Ok(rows),
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    pub fn last_insert_rowid(&self) -> i64 {
+        // SAFETY: `db` is open.
+        unsafe { ffi::sqlite3_last_insert_rowid(self.db) }
+    }
+
+    fn error(&self) -> String {
+        if self.db.is_null() {
+            return "out of memory".to_string();
+        }
+        // SAFETY: `db` is a handle from `sqlite3_open_v2`, and the message is a C string it owns.
+        unsafe { std::ffi::CStr::from_ptr(ffi::sqlite3_errmsg(self.db)) }.to_string_lossy().into_owned()
+    }
+}
+
+#[cfg(feature = "history")]
+impl Drop for Connection {
+    fn drop(&mut self) {
+        // SAFETY: every statement is finalized when its `Statement` drops, before this.
+        unsafe { ffi::sqlite3_close(self.db) };
+    }
+}
+
+#[cfg(feature = "history")]
+struct Statement(*mut ffi::Stmt);
+
+#[cfg(feature = "history")]
+impl Statement {
+    fn prepare(connection: &Connection, sql: &str) -> Result<Self, String> {
+        let mut stmt = std::ptr::null_mut();
+        // SAFETY: `sql` is passed with its length, so it need not be NUL-terminated.
+        let code = unsafe {
+            ffi::sqlite3_prepare_v2(connection.db, sql.as_ptr().cast(), sql.len() as i32, &mut stmt, std::ptr::null_mut())
+        };
+        if code != ffi::OK {
+            return Err(connection.error());
+        }
+        Ok(Statement(stmt))
+    }
+
+    fn bind(&self, index: i32, param: &Param) -> Result<(), ()> {
+        // SAFETY: the statement is prepared; text is copied by SQLite (`TRANSIENT`).
+        let code = unsafe {
+            match *param {
+                Param::Int(value) => ffi::sqlite3_bind_int64(self.0, index, value),
+                Param::Real(value) => ffi::sqlite3_bind_double(self.0, index, value),
+                Param::Null => ffi::sqlite3_bind_null(self.0, index),
+                Param::Text(text) => {
+                    ffi::sqlite3_bind_text(self.0, index, text.as_ptr().cast(), text.len() as i32, ffi::TRANSIENT)
+                }
+            }
+        };
+        if code == ffi::OK {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    fn row(&self) -> Vec<Column> {
+        // SAFETY: called only after `sqlite3_step` returned a row; text is read with its length
+        // before the next step.
+        unsafe {
+            (0..ffi::sqlite3_column_count(self.0))
+                .map(|column| match ffi::sqlite3_column_type(self.0, column) {
+                    ffi::INTEGER => Column::Int(ffi::sqlite3_column_int64(self.0, column)),
+                    ffi::FLOAT => Column::Real(ffi::sqlite3_column_double(self.0, column)),
+                    ffi::TEXT => {
+                        let text = ffi::sqlite3_column_text(self.0, column).cast::<u8>();
+                        let len = ffi::sqlite3_column_bytes(self.0, column) as usize;
+                        Column::Text(String::from_utf8_lossy(std::slice::from_raw_parts(text, len)).into_owned())
+                    }
+                    _ => Column::Null,
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(feature = "history")]
+impl Drop for Statement {
+    fn drop(&mut self) {
+        // SAFETY: the statement came from `sqlite3_prepare_v2` and is finalized once.
+        unsafe { ffi::sqlite3_finalize(self.0) };
+    }
+}
+
+#[cfg(not(feature = "history"))]
+pub struct Connection;
+
+#[cfg(not(feature = "history"))]
+impl Connection {
+    pub fn open(_path: &Path) -> Result<Self, String> {
+        Err("Session history needs SQLite; rebuild with `cargo build --release --features history`".to_string())
+    }
+
+    pub fn execute(&self, _sql: &str, _params: &[Param]) -> Result<(), String> {
+        unreachable!("no connection can be opened without the history feature")
+    }
+
+    pub fn query(&self, _sql: &str, _params: &[Param]) -> Result<Vec<Vec<Column>>, String> {
+        unreachable!("no connection can be opened without the history feature")
+    }
+
+    pub fn last_insert_rowid(&self) -> i64 {
+        unreachable!("no connection can be opened without the history feature")
+    }
+}
@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+#[derive(Default)]
+pub struct SessionStats {
+    pub notes_measured: u32,
+    signal_frames: u64,
+    in_tune_frames: u64,
+    abs_deviation_sum: f64,
+}
+
+impl SessionStats {
+    pub fn record_frame(&mut self, deviation: f32, in_tune: bool) {
+        self.signal_frames += 1;
+        if in_tune {
+            self.in_tune_frames += 1;
+        }
+        self.abs_deviation_sum += deviation.abs() as f64;
+    }
+
+    pub fn record_note(&mut self) {
+        self.notes_measured += 1;
+    }
+
+    /// Share of analysed frames with a signal that were in tune.
+    pub fn in_tune_percent(&self) -> Option<f32> {
+        (self.signal_frames > 0).then(|| self.in_tune_frames as f32 * 100.0 / self.signal_frames as f32)
+    }
+
+    pub fn average_deviation(&self) -> Option<f32> {
+        (self.signal_frames > 0).then(|| (self.abs_deviation_sum / self.signal_frames as f64) as f32)
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}h {:02}m {:02}s", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
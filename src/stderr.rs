@@ -0,0 +1,102 @@
+//! While the terminal UI is drawn, anything written to stderr would land on
+//! top of it: ALSA and other audio libraries print straight from their own
+//! threads. Stderr is pointed at a pipe instead, and each line read from it
+//! goes to the log file and, with `--verbose`, to the notification area.
+
+use crossbeam_channel::Sender;
+
+use crate::notify::Notice;
+
+#[cfg(unix)]
+mod ffi {
+    use std::ffi::c_int;
+
+    pub const STDERR: c_int = 2;
+
+    extern "C" {
+        pub fn pipe(fds: *mut c_int) -> c_int;
+        pub fn dup(fd: c_int) -> c_int;
+        pub fn dup2(from: c_int, to: c_int) -> c_int;
+        pub fn close(fd: c_int) -> c_int;
+    }
+}
+
+/// Stderr redirected into the log until this is dropped.
+#[cfg(unix)]
+pub struct Capture {
+    saved: std::ffi::c_int,
+}
+
+#[cfg(unix)]
+impl Capture {
+    /// `notices`, when given, also receives every line as a warning.
+    pub fn start(notices: Option<Sender<Notice>>) -> Result<Self, String> {
+        use std::io::{BufRead, BufReader};
+        use std::os::fd::FromRawFd;
+
+        let mut fds = [0; 2];
+        let failed = |what: &str| format!("Failed to {} for stderr: {}", what, std::io::Error::last_os_error());
+        // SAFETY: `fds` has room for the two descriptors `pipe` writes.
+        if unsafe { ffi::pipe(fds.as_mut_ptr()) } != 0 {
+            return Err(failed("create a pipe"));
+        }
+        let [read, write] = fds;
+        // SAFETY: plain descriptor calls on descriptors this process owns; `write` is closed once
+        // stderr refers to it, so dropping the capture later ends the reader with end of file.
+        let saved = unsafe {
+            let saved = ffi::dup(ffi::STDERR);
+            if saved < 0 || ffi::dup2(write, ffi::STDERR) < 0 {
+                ffi::close(read);
+                ffi::close(write);
+                if saved >= 0 {
+                    ffi::close(saved);
+                }
+                return Err(failed("redirect stderr"));
+            }
+            ffi::close(write);
+            saved
+        };
+
+        // SAFETY: `read` is open and owned by nothing else from here on.
+        let reader = BufReader::new(unsafe { std::fs::File::from_raw_fd(read) });
+        std::thread::spawn(move || {
+            for line in reader.lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                let line = line.trim_end();
+                if line.is_empty() {
+                    continue;
+                }
+                log::warn!(target: "stderr", "{}", line);
+                if let Some(notices) = &notices {
+                    let _ = notices.send(Notice::warning(line));
+                }
+            }
+        });
+        Ok(Capture { saved })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Capture {
+    fn drop(&mut self) {
+        // SAFETY: `saved` is the original stderr, duplicated in `start` and closed only here.
+        unsafe {
+            ffi::dup2(self.saved, ffi::STDERR);
+            ffi::close(self.saved);
+        }
+    }
+}
+
+/// Stderr is left alone on other platforms, where audio libraries do not
+/// write to it.
+#[cfg(not(unix))]
+pub struct Capture;
+
+#[cfg(not(unix))]
+impl Capture {
+    pub fn start(_notices: Option<Sender<Notice>>) -> Result<Self, String> {
+        Ok(Capture)
+    }
+}
@@ -0,0 +1,113 @@
+use std::f32::consts::PI;
+
+use crate::metronome::Metronome;
+
+pub const VOICES: usize = 2;
+
+const GAIN: f32 = 0.2;
+const RAMP_SECONDS: f32 = 0.02;
+
+const CHIME_NOTES: [(f32, f32); 2] = [(880.0, 0.0), (1318.5, 0.12)];
+const CHIME_SECONDS: f32 = 0.6;
+const CHIME_GAIN: f32 = 0.25;
+
+#[derive(Clone, Copy)]
+struct Voice {
+    freq: f32,
+    phase: f32,
+    gain: f32,
+    target_gain: f32,
+}
+
+pub struct Synth {
+    sample_rate: f32,
+    ramp_step: f32,
+    voices: [Voice; VOICES],
+    pub metronome: Metronome,
+    chime_pos: Option<usize>,
+}
+
+impl Synth {
+    pub fn new(sample_rate: u32) -> Self {
+        let sample_rate = sample_rate as f32;
+        let voice = Voice {
+            freq: 0.0,
+            phase: 0.0,
+            gain: 0.0,
+            target_gain: 0.0,
+        };
+
+        Synth {
+            sample_rate,
+            ramp_step: GAIN / (RAMP_SECONDS * sample_rate),
+            voices: [voice; VOICES],
+            metronome: Metronome::new(sample_rate as u32),
+            chime_pos: None,
+        }
+    }
+
+    pub fn set_voice(&mut self, index: usize, freq: Option<f32>) {
+        let voice = &mut self.voices[index];
+        match freq {
+            Some(freq) => {
+                voice.freq = freq;
+                voice.target_gain = GAIN;
+            }
+            None => voice.target_gain = 0.0,
+        }
+    }
+
+    pub fn silence(&mut self) {
+        for index in 0..VOICES {
+            self.set_voice(index, None);
+        }
+    }
+
+    pub fn trigger_chime(&mut self) {
+        self.chime_pos = Some(0);
+    }
+
+    fn next_chime_sample(&mut self) -> f32 {
+        let Some(pos) = self.chime_pos else {
+            return 0.0;
+        };
+
+        let t = pos as f32 / self.sample_rate;
+        if t >= CHIME_SECONDS {
+            self.chime_pos = None;
+            return 0.0;
+        }
+        self.chime_pos = Some(pos + 1);
+
+        CHIME_NOTES
+            .iter()
+            .filter(|(_, start)| t >= *start)
+            .map(|(freq, start)| {
+                let local = t - start;
+                let envelope = (-local * 8.0).exp();
+                CHIME_GAIN * envelope * (2.0 * PI * freq * local).sin()
+            })
+            .sum()
+    }
+
+    pub fn next_sample(&mut self) -> f32 {
+        let mut sample = self.metronome.next_sample() + self.next_chime_sample();
+
+        for voice in self.voices.iter_mut() {
+            if voice.gain < voice.target_gain {
+                voice.gain = (voice.gain + self.ramp_step).min(voice.target_gain);
+            } else if voice.gain > voice.target_gain {
+                voice.gain = (voice.gain - self.ramp_step).max(voice.target_gain);
+            }
+
+            if voice.gain <= 0.0 {
+                continue;
+            }
+
+            sample += voice.gain * (2.0 * PI * voice.phase).sin();
+            voice.phase = (voice.phase + voice.freq / self.sample_rate).fract();
+        }
+
+        sample
+    }
+}
@@ -0,0 +1,249 @@
+use ratatui::style::Color;
+use std::str::FromStr;
+
+use crate::config::{Config, Value};
+
+pub const BUILTIN_THEMES: [&str; 5] = ["default", "solarized", "light", "mono", "high-contrast"];
+
+/// The sixteen named terminal colours as xterm draws them, in ANSI order.
+const PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+#[derive(Clone)]
+pub struct Theme {
+    pub name: String,
+    pub perfect: Color,
+    pub close: Color,
+    pub far: Color,
+    pub no_signal: Color,
+    pub accent: Color,
+    pub highlight: Color,
+    pub text: Color,
+    pub muted: Color,
+    pub info: Color,
+    pub target: Color,
+    pub warning: Color,
+    pub high_contrast: bool,
+}
+
+impl Theme {
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default_theme()),
+            "solarized" => Some(Self::solarized()),
+            "light" => Some(Self::light()),
+            "mono" => Some(Self::mono()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        let name = config.get_str("theme", "name").unwrap_or("default");
+        let mut theme = Self::builtin(name).ok_or_else(|| format!("Unknown theme: {}", name))?;
+
+        for (key, value) in config.section("theme") {
+            if key == "name" {
+                continue;
+            }
+            let Value::String(text) = value else {
+                return Err(format!("theme.{} must be a color string", key));
+            };
+            let color = Color::from_str(text).map_err(|_| format!("theme.{}: invalid color `{}`", key, text))?;
+            let slot = theme.slot_mut(key).ok_or_else(|| format!("Unknown theme color: {}", key))?;
+            *slot = color;
+        }
+
+        Ok(theme)
+    }
+
+    pub fn no_color_requested() -> bool {
+        std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+    }
+
+    pub fn next(&self) -> Self {
+        let idx = BUILTIN_THEMES.iter().position(|&n| n == self.name);
+        let next = idx.map_or(0, |i| (i + 1) % BUILTIN_THEMES.len());
+        Self::builtin(BUILTIN_THEMES[next]).unwrap_or_else(Self::default_theme)
+    }
+
+    pub fn previous(&self) -> Self {
+        let idx = BUILTIN_THEMES.iter().position(|&n| n == self.name).unwrap_or(0);
+        let previous = (idx + BUILTIN_THEMES.len() - 1) % BUILTIN_THEMES.len();
+        Self::builtin(BUILTIN_THEMES[previous]).unwrap_or_else(Self::default_theme)
+    }
+
+    fn slot_mut(&mut self, key: &str) -> Option<&mut Color> {
+        match key {
+            "perfect" => Some(&mut self.perfect),
+            "close" => Some(&mut self.close),
+            "far" => Some(&mut self.far),
+            "no_signal" => Some(&mut self.no_signal),
+            "accent" => Some(&mut self.accent),
+            "highlight" => Some(&mut self.highlight),
+            "text" => Some(&mut self.text),
+            "muted" => Some(&mut self.muted),
+            "info" => Some(&mut self.info),
+            "target" => Some(&mut self.target),
+            "warning" => Some(&mut self.warning),
+            _ => None,
+        }
+    }
+
+    fn default_theme() -> Self {
+        Theme {
+            name: "default".to_string(),
+            perfect: Color::Green,
+            close: Color::Yellow,
+            far: Color::Red,
+            no_signal: Color::DarkGray,
+            accent: Color::Cyan,
+            highlight: Color::Yellow,
+            text: Color::White,
+            muted: Color::DarkGray,
+            info: Color::Magenta,
+            target: Color::Blue,
+            warning: Color::Red,
+            high_contrast: false,
+        }
+    }
+
+    fn solarized() -> Self {
+        Theme {
+            name: "solarized".to_string(),
+            perfect: Color::Rgb(133, 153, 0),
+            close: Color::Rgb(181, 137, 0),
+            far: Color::Rgb(220, 50, 47),
+            no_signal: Color::Rgb(88, 110, 117),
+            accent: Color::Rgb(42, 161, 152),
+            highlight: Color::Rgb(203, 75, 22),
+            text: Color::Rgb(147, 161, 161),
+            muted: Color::Rgb(88, 110, 117),
+            info: Color::Rgb(108, 113, 196),
+            target: Color::Rgb(38, 139, 210),
+            warning: Color::Rgb(220, 50, 47),
+            high_contrast: false,
+        }
+    }
+
+    fn light() -> Self {
+        Theme {
+            name: "light".to_string(),
+            perfect: Color::Rgb(0, 128, 0),
+            close: Color::Rgb(170, 100, 0),
+            far: Color::Rgb(190, 0, 0),
+            no_signal: Color::Gray,
+            accent: Color::Rgb(0, 90, 160),
+            highlight: Color::Rgb(150, 60, 0),
+            text: Color::Black,
+            muted: Color::Rgb(110, 110, 110),
+            info: Color::Rgb(130, 0, 130),
+            target: Color::Rgb(0, 0, 160),
+            warning: Color::Rgb(190, 0, 0),
+            high_contrast: false,
+        }
+    }
+
+    fn mono() -> Self {
+        Theme {
+            name: "mono".to_string(),
+            perfect: Color::Reset,
+            close: Color::Reset,
+            far: Color::Reset,
+            no_signal: Color::Reset,
+            accent: Color::Reset,
+            highlight: Color::Reset,
+            text: Color::Reset,
+            muted: Color::Reset,
+            info: Color::Reset,
+            target: Color::Reset,
+            warning: Color::Reset,
+            high_contrast: false,
+        }
+    }
+
+    fn high_contrast() -> Self {
+        Theme {
+            name: "high-contrast".to_string(),
+            perfect: Color::LightGreen,
+            close: Color::LightYellow,
+            far: Color::LightRed,
+            no_signal: Color::White,
+            accent: Color::White,
+            highlight: Color::LightYellow,
+            text: Color::White,
+            muted: Color::Gray,
+            info: Color::LightMagenta,
+            target: Color::LightCyan,
+            warning: Color::LightRed,
+            high_contrast: true,
+        }
+    }
+}
+
+pub fn named_index(color: Color) -> Option<u8> {
+    Some(match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        _ => return None,
+    })
+}
+
+/// `color` in RGB as xterm draws it, or None for the terminal's default.
+pub fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    Some(match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(index) if index < 16 => PALETTE[index as usize],
+        Color::Indexed(index) if index < 232 => {
+            // The 6×6×6 colour cube.
+            let level = |n: u8| if n == 0 { 0 } else { 55 + 40 * n };
+            let n = index - 16;
+            (level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        Color::Indexed(index) => {
+            let gray = 8 + 10 * (index - 232);
+            (gray, gray, gray)
+        }
+        named => PALETTE[named_index(named)? as usize],
+    })
+}
+
+/// The colour a fraction `t` of the way from `from` to `to`, or None if
+/// either is the terminal's default and so has no RGB value to blend.
+pub fn mix(from: Color, to: Color, t: f32) -> Option<Color> {
+    let ((r1, g1, b1), (r2, g2, b2)) = (rgb(from)?, rgb(to)?);
+    let t = t.clamp(0.0, 1.0);
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Some(Color::Rgb(channel(r1, r2), channel(g1, g2), channel(b1, b2)))
+}
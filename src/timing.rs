@@ -0,0 +1,79 @@
+use std::time::{Duration, Instant};
+
+/// Countdown lengths offered on the Settings page, in seconds; 0 turns it off.
+pub const COUNTDOWNS: [u64; 7] = [0, 10, 15, 20, 30, 45, 60];
+
+/// The clock starts at the first reading this close to the target, so time
+/// spent on another string or in silence does not count.
+const NEAR_CENTS: f32 = 100.0;
+
+/// How long one string took from its first reading to holding in tune.
+pub struct StringTime {
+    pub note: String,
+    pub octave: i32,
+    pub elapsed: Duration,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Clock {
+    /// No reading near the target yet.
+    Waiting,
+    Running(Instant),
+    /// Held in tune after this long.
+    Done(Duration),
+}
+
+/// Times each target string from the first reading near it until the chime
+/// hold confirms it in tune, for speed-tuning practice.
+pub struct StringTimer {
+    target: (String, i32),
+    clock: Clock,
+    times: Vec<StringTime>,
+}
+
+impl StringTimer {
+    pub fn new() -> Self {
+        StringTimer {
+            target: (String::new(), 0),
+            clock: Clock::Waiting,
+            times: Vec::new(),
+        }
+    }
+
+    /// Call after each analysis. Choosing another target starts over for it;
+    /// `confirmed_since` is when the pitch entered the in-tune zone, once it
+    /// has held there.
+    pub fn update(&mut self, note: &str, octave: i32, deviation: Option<f32>, confirmed_since: Option<Instant>) {
+        if self.target.0 != note || self.target.1 != octave {
+            self.target = (note.to_string(), octave);
+            self.clock = Clock::Waiting;
+        }
+        match self.clock {
+            Clock::Waiting if deviation.is_some_and(|cents| cents.abs() <= NEAR_CENTS) => {
+                self.clock = Clock::Running(Instant::now());
+            }
+            Clock::Running(start) => {
+                if let Some(since) = confirmed_since {
+                    let elapsed = since.saturating_duration_since(start);
+                    self.clock = Clock::Done(elapsed);
+                    self.times.retain(|time| time.note != note || time.octave != octave);
+                    self.times.push(StringTime {
+                        note: note.to_string(),
+                        octave,
+                        elapsed,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn clock(&self) -> Clock {
+        self.clock
+    }
+
+    /// The latest time for a string, if it has been tuned this session.
+    pub fn time_for(&self, note: &str, octave: i32) -> Option<Duration> {
+        self.times.iter().find(|time| time.note == note && time.octave == octave).map(|time| time.elapsed)
+    }
+}
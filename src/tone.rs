@@ -0,0 +1,46 @@
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::audio::AudioPlayback;
+use crate::config::Config;
+use crate::settings;
+use crate::synth::Synth;
+use crate::tuner::Tuner;
+
+/// Plays a sine at `note` (a name such as `A4` or `Eb2`, or a frequency in Hz)
+/// on the configured output device until Enter is pressed.
+pub fn run(config: &Config, note: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let a4_freq = config.get_f64("tuner", "a4").map_or(440.0, |a4| (a4 as f32).clamp(432.0, 450.0));
+    let (label, freq) = match settings::parse_note(note) {
+        Some((name, octave)) => (format!("{}{}", name, octave), Tuner::note_name_to_frequency(name, octave, a4_freq)),
+        None => match note.parse::<f32>() {
+            Ok(hz) if (20.0..=5000.0).contains(&hz) => (format!("{} Hz", hz), hz),
+            _ => return Err(format!("Not a note or a frequency between 20 and 5000 Hz: {}", note).into()),
+        },
+    };
+
+    let playback = match config.get_str("audio", "output_device") {
+        Some(name) => AudioPlayback::with_device(name)?,
+        None => AudioPlayback::new()?,
+    };
+    let synth = Arc::new(Mutex::new(Synth::new(playback.sample_rate())));
+    let (notice_tx, notice_rx) = crossbeam_channel::unbounded();
+    let _stream = playback.start_playback(Arc::clone(&synth), notice_tx)?;
+    if let Ok(mut synth) = synth.lock() {
+        synth.set_voice(0, Some(freq));
+    }
+
+    println!("Playing {} ({:.2} Hz) on {}. Press Enter to stop.", label, freq, playback.device_name());
+    io::stdin().read_line(&mut String::new())?;
+    // Let the voice ramp down instead of cutting off with a click.
+    if let Ok(mut synth) = synth.lock() {
+        synth.silence();
+    }
+    thread::sleep(Duration::from_millis(50));
+    for notice in notice_rx.try_iter() {
+        eprintln!("{}", notice.text);
+    }
+    Ok(())
+}
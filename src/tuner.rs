@@ -1,61 +1,429 @@
-use rustfft::{FftPlanner, num_complex::Complex};
+use rustfft::{Fft, FftPlanner, num_complex::Complex};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::detector::{PitchDetector, Yin, YIN};
 
 const NOTES: [&str; 12] = ["A", "A#", "B", "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#"];
+pub const PARTIALS: usize = 6;
+const PARTIAL_SEARCH: f32 = 0.03;
+const FFT_PEAK: &str = "FFT peak";
+/// Bins either side of each harmonic counted towards `confidence`.
+const CONFIDENCE_BINS: usize = 2;
+/// Multiples of the detected frequency tried as the fundamental, over a
+/// stretch of spectrum up to `CONSENSUS_SPAN` times the detection.
+const OCTAVE_CANDIDATES: [f32; 5] = [0.25, 1.0 / 3.0, 0.5, 1.0, 2.0];
+const CONSENSUS_SPAN: f32 = 6.0;
+const LOWEST_FUNDAMENTAL: f32 = 25.0;
+/// Peaks below this share of the loudest are left out of the consensus.
+const CONSENSUS_FLOOR: f32 = 0.05;
+/// Cost of an expected partial with no peak, where the loudest peak counts 1.
+const MISSING_PARTIAL: f32 = 0.2;
+/// Window of the low-register tuner, about 0.7 s at 48 kHz, and how many of
+/// its detections are averaged. Detections further than `AVERAGE_RESET_CENTS`
+/// from the average start a new one, so a new note is not blended with the last.
+const LOW_REGISTER_FFT: usize = 32768;
+const LOW_REGISTER_AVERAGE: usize = 8;
+const AVERAGE_RESET_CENTS: f32 = 15.0;
+/// Window of the high-register tuner, about 21 ms at 48 kHz, and the highest
+/// note it reads. It stays below `NYQUIST_SHARE` of the sample rate, past which
+/// the input's anti-aliasing filter lets folded-back images through.
+const HIGH_REGISTER_FFT: usize = 1024;
+const HIGH_MAX_FREQ: f32 = 12_000.0;
+const NYQUIST_SHARE: f32 = 0.4;
+/// Periods of the lowest note that must fit in a window.
+const MIN_PERIODS: f32 = 4.0;
+/// Above the normal range, a peak must be this many times the spectrum's mean
+/// magnitude; aliases and images are faint next to a real whistle or piccolo.
+const HIGH_PEAK_PROMINENCE: f32 = 20.0;
+const MAX_FREQ: f32 = 5000.0;
+const MIN_FREQ: f32 = 20.0;
+/// Window of the normal register, and the range `set_window_size` accepts.
+const NORMAL_FFT: usize = 4096;
+pub const MIN_WINDOW: usize = 1024;
+pub const MAX_WINDOW: usize = 32768;
+
+/// The range of notes a [`Tuner`] is set up for.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Register {
+    /// 4096-sample windows for 20 Hz to 5 kHz.
+    Normal,
+    /// Long, averaged windows for the lowest bass strings.
+    Low,
+    /// Short windows up to 12 kHz for piccolo and tin whistle.
+    High,
+}
+
+impl Register {
+    pub const ALL: [Register; 3] = [Register::Normal, Register::Low, Register::High];
+
+    /// Name in `[tuner] register`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Register::Normal => "normal",
+            Register::Low => "low",
+            Register::High => "high",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|register| register.name() == name)
+    }
+}
 
+#[derive(Clone)]
+pub struct Partial {
+    pub number: usize,
+    pub freq: f32,
+    pub deviation_cents: f32,
+}
+
+/// All buffers are allocated once in `new`, so detection does not allocate.
 pub struct Tuner {
     sample_rate: u32,
     fft_size: usize,
-    planner: FftPlanner<f32>,
+    fft: Arc<dyn Fft<f32>>,
+    /// Hann window coefficients.
+    window: Vec<f32>,
+    buffer: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+    spectrum: Vec<f32>,
+    detector: Option<Box<dyn PitchDetector>>,
+    register: Register,
+    /// Range of frequencies reported.
+    min_freq: f32,
+    max_freq: f32,
+    /// Detections averaged into each reading, and the most recent ones.
+    average: usize,
+    recent: VecDeque<f32>,
+    /// Estimates per second set by [`set_analysis_rate`](Self::set_analysis_rate).
+    rate: Option<f32>,
+    /// Whether normal-register FFT peaks are refined as the other registers' are.
+    refine_peaks: bool,
+    /// Spectral peaks and which of them a candidate's partials explain, for
+    /// `consensus_fundamental`.
+    peaks: Vec<(f32, f32)>,
+    explained: Vec<bool>,
 }
 
 impl Tuner {
     pub fn new(sample_rate: u32) -> Self {
-        let fft_size = 4096;
-        let mut planner = FftPlanner::new();
-        planner.plan_fft_forward(fft_size);
+        Self::for_register(sample_rate, Register::Normal)
+    }
+
+    /// A tuner for `register`.
+    ///
+    /// The low register suits the lowest strings, such as a bass's low B at
+    /// 31 Hz: a window eight times as long, whose detections are averaged over
+    /// about two seconds. A 4096-sample window holds under three periods of B0
+    /// and cannot place it within a cent.
+    ///
+    /// The high register reads up to 12 kHz from windows a quarter as long, so
+    /// fast whistle and piccolo passages keep up, and reads nothing below a few
+    /// hundred hertz.
+    pub fn for_register(sample_rate: u32, register: Register) -> Self {
+        let fft_size = match register {
+            Register::Normal => NORMAL_FFT,
+            Register::Low => LOW_REGISTER_FFT,
+            Register::High => HIGH_REGISTER_FFT,
+        };
+        Self::sized(sample_rate, register, fft_size)
+    }
+
+    fn sized(sample_rate: u32, register: Register, fft_size: usize) -> Self {
+        let rate = sample_rate as f32;
+        let (average, min_freq, max_freq) = match register {
+            // A window shorter than the usual one holds fewer periods of a
+            // low note, so the floor rises with it.
+            Register::Normal => (1, MIN_FREQ * (NORMAL_FFT as f32 / fft_size as f32).max(1.0), MAX_FREQ),
+            Register::Low => (LOW_REGISTER_AVERAGE, MIN_FREQ, MAX_FREQ),
+            Register::High => (1, MIN_PERIODS * rate / fft_size as f32, HIGH_MAX_FREQ.min(NYQUIST_SHARE * rate)),
+        };
+        let fft = FftPlanner::new().plan_fft_forward(fft_size);
+        let scratch = vec![Complex::new(0.0, 0.0); fft.get_inplace_scratch_len()];
 
         Tuner {
             sample_rate,
             fft_size,
-            planner,
+            fft,
+            window: (0..fft_size).map(|i| Self::hann_window(i, fft_size)).collect(),
+            buffer: vec![Complex::new(0.0, 0.0); fft_size],
+            scratch,
+            spectrum: Vec::with_capacity(fft_size / 2),
+            detector: None,
+            register,
+            min_freq,
+            max_freq,
+            average,
+            recent: VecDeque::with_capacity(average),
+            rate: None,
+            refine_peaks: false,
+            peaks: Vec::with_capacity(fft_size / 2),
+            explained: Vec::with_capacity(fft_size / 2),
         }
     }
 
+    /// Analyses windows of `size` samples instead of 4096, a power of two from
+    /// `MIN_WINDOW` to `MAX_WINDOW`. The FFT is planned again and every buffer
+    /// reallocated, so call this before handing the tuner to the analysis
+    /// thread. A longer window places a note more precisely but follows it
+    /// later; a shorter one reacts sooner and cannot read as low, the lowest
+    /// note rising from 20 Hz to 80 Hz at 1024 samples. The low and high
+    /// registers keep their own windows.
+    pub fn set_window_size(&mut self, size: usize) -> Result<(), String> {
+        if !size.is_power_of_two() || !(MIN_WINDOW..=MAX_WINDOW).contains(&size) {
+            let range = format!("{} to {}", MIN_WINDOW, MAX_WINDOW);
+            return Err(format!("Analysis window must be a power of two from {} samples, got {}", range, size));
+        }
+        if self.register != Register::Normal || size == self.fft_size {
+            return Ok(());
+        }
+        let yin = self.algorithm() == YIN;
+        let mut tuner = Self::sized(self.sample_rate, self.register, size);
+        tuner.rate = self.rate;
+        tuner.refine_peaks = self.refine_peaks;
+        tuner.detector = self.detector.take();
+        if yin {
+            tuner.set_algorithm("yin")?;
+        }
+        *self = tuner;
+        Ok(())
+    }
+
+    /// Uses `detector` for the frequency instead of the FFT peak.
+    pub fn set_detector(&mut self, detector: Box<dyn PitchDetector>) {
+        self.detector = Some(detector);
+    }
+
+    /// Selects one of [`BUILTIN`](crate::detector::BUILTIN) by name.
+    pub fn set_algorithm(&mut self, name: &str) -> Result<(), String> {
+        match name {
+            "fft" => self.detector = None,
+            "yin" => {
+                let yin = Yin::with_range(self.sample_rate, self.fft_size, self.min_freq, self.max_freq);
+                self.detector = Some(Box::new(yin));
+            }
+            _ => return Err(format!("Unknown algorithm: {} (expected fft or yin)", name)),
+        }
+        Ok(())
+    }
+
+    /// Display name of the active algorithm.
+    pub fn algorithm(&self) -> &'static str {
+        self.detector.as_ref().map_or(FFT_PEAK, |detector| detector.name())
+    }
+
     fn hann_window(index: usize, size: usize) -> f32 {
         let n = size as f32;
         let i = index as f32;
         0.5 * (1.0 - (2.0 * std::f32::consts::PI * i / (n - 1.0)).cos())
     }
 
+    /// Computes the spectrum of the first window of samples and returns the
+    /// frequency found by the active algorithm, moved to the octave that
+    /// best explains the partials in the spectrum. A low-register tuner
+    /// returns the average of its recent detections of the same note, and a
+    /// high-register one drops peaks above 5 kHz that barely clear the rest
+    /// of the spectrum.
     pub fn detect_frequency(&mut self, samples: &[f32]) -> Option<f32> {
+        let peak = self.fft_peak(samples);
+        let freq = match self.detector.as_mut() {
+            Some(detector) => detector.detect(samples),
+            None => peak,
+        };
         if samples.len() < self.fft_size {
+            return freq;
+        }
+        let freq = freq.map(|freq| self.consensus_fundamental(freq));
+        match self.register {
+            Register::Normal if self.refine_peaks && self.detector.is_none() => {
+                freq.map(|freq| self.hann_peak(freq).unwrap_or(freq))
+            }
+            Register::Normal => freq,
+            Register::Low => {
+                let freq = freq.map(|freq| self.hann_peak(freq).unwrap_or(freq));
+                self.averaged(freq)
+            }
+            Register::High => freq
+                .map(|freq| self.hann_peak(freq).unwrap_or(freq))
+                .filter(|&freq| (self.min_freq..self.max_freq).contains(&freq))
+                .filter(|&freq| freq <= MAX_FREQ || self.stands_out(freq)),
+        }
+    }
+
+    /// Whether the spectrum's peak at `freq` clears `HIGH_PEAK_PROMINENCE`.
+    fn stands_out(&self, freq: f32) -> bool {
+        let bin = (freq / self.bin_hz()).round() as usize;
+        let mean = self.spectrum.iter().sum::<f32>() / self.spectrum.len().max(1) as f32;
+        self.spectrum.get(bin).is_some_and(|&magnitude| magnitude >= mean * HIGH_PEAK_PROMINENCE)
+    }
+
+    /// Frequency of the spectral peak within 3% or a bin of `freq` from its two
+    /// largest bins, exact for a lone sinusoid under a Hann window (Grandke,
+    /// 1983). The three-bin refinement of the FFT peak is off by up to a fifth
+    /// of a bin, fifteen cents at 31 Hz in the low-register window, and YIN
+    /// by over a cent; `None` if no peak is there.
+    fn hann_peak(&self, freq: f32) -> Option<f32> {
+        let bin_hz = self.bin_hz();
+        let center = (freq / bin_hz).round() as usize;
+        let spread = ((freq * PARTIAL_SEARCH / bin_hz).ceil() as usize).max(1);
+        if center < spread + 1 || center + spread + 1 >= self.spectrum.len() {
+            return None;
+        }
+        let bins = center - spread..=center + spread;
+        let bin = bins.max_by(|&a, &b| self.spectrum[a].total_cmp(&self.spectrum[b]))?;
+        let (prev, curr, next) = (self.spectrum[bin - 1], self.spectrum[bin], self.spectrum[bin + 1]);
+        if curr < 0.01 || prev >= curr || next > curr {
             return None;
         }
+        let (side, sign) = if next > prev { (bin + 1, 1.0) } else { (bin - 1, -1.0) };
+        let ratio = self.spectrum[side] / self.spectrum[bin];
+        let offset = (2.0 * ratio - 1.0) / (ratio + 1.0);
+        Some((bin as f32 + sign * offset.clamp(0.0, 1.0)) * bin_hz)
+    }
+
+    fn averaged(&mut self, freq: Option<f32>) -> Option<f32> {
+        let Some(freq) = freq else {
+            self.recent.clear();
+            return None;
+        };
+        let mean = |recent: &VecDeque<f32>| recent.iter().sum::<f32>() / recent.len() as f32;
+        if !self.recent.is_empty() && (1200.0 * (freq / mean(&self.recent)).log2()).abs() > AVERAGE_RESET_CENTS {
+            self.recent.clear();
+        }
+        if self.recent.len() == self.average {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(freq);
+        Some(mean(&self.recent))
+    }
+
+    pub fn register(&self) -> Register {
+        self.register
+    }
+
+    /// New samples between the starts of consecutive windows: half a window,
+    /// or a quarter of the long low-register one so a slow detector keeps up.
+    pub fn hop(&self) -> usize {
+        match (self.rate, self.register) {
+            (Some(rate), _) => {
+                let hop = (self.sample_rate as f32 / rate).round() as usize;
+                hop.clamp(self.fft_size / 16, self.fft_size)
+            }
+            (None, Register::Low) => self.fft_size / 4,
+            (None, _) => self.fft_size / 2,
+        }
+    }
 
-        let windowed: Vec<f32> = samples[..self.fft_size]
-            .iter()
-            .enumerate()
-            .map(|(i, &sample)| {
-                let window = Self::hann_window(i, self.fft_size);
-                sample * window
-            })
-            .collect();
+    /// Produces about `per_second` estimates a second, however fast they are
+    /// drawn, by shortening or lengthening the hop. It is kept between a
+    /// sixteenth of a window and a whole one, so windows still overlap or at
+    /// least touch; zero or less restores the register's hop.
+    pub fn set_analysis_rate(&mut self, per_second: f32) {
+        self.rate = (per_second > 0.0).then_some(per_second);
+    }
+
+    /// Refines the FFT peak of each normal-register window from its two
+    /// strongest bins, as the low and high registers do. The three-bin estimate
+    /// is off by tens of cents in a short window, where a bin spans over 20 Hz;
+    /// YIN is left as it is.
+    pub fn set_refine_peaks(&mut self, on: bool) {
+        self.refine_peaks = on;
+    }
 
-        let mut complex_samples: Vec<Complex<f32>> = windowed
-            .iter()
-            .map(|&s| Complex::new(s, 0.0))
-            .collect();
+    /// Estimates produced per second of audio.
+    pub fn analysis_rate(&self) -> f32 {
+        self.sample_rate as f32 / self.hop() as f32
+    }
 
-        complex_samples.resize(self.fft_size, Complex::new(0.0, 0.0));
+    /// Picks the candidate fundamental whose harmonic series accounts for
+    /// the most peak energy, less a cost for each of its partials that is
+    /// missing. The strongest bin of a low string is often its second or
+    /// third partial, and a detector can land an octave low on a strong
+    /// second partial; a series an octave too high leaves the odd partials
+    /// unexplained, and one an octave too low expects partials that are not
+    /// there.
+    fn consensus_fundamental(&mut self, detected: f32) -> f32 {
+        let bin_hz = self.bin_hz();
+        let limit = (detected * CONSENSUS_SPAN).min(self.spectrum.len().saturating_sub(2) as f32 * bin_hz);
+        let first = ((LOWEST_FUNDAMENTAL * 0.9 / bin_hz) as usize).max(1);
+        let last = (limit / bin_hz) as usize;
+        if first >= last {
+            return detected;
+        }
+        let loudest = self.spectrum[first..=last].iter().fold(0.0f32, |a, &m| a.max(m));
+        if loudest <= 0.0 {
+            return detected;
+        }
+        let mut peaks = std::mem::take(&mut self.peaks);
+        peaks.clear();
+        peaks.extend(
+            (first..=last)
+                .filter(|&bin| {
+                    let magnitude = self.spectrum[bin];
+                    magnitude >= loudest * CONSENSUS_FLOOR
+                        && magnitude > self.spectrum[bin - 1]
+                        && magnitude >= self.spectrum[bin + 1]
+                })
+                .map(|bin| (self.interpolate(bin) * bin_hz, self.spectrum[bin] / loudest)),
+        );
 
-        let fft = self.planner.plan_fft_forward(self.fft_size);
-        fft.process(&mut complex_samples);
+        let mut explained = std::mem::take(&mut self.explained);
+        let mut score = |fundamental: f32| {
+            explained.clear();
+            explained.resize(peaks.len(), false);
+            let mut missing = 0;
+            for number in 1..=(limit / fundamental) as usize {
+                let ideal = fundamental * number as f32;
+                let tolerance = (ideal * 0.015).max(bin_hz);
+                let mut found = false;
+                for (index, &(freq, _)) in peaks.iter().enumerate() {
+                    if (freq - ideal).abs() <= tolerance {
+                        explained[index] = true;
+                        found = true;
+                    }
+                }
+                missing += usize::from(!found);
+            }
+            let balance: f32 =
+                peaks.iter().zip(&explained).map(|(&(_, level), &hit)| if hit { level } else { -level }).sum();
+            balance - MISSING_PARTIAL * missing as f32
+        };
+
+        let mut best = (detected, score(detected));
+        for ratio in OCTAVE_CANDIDATES {
+            let candidate = detected * ratio;
+            if !(LOWEST_FUNDAMENTAL.max(self.min_freq)..self.max_freq).contains(&candidate) || ratio == 1.0 {
+                continue;
+            }
+            let candidate_score = score(candidate);
+            if candidate_score > best.1 {
+                best = (candidate, candidate_score);
+            }
+        }
+        self.peaks = peaks;
+        self.explained = explained;
+        best.0
+    }
+
+    fn fft_peak(&mut self, samples: &[f32]) -> Option<f32> {
+        if samples.len() < self.fft_size {
+            return None;
+        }
+
+        for ((slot, &sample), &window) in self.buffer.iter_mut().zip(samples).zip(&self.window) {
+            *slot = Complex::new(sample * window, 0.0);
+        }
+        self.fft.process_with_scratch(&mut self.buffer, &mut self.scratch);
 
         let mut max_magnitude = 0.0;
         let mut max_bin = 0;
 
-        for (i, complex) in complex_samples.iter().enumerate().take(self.fft_size / 2) {
+        self.spectrum.clear();
+        for (i, complex) in self.buffer.iter().enumerate().take(self.fft_size / 2) {
             let magnitude = complex.norm();
+            self.spectrum.push(magnitude);
             if magnitude > max_magnitude {
                 max_magnitude = magnitude;
                 max_bin = i;
@@ -68,16 +436,17 @@ impl Tuner {
 
         let freq = (max_bin as f32 * self.sample_rate as f32) / self.fft_size as f32;
 
-        let refined_freq = self.refine_frequency(&complex_samples, max_bin, freq);
+        let refined_freq = self.refine_frequency(max_bin, freq);
 
-        if refined_freq > 20.0 && refined_freq < 5000.0 {
+        if refined_freq > self.min_freq && refined_freq < self.max_freq {
             Some(refined_freq)
         } else {
             None
         }
     }
 
-    fn refine_frequency(&self, fft_result: &[Complex<f32>], bin: usize, rough_freq: f32) -> f32 {
+    fn refine_frequency(&self, bin: usize, rough_freq: f32) -> f32 {
+        let fft_result = &self.buffer;
         if bin == 0 || bin >= fft_result.len() / 2 - 1 {
             return rough_freq;
         }
@@ -96,10 +465,109 @@ impl Tuner {
         (bin_center * self.sample_rate as f32) / self.fft_size as f32
     }
 
+    /// Locates the first `PARTIALS` harmonics of `fundamental` in the last
+    /// spectrum. Each is searched within ±3% of its ideal frequency and its
+    /// deviation is measured against n times the refined first partial.
+    pub fn partials(&self, fundamental: f32) -> Vec<Partial> {
+        let mut partials = Vec::new();
+        let Some(base) = self.find_partial(fundamental) else {
+            return partials;
+        };
+
+        for number in 1..=PARTIALS {
+            let ideal = base * number as f32;
+            if let Some(freq) = self.find_partial(ideal) {
+                partials.push(Partial {
+                    number,
+                    freq,
+                    deviation_cents: 1200.0 * (freq / ideal).log2(),
+                });
+            }
+        }
+
+        partials
+    }
+
+    fn find_partial(&self, ideal: f32) -> Option<f32> {
+        let bin_hz = self.bin_hz();
+        let low = ((ideal * (1.0 - PARTIAL_SEARCH)) / bin_hz).floor() as usize;
+        let high = ((ideal * (1.0 + PARTIAL_SEARCH)) / bin_hz).ceil() as usize;
+        if low == 0 || high + 1 >= self.spectrum.len() {
+            return None;
+        }
+
+        let bin = (low..=high).max_by(|&a, &b| self.spectrum[a].total_cmp(&self.spectrum[b]))?;
+        if self.spectrum[bin] < 0.01 {
+            return None;
+        }
+        Some(self.interpolate(bin) * bin_hz)
+    }
+
+    /// Fractional bin of the peak at `bin`, by parabolic interpolation on
+    /// log magnitudes; `bin` must have a neighbour on each side.
+    fn interpolate(&self, bin: usize) -> f32 {
+        let (prev, curr, next) = (
+            self.spectrum[bin - 1].max(1e-9).ln(),
+            self.spectrum[bin].max(1e-9).ln(),
+            self.spectrum[bin + 1].max(1e-9).ln(),
+        );
+        let denom = prev - 2.0 * curr + next;
+        let offset = if denom.abs() > 1e-9 { 0.5 * (prev - next) / denom } else { 0.0 };
+        bin as f32 + offset.clamp(-0.5, 0.5)
+    }
+
+    /// Share of the last spectrum's energy that lies on the harmonics of
+    /// `fundamental`, from 0 (noise) to 1 (a clean harmonic tone).
+    pub fn confidence(&self, fundamental: f32) -> f32 {
+        let total: f32 = self.spectrum.iter().map(|m| m * m).sum();
+        if total <= 0.0 {
+            return 0.0;
+        }
+
+        let bin_hz = self.bin_hz();
+        let mut harmonic = 0.0;
+        let mut last_end = 0;
+        for n in 1..=PARTIALS {
+            let center = (fundamental * n as f32 / bin_hz).round() as usize;
+            let start = center.saturating_sub(CONFIDENCE_BINS).max(last_end);
+            let end = (center + CONFIDENCE_BINS + 1).min(self.spectrum.len());
+            if start >= end {
+                continue;
+            }
+            harmonic += self.spectrum[start..end].iter().map(|m| m * m).sum::<f32>();
+            last_end = end;
+        }
+        (harmonic / total).min(1.0)
+    }
+
+    pub fn spectrum(&self) -> &[f32] {
+        &self.spectrum
+    }
+
+    /// Duration of audio consumed by one detection.
+    pub fn window(&self) -> Duration {
+        let size = self.detector.as_ref().map_or(self.fft_size, |detector| detector.window_size());
+        Duration::from_secs_f32(size as f32 / self.sample_rate as f32)
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn bin_hz(&self) -> f32 {
+        self.sample_rate as f32 / self.fft_size as f32
+    }
+
+    /// Nearest note name, its octave, and the deviation from it in cents.
     pub fn frequency_to_note(&self, frequency: f32, a4_freq: f32) -> (String, i32, f32) {
+        Self::nearest_note(frequency, a4_freq)
+    }
+
+    /// Same as [`frequency_to_note`](Self::frequency_to_note), for callers without a tuner at hand.
+    pub fn nearest_note(frequency: f32, a4_freq: f32) -> (String, i32, f32) {
         let semitones_from_a4 = 12.0 * (frequency / a4_freq).log2();
         let rounded_semitones = semitones_from_a4.round() as i32;
-        let octave = 4 + (rounded_semitones + 9) / 12;
+        let octave = 4 + (rounded_semitones + 9).div_euclid(12);
         let note_index = ((rounded_semitones % 12) + 12) % 12;
         let note_name = NOTES[note_index as usize].to_string();
 
@@ -109,10 +577,259 @@ impl Tuner {
         (note_name, octave, deviation_cents)
     }
 
+    /// Frequency of a note such as `("A", 4)`; octaves start at C as in scientific pitch notation.
     pub fn note_name_to_frequency(note_name: &str, octave: i32, a4_freq: f32) -> f32 {
         let note_index = NOTES.iter().position(|&n| n == note_name).unwrap_or(0) as i32;
-        let semitones_from_a4 = (octave - 4) * 12 + (note_index - 9);
+        let from_c = (note_index + 9) % 12;
+        let semitones_from_a4 = (octave - 4) * 12 + from_c - 9;
         a4_freq * 2.0_f32.powf(semitones_from_a4 as f32 / 12.0)
     }
 }
 
+impl PitchDetector for Tuner {
+    fn name(&self) -> &'static str {
+        self.algorithm()
+    }
+
+    fn window_size(&self) -> usize {
+        self.fft_size
+    }
+
+    fn detect(&mut self, samples: &[f32]) -> Option<f32> {
+        self.detect_frequency(samples)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Register, Tuner};
+    use crate::detector::PitchDetector;
+    use crate::signal::{self, cents_off};
+
+    const RATES: [u32; 2] = [44_100, 48_000];
+    /// Guitar and bass strings, plus notes up the neck.
+    const NOTES: [f32; 10] = [41.20, 55.00, 82.41, 110.00, 146.83, 196.00, 246.94, 329.63, 659.26, 1318.51];
+    const LEN: usize = 4096;
+
+    fn detect(algorithm: &str, sample_rate: u32, samples: &[f32]) -> Option<f32> {
+        let mut tuner = Tuner::new(sample_rate);
+        tuner.set_algorithm(algorithm).unwrap();
+        tuner.detect_frequency(samples)
+    }
+
+    /// Asserts every note is detected within `tolerance` cents.
+    fn assert_accuracy(algorithm: &str, notes: &[f32], tolerance: f32, make: impl Fn(f32, u32) -> Vec<f32>) {
+        for sample_rate in RATES {
+            for &freq in notes {
+                let detected = detect(algorithm, sample_rate, &make(freq, sample_rate))
+                    .unwrap_or_else(|| panic!("{}: nothing detected at {} Hz ({} Hz)", algorithm, freq, sample_rate));
+                let off = cents_off(detected, freq);
+                assert!(
+                    off.abs() <= tolerance,
+                    "{}: {} Hz read as {} Hz ({:+.1} cents) at {} Hz",
+                    algorithm,
+                    freq,
+                    detected,
+                    off,
+                    sample_rate
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn yin_sine() {
+        assert_accuracy("yin", &NOTES, 1.0, |freq, rate| signal::sine(freq, 0.5, rate, LEN));
+    }
+
+    #[test]
+    fn yin_pluck() {
+        assert_accuracy("yin", &NOTES, 1.0, |freq, rate| signal::pluck(freq, 8, 1.5, rate, LEN));
+    }
+
+    #[test]
+    fn yin_noisy_pluck() {
+        assert_accuracy("yin", &NOTES, 10.0, |freq, rate| {
+            signal::mix(signal::pluck(freq, 8, 1.5, rate, LEN), &signal::noise(0.05, 7, LEN))
+        });
+    }
+
+    #[test]
+    fn yin_vibrato_stays_within_depth() {
+        assert_accuracy("yin", &NOTES, 10.0, |freq, rate| signal::vibrato(freq, 10.0, 5.0, rate, LEN));
+    }
+
+    /// The FFT peak is only as fine as its ~11 Hz bins, so it is held to
+    /// naming the right note, above the lowest bass strings.
+    #[test]
+    fn fft_names_the_note() {
+        let notes = &NOTES[2..];
+        assert_accuracy("fft", notes, 50.0, |freq, rate| signal::sine(freq, 0.5, rate, LEN));
+        assert_accuracy("fft", notes, 50.0, |freq, rate| signal::pluck(freq, 8, 1.5, rate, LEN));
+        assert_accuracy("fft", notes, 50.0, |freq, rate| {
+            signal::mix(signal::pluck(freq, 8, 1.5, rate, LEN), &signal::noise(0.05, 7, LEN))
+        });
+    }
+
+    /// Low strings whose second or third partial is the loudest still read
+    /// in the octave of their fundamental.
+    #[test]
+    fn weak_fundamentals_keep_their_octave() {
+        let spectra: [&[f32]; 2] = [&[0.2, 1.0, 0.7, 0.5, 0.4, 0.3], &[0.1, 0.6, 1.0, 0.5, 0.3, 0.2]];
+        for levels in spectra {
+            for algorithm in ["fft", "yin"] {
+                assert_accuracy(algorithm, &NOTES, 50.0, |freq, rate| signal::tone(freq, levels, 0.3, rate, LEN));
+            }
+        }
+    }
+
+    /// A bass's low B and E, read through the low-register window and its
+    /// averaging as the analysis thread steps through them.
+    #[test]
+    fn low_register_reads_low_b_within_a_cent() {
+        let cases = [("fft", 44_100, 30.87), ("fft", 48_000, 30.87), ("fft", 48_000, 41.20), ("yin", 48_000, 30.87)];
+        for (algorithm, sample_rate, freq) in cases {
+            let mut tuner = Tuner::for_register(sample_rate, Register::Low);
+            tuner.set_algorithm(algorithm).unwrap();
+            let len = super::LOW_REGISTER_FFT + super::LOW_REGISTER_AVERAGE * 8192;
+            let levels = [0.3, 1.0, 0.6, 0.4, 0.3];
+            let samples = signal::mix(signal::tone(freq, &levels, 0.3, sample_rate, len), &signal::noise(0.05, 7, len));
+            let mut reading = None;
+            for start in (0..=len - super::LOW_REGISTER_FFT).step_by(8192) {
+                reading = tuner.detect_frequency(&samples[start..]);
+            }
+            let off = cents_off(reading.expect("no reading"), freq);
+            assert!(off.abs() <= 1.0, "{}: {} Hz off by {:+.2} cents at {} Hz", algorithm, freq, off, sample_rate);
+        }
+    }
+
+    /// Tin whistle and piccolo notes and overtones up to 9 kHz, which the
+    /// normal register cuts off at 5 kHz; a faint tone in noise up there is
+    /// taken for an alias and ignored.
+    #[test]
+    fn high_register_reads_above_five_kilohertz() {
+        const HIGH_LEN: usize = super::HIGH_REGISTER_FFT;
+        let levels = [1.0, 0.3, 0.1];
+        let notes = [587.33, 1174.66, 2349.32, 4186.01, 7040.0, 9000.0];
+        for algorithm in ["fft", "yin"] {
+            for sample_rate in RATES {
+                for freq in notes {
+                    let mut tuner = Tuner::for_register(sample_rate, Register::High);
+                    tuner.set_algorithm(algorithm).unwrap();
+                    let tone = signal::tone(freq, &levels, 0.3, sample_rate, HIGH_LEN);
+                    let samples = signal::mix(tone, &signal::noise(0.02, 3, HIGH_LEN));
+                    let off = cents_off(tuner.detect_frequency(&samples).expect("no reading"), freq);
+                    assert!(
+                        off.abs() <= 1.0,
+                        "{}: {} Hz off by {:+.2} cents at {} Hz",
+                        algorithm,
+                        freq,
+                        off,
+                        sample_rate
+                    );
+                }
+                let mut tuner = Tuner::for_register(sample_rate, Register::High);
+                tuner.set_algorithm(algorithm).unwrap();
+                let faint = signal::sine(8000.0, 0.01, sample_rate, HIGH_LEN);
+                let faint = signal::mix(faint, &signal::noise(0.3, 9, HIGH_LEN));
+                assert_eq!(tuner.detect_frequency(&faint), None, "{}", algorithm);
+            }
+        }
+    }
+
+    #[test]
+    fn analysis_rate_sets_the_hop_within_a_window() {
+        let mut tuner = Tuner::new(48_000);
+        assert_eq!(tuner.hop(), 2048);
+        tuner.set_analysis_rate(50.0);
+        assert_eq!(tuner.hop(), 960);
+        assert_eq!(tuner.analysis_rate(), 50.0);
+        tuner.set_analysis_rate(1000.0);
+        assert_eq!(tuner.hop(), 256);
+        tuner.set_analysis_rate(1.0);
+        assert_eq!(tuner.hop(), 4096);
+        tuner.set_analysis_rate(0.0);
+        assert_eq!(tuner.hop(), 2048);
+    }
+
+    /// YIN keeps its accuracy through shorter and longer normal-register
+    /// windows, and the hop follows the window unless a rate is set.
+    #[test]
+    fn window_size_resizes_the_normal_register() {
+        for size in [2048, 16384] {
+            let mut tuner = Tuner::new(48_000);
+            tuner.set_algorithm("yin").unwrap();
+            tuner.set_window_size(size).unwrap();
+            assert_eq!((tuner.window_size(), tuner.hop(), tuner.algorithm()), (size, size / 2, "YIN"));
+            for freq in [82.41, 110.0, 329.63] {
+                let samples = signal::tone(freq, &[1.0, 0.5, 0.3], 0.3, 48_000, size);
+                let off = cents_off(tuner.detect_frequency(&samples).expect("no reading"), freq);
+                assert!(off.abs() <= 1.0, "{} Hz off by {:+.2} cents in {} samples", freq, off, size);
+            }
+        }
+        let mut tuner = Tuner::new(48_000);
+        assert!(tuner.set_window_size(3000).is_err());
+        assert!(tuner.set_window_size(512).is_err());
+        tuner.set_analysis_rate(100.0);
+        tuner.set_window_size(1024).unwrap();
+        assert_eq!(tuner.hop(), 480);
+    }
+
+    /// A 2048-sample window's FFT peak is tens of cents off until refined.
+    #[test]
+    fn refined_peaks_read_short_windows_within_a_cent() {
+        const SHORT: usize = 2048;
+        for freq in [110.0, 146.83, 196.0, 329.63, 659.26] {
+            let mut tuner = Tuner::new(48_000);
+            tuner.set_window_size(SHORT).unwrap();
+            tuner.set_refine_peaks(true);
+            let samples = signal::mix(signal::pluck(freq, 8, 1.5, 48_000, SHORT), &signal::noise(0.05, 7, SHORT));
+            let off = cents_off(tuner.detect_frequency(&samples).expect("no reading"), freq);
+            assert!(off.abs() <= 1.0, "{} Hz off by {:+.2} cents", freq, off);
+        }
+    }
+
+    #[test]
+    fn silence_and_noise_give_no_reading() {
+        for algorithm in ["fft", "yin"] {
+            assert_eq!(detect(algorithm, 44_100, &vec![0.0; LEN]), None, "{}", algorithm);
+        }
+        assert_eq!(detect("yin", 44_100, &signal::noise(0.5, 7, LEN)), None);
+    }
+
+    #[test]
+    fn note_names_convert_to_frequencies() {
+        let hz = |note, octave, a4| Tuner::note_name_to_frequency(note, octave, a4);
+        assert!((hz("A", 4, 440.0) - 440.0).abs() < 1e-3);
+        assert!((hz("C#", 4, 440.0) - 277.183).abs() < 1e-2);
+        assert!((hz("F#", 2, 440.0) - 92.499).abs() < 1e-2);
+        // Octaves start at C, so B3 is just below C4.
+        assert!((hz("B", 3, 440.0) - 246.942).abs() < 1e-2);
+        assert!((hz("C", 4, 440.0) - 261.626).abs() < 1e-2);
+        assert!((hz("A", 4, 432.0) - 432.0).abs() < 1e-3);
+        assert!((hz("E", 2, 442.0) - 82.781).abs() < 1e-2);
+    }
+
+    #[test]
+    fn frequencies_name_their_nearest_note() {
+        let near = |freq, a4| Tuner::nearest_note(freq, a4);
+        let (note, octave, cents) = near(277.183, 440.0);
+        assert_eq!((note.as_str(), octave), ("C#", 4));
+        assert!(cents.abs() < 0.1);
+
+        // Either side of the B-to-C boundary, over 20 cents from each.
+        let (note, octave, cents) = near(250.0, 440.0);
+        assert_eq!((note.as_str(), octave), ("B", 3));
+        assert!((cents - 21.3).abs() < 0.1, "B3 {:+.2}", cents);
+        let (note, octave, cents) = near(258.0, 440.0);
+        assert_eq!((note.as_str(), octave), ("C", 4));
+        assert!((cents + 24.1).abs() < 0.1, "C4 {:+.2}", cents);
+
+        // At A4 = 432 Hz, 440 Hz is A4 sharp rather than in tune.
+        let (note, octave, cents) = near(440.0, 432.0);
+        assert_eq!((note.as_str(), octave), ("A", 4));
+        assert!((cents - 31.8).abs() < 0.1, "A4 {:+.2}", cents);
+        let (note, octave, _) = near(Tuner::note_name_to_frequency("G#", 1, 432.0), 432.0);
+        assert_eq!((note.as_str(), octave), ("G#", 1));
+    }
+}
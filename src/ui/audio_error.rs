@@ -0,0 +1,68 @@
+use super::UiState;
+use crate::i18n::{tr, trf};
+use crate::keymap::Action;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget, Wrap},
+    Frame,
+};
+
+pub(super) fn render(frame: &mut Frame, state: &UiState, area: Rect) {
+    let Some(error) = &state.audio_error else {
+        return;
+    };
+    let theme = &state.theme;
+
+    let mut lines = vec![
+        Line::styled(error.message.as_str(), Style::default().fg(theme.far)),
+        Line::raw(""),
+        Line::styled(tr("audio_error.choose"), Style::default().fg(theme.text)),
+    ];
+    let entries = std::iter::once(tr("audio_error.default")).chain(error.devices.iter().map(String::as_str));
+    for (i, name) in entries.enumerate() {
+        let line = if i == error.cursor {
+            Line::styled(format!(" > {}", name), Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD))
+        } else {
+            Line::styled(format!("   {}", name), Style::default().fg(theme.text))
+        };
+        lines.push(line);
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::from(Span::styled(
+        trf(
+            "audio_error.hint",
+            &[
+                &state.keymap.pair_label(Action::OctaveUp, Action::OctaveDown),
+                &state.keymap.label(Action::Select),
+                &state.keymap.label(Action::Quit),
+            ],
+        ),
+        Style::default().fg(theme.muted),
+    )));
+
+    let width = 64.min(area.width);
+    // The message is the only line long enough to wrap.
+    let wrapped = Span::raw(error.message.as_str()).width() as u16 / width.saturating_sub(2).max(1);
+    let height = (lines.len() as u16 + 2 + wrapped).min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.warning))
+        .title(tr("audio_error.title"))
+        .title_alignment(Alignment::Center);
+
+    Clear.render(popup, frame.buffer_mut());
+    Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(block)
+        .render(popup, frame.buffer_mut());
+}
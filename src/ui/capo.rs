@@ -0,0 +1,81 @@
+use super::UiState;
+use crate::i18n::{tr, trf};
+use crate::keymap::Action;
+use crate::midi;
+use crate::presets;
+use crate::settings::note_number;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+    Frame,
+};
+
+/// Open-position chord shapes: the root as a semitone above C, and whether minor.
+const SHAPES: [(u8, bool); 8] =
+    [(0, false), (9, false), (7, false), (4, false), (2, false), (9, true), (4, true), (2, true)];
+const FRETS: u8 = 12;
+const COLUMN: usize = 5;
+
+fn chord_name(root: u8, minor: bool) -> String {
+    let (name, _) = midi::note_name(root % 12);
+    format!("{}{}", name, if minor { "m" } else { "" })
+}
+
+/// For each capo fret, the chord each open shape sounds as and the pitch of
+/// every open string of the active preset.
+pub(super) fn render(frame: &mut Frame, state: &UiState, area: Rect) {
+    let preset = &presets::all()[state.preset];
+    let strings: Vec<u8> = preset.strings.iter().map(|&(note, octave)| note_number(note, octave)).collect();
+    let header_style = Style::default().fg(state.theme.accent).add_modifier(Modifier::BOLD);
+    let muted = Style::default().fg(state.theme.muted);
+
+    let mut header = vec![Span::styled(format!("{:<w$}", tr("capo.fret"), w = COLUMN + 1), header_style)];
+    for &(root, minor) in &SHAPES {
+        header.push(Span::styled(format!("{:<w$}", chord_name(root, minor), w = COLUMN), header_style));
+    }
+    header.push(Span::styled("│ ", muted));
+    header.push(Span::styled(tr("capo.strings"), header_style));
+
+    let mut lines = vec![Line::from(header)];
+    for fret in 0..=FRETS {
+        let style = if fret == 0 { muted } else { Style::default().fg(state.theme.text) };
+        let fret_style = Style::default().fg(state.theme.highlight);
+        let mut spans = vec![Span::styled(format!("{:<w$}", fret, w = COLUMN + 1), fret_style)];
+        for &(root, minor) in &SHAPES {
+            spans.push(Span::styled(format!("{:<w$}", chord_name(root + fret, minor), w = COLUMN), style));
+        }
+        spans.push(Span::styled("│ ", muted));
+        let open: Vec<String> = strings
+            .iter()
+            .map(|&string| {
+                let (note, octave) = midi::note_name(string.saturating_add(fret).min(127));
+                format!("{}{}", note, octave)
+            })
+            .collect();
+        spans.push(Span::styled(open.join(" "), Style::default().fg(state.theme.info)));
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(tr("capo.hint"), muted));
+
+    let width = 80.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(trf("capo.title", &[&preset.name, &state.keymap.label(Action::Capo)]))
+        .title_alignment(Alignment::Center);
+
+    Clear.render(popup, frame.buffer_mut());
+    Paragraph::new(lines).block(block).render(popup, frame.buffer_mut());
+}
@@ -0,0 +1,111 @@
+use super::UiState;
+use crate::i18n::{tr, trf};
+use crate::keymap::Action;
+use crate::settings::{on_off, SETTINGS};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+    Frame,
+};
+
+/// Actions listed in the help overlay with the translation key of their
+/// description; the keys shown are whatever the keymap binds them to.
+const BINDINGS: [(&[Action], &str); 36] = [
+    (&[Action::NotePrevious, Action::NoteNext], "help.note"),
+    (&[Action::OctaveUp, Action::OctaveDown], "help.octave"),
+    (&[Action::A4Up, Action::A4Down], "help.a4"),
+    (&[Action::NextPage, Action::PreviousPage], "help.pages"),
+    (&[Action::Page(0), Action::Page(1), Action::Page(2), Action::Page(3), Action::Page(4), Action::Page(5)], "help.page_jump"),
+    (&[Action::Beat], "help.beat"),
+    (&[Action::Reference], "help.reference"),
+    (&[Action::ClearReferences], "help.clear_references"),
+    (&[Action::Copy], "help.copy"),
+    (&[Action::Snapshot], "help.snapshot"),
+    (&[Action::Metronome], "help.metronome"),
+    (&[Action::MetronomePattern], "help.pattern"),
+    (&[Action::TempoDown, Action::TempoUp], "help.tempo"),
+    (&[Action::TempoDownCoarse, Action::TempoUpCoarse], "help.tempo_coarse"),
+    (&[Action::Chime], "help.chime"),
+    (&[Action::Flash], "help.flash"),
+    (&[Action::Output], "help.output"),
+    (&[Action::PitchPipe], "help.pipe"),
+    (&[Action::Select], "help.pipe_play"),
+    (&[Action::PipeAll], "help.pipe_all"),
+    (&[Action::Instrument], "help.instrument"),
+    (&[Action::Capo], "help.capo"),
+    (&[Action::Waveform], "help.waveform"),
+    (&[Action::History], "help.history"),
+    (&[Action::Piano], "help.keyboard"),
+    (&[Action::Damping], "help.damping"),
+    (&[Action::MeterRange], "help.range"),
+    (&[Action::VerticalMeter], "help.vertical_meter"),
+    (&[Action::Continuous], "help.continuous"),
+    (&[Action::BigNote], "help.big_note"),
+    (&[Action::Zen], "help.zen"),
+    (&[Action::Theme], "help.theme"),
+    (&[Action::Pause], "help.pause"),
+    (&[Action::Save], "help.save"),
+    (&[Action::Help], "help.help"),
+    (&[Action::Quit], "help.quit"),
+];
+
+pub(super) fn render(frame: &mut Frame, state: &UiState, area: Rect) {
+    let mut lines: Vec<Line> = BINDINGS
+        .iter()
+        .map(|(actions, description)| {
+            let keys: Vec<String> = actions.iter().map(|&action| state.keymap.label(action)).collect();
+            Line::from(vec![
+                Span::styled(format!("{:>13}  ", keys.join("/")), Style::default().fg(state.theme.highlight)),
+                Span::styled(tr(description), Style::default().fg(state.theme.text)),
+            ])
+        })
+        .collect();
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        tr("help.current"),
+        Style::default().fg(state.theme.accent).add_modifier(Modifier::BOLD),
+    ));
+
+    let modes = [
+        (tr("mode.beat"), on_off(state.beat_mode).to_string()),
+        (tr("mode.reference"), match state.references.len() {
+            0 => on_off(false).to_string(),
+            count => trf("mode.reference_notes", &[&count.to_string()]),
+        }),
+        (tr("mode.metronome"), on_off(state.metronome_running).to_string()),
+        (tr("mode.pipe"), on_off(state.pitch_pipe).to_string()),
+        (tr("mode.waveform"), on_off(state.show_waveform).to_string()),
+        (tr("mode.history"), on_off(state.show_history).to_string()),
+        (tr("mode.keyboard"), on_off(state.show_piano).to_string()),
+        (tr("mode.continuous"), on_off(state.continuous).to_string()),
+    ];
+    let settings = SETTINGS.iter().map(|s| (s.label(), s.value(state)));
+    for (label, value) in settings.chain(modes) {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:>26}  ", label), Style::default().fg(state.theme.text)),
+            Span::styled(value, Style::default().fg(state.theme.highlight)),
+        ]));
+    }
+
+    let width = 68.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(trf("help.title", &[&state.keymap.label(Action::Help), &state.keymap.label(Action::Quit)]))
+        .title_alignment(Alignment::Center);
+
+    Clear.render(popup, frame.buffer_mut());
+    Paragraph::new(lines).block(block).render(popup, frame.buffer_mut());
+}
@@ -0,0 +1,166 @@
+use super::{status_appearance, UiState, HISTORY_WINDOW};
+use crate::i18n::{tr, trf};
+use crate::stats;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Row, Table, Widget},
+    Frame,
+};
+
+pub(super) fn render(frame: &mut Frame, state: &UiState, area: Rect) {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Length(3), Constraint::Min(5)])
+        .split(area);
+
+    render_deviation_history(frame, state, vertical[0], state.meter_range);
+    render_session_stats(frame, state, vertical[1]);
+    render_note_log(frame, state, vertical[2]);
+}
+
+fn render_session_stats(frame: &mut Frame, state: &UiState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.info))
+        .title(tr("log.session"))
+        .title_alignment(Alignment::Center);
+
+    let label = Style::default().fg(state.theme.text);
+    let value = Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD);
+    let in_tune = state.stats.in_tune_percent().map_or("---".to_string(), |p| format!("{:.0}%", p));
+    let average = state
+        .stats
+        .average_deviation()
+        .map_or("---".to_string(), |d| trf("fmt.cents", &[&format!("{:.1}", d)]));
+
+    let line = Line::from(vec![
+        Span::styled(format!("{} ", tr("log.time")), label),
+        Span::styled(stats::format_duration(state.display_time().duration_since(state.session_start)), value),
+        Span::raw(" | "),
+        Span::styled(format!("{} ", tr("log.notes")), label),
+        Span::styled(state.stats.notes_measured.to_string(), value),
+        Span::raw(" | "),
+        Span::styled(format!("{} ", tr("log.in_tune")), label),
+        Span::styled(in_tune, value),
+        Span::raw(" | "),
+        Span::styled(format!("{} ", tr("log.average")), label),
+        Span::styled(average, value),
+    ]);
+
+    Paragraph::new(line)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn render_note_log(frame: &mut Frame, state: &UiState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(trf("log.readings", &[&state.note_log.len()]))
+        .title_alignment(Alignment::Center);
+
+    if state.note_log.is_empty() {
+        Paragraph::new(Span::styled(
+            tr("log.empty"),
+            Style::default().fg(state.theme.muted),
+        ))
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+        return;
+    }
+
+    let rows: Vec<Row> = state
+        .note_log
+        .iter()
+        .rev()
+        .take(area.height.saturating_sub(3) as usize)
+        .map(|reading| {
+            let elapsed = reading.at.duration_since(state.session_start);
+            let (color, _, _) = status_appearance(state.status_for(Some(reading.deviation_cents)), &state.theme);
+            Row::new(vec![
+                Span::styled(stats::format_duration(elapsed), Style::default().fg(state.theme.muted)),
+                Span::styled(
+                    format!("{}{}", reading.note, reading.octave),
+                    Style::default().fg(state.theme.accent).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("{:.2} Hz", reading.freq), Style::default().fg(state.theme.highlight)),
+                Span::styled(trf("fmt.cents", &[&format!("{:+.1}", reading.deviation_cents)]), Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(8),
+        Constraint::Length(6),
+        Constraint::Length(12),
+        Constraint::Length(14),
+    ];
+
+    Table::new(rows, widths)
+        .header(
+            Row::new(vec![tr("log.time"), tr("log.note"), tr("log.frequency"), tr("log.deviation")])
+                .style(Style::default().fg(state.theme.text).add_modifier(Modifier::BOLD)),
+        )
+        .block(block)
+        .render(area, frame.buffer_mut());
+}
+
+/// Cents against the target over the last 30 seconds, within ±`range`.
+pub(super) fn render_deviation_history(frame: &mut Frame, state: &UiState, area: Rect, range: f32) {
+    let now = state.display_time();
+    let window = HISTORY_WINDOW.as_secs_f64();
+
+    let points: Vec<(f64, f64)> = state
+        .deviation_history
+        .iter()
+        .map(|&(at, dev)| {
+            let age = now.duration_since(at).as_secs_f64();
+            (-age, dev.clamp(-range, range) as f64)
+        })
+        .collect();
+    let center = [(-window, 0.0), (0.0, 0.0)];
+
+    let datasets = vec![
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(state.theme.muted))
+            .data(&center),
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(state.theme.highlight))
+            .data(&points),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(tr("log.history"))
+        .title_alignment(Alignment::Center);
+
+    Chart::new(datasets)
+        .block(block)
+        .x_axis(
+            Axis::default()
+                .bounds([-window, 0.0])
+                .labels(vec![Span::raw(format!("-{}s", window as u32)), Span::raw(tr("log.now"))])
+                .style(Style::default().fg(state.theme.muted)),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([-range as f64, range as f64])
+                .labels(vec![
+                    Span::raw(format!("-{}", range)),
+                    Span::raw("0"),
+                    Span::raw(format!("+{}", range)),
+                ])
+                .style(Style::default().fg(state.theme.muted)),
+        )
+        .render(area, frame.buffer_mut());
+}
@@ -0,0 +1,978 @@
+mod audio_error;
+mod capo;
+mod help;
+mod log;
+mod notices;
+mod piano;
+mod practice;
+mod settings;
+mod spectrogram;
+mod spectrum;
+mod status;
+mod tuner;
+
+use crate::analysis::Mode;
+use crate::audio;
+use crate::autolock::AutoLock;
+use crate::bell::BellMode;
+use crate::i18n::tr;
+use crate::keymap::Keymap;
+use crate::matching::References;
+use crate::notify::{Notice, Severity};
+use crate::practice::Practice;
+use crate::presets;
+use crate::stats::SessionStats;
+use crate::theme::{self, Theme};
+use crate::timing::StringTimer;
+use crate::midi;
+use crate::tuner::{Partial, Register, Tuner};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Tabs, Widget},
+    Frame,
+};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+const WAVEFORM_LEN: usize = 2048;
+const HISTORY_WINDOW: Duration = Duration::from_secs(30);
+const STABLE_FRAMES: u32 = 3;
+/// Estimates of the current note kept for its spread, and the widest spread
+/// in cents at which a reading is trusted enough to log.
+const STABILITY_WINDOW: usize = 10;
+pub const MAX_STEADY_SPREAD: f32 = 20.0;
+const NOTE_LOG_LEN: usize = 200;
+const PEAK_HOLD: Duration = Duration::from_secs(2);
+const PEAK_DECAY: f32 = 10.0;
+pub const METER_RANGES: [f32; 3] = [10.0, 25.0, 50.0];
+const FLASH_DURATION: Duration = Duration::from_millis(300);
+/// The off-note alarm sounds once this many readings in a row are more than
+/// `OFF_NOTE_CENTS` from the target, so a pluck's attack does not set it off.
+const OFF_NOTE_CENTS: f32 = 100.0;
+const OFF_NOTE_READINGS: usize = 3;
+/// Time constant of the needle's glide towards the latest measurement, and
+/// the shorter one of fast mode.
+const NEEDLE_GLIDE: f32 = 0.06;
+const FAST_NEEDLE_GLIDE: f32 = 0.015;
+const MAX_NOTICES: usize = 4;
+const DEFAULT_DIM_AFTER: Duration = Duration::from_secs(60);
+const HOLD_WINDOW: Duration = Duration::from_secs(15);
+const COMPACT_HEIGHT: u16 = 20;
+const COMPACT_WIDTH: u16 = 60;
+const LARGE_HEIGHT: u16 = 45;
+const LARGE_WIDTH: u16 = 120;
+
+#[derive(Clone)]
+pub struct Reading {
+    pub freq: f32,
+    pub note: String,
+    pub octave: i32,
+    pub deviation_cents: f32,
+    pub at: Instant,
+}
+
+/// What the status bar reports about the input and the analysis loop.
+pub struct AnalysisStatus {
+    pub input_device: String,
+    pub sample_rate: u32,
+    pub algorithm: &'static str,
+    /// Audio covered by one analysis window.
+    pub window: Duration,
+    /// Processing time of the most recent analysis.
+    pub last_time: Duration,
+    /// Share of wall time spent analysing over the last second, in percent.
+    pub cpu_percent: f32,
+    /// Analyses received over the last second.
+    pub rate: u32,
+    /// Audio callbacks dropped because analysis fell behind.
+    pub dropped_frames: u64,
+    /// The register the tuner is set up for.
+    pub register: Register,
+    pub mode: Mode,
+    busy: Duration,
+    count: u32,
+    since: Instant,
+}
+
+impl AnalysisStatus {
+    pub fn new(input_device: String, sample_rate: u32, algorithm: &'static str, window: Duration) -> Self {
+        AnalysisStatus {
+            input_device,
+            sample_rate,
+            algorithm,
+            window,
+            last_time: Duration::ZERO,
+            cpu_percent: 0.0,
+            rate: 0,
+            dropped_frames: 0,
+            register: Register::Normal,
+            mode: Mode::Accurate,
+            busy: Duration::ZERO,
+            count: 0,
+            since: Instant::now(),
+        }
+    }
+
+    pub fn record(&mut self, elapsed: Duration) {
+        self.last_time = elapsed;
+        self.busy += elapsed;
+        self.count += 1;
+
+        let wall = self.since.elapsed();
+        if wall >= Duration::from_secs(1) {
+            self.cpu_percent = self.busy.as_secs_f32() * 100.0 / wall.as_secs_f32();
+            self.rate = (self.count as f32 / wall.as_secs_f32()).round() as u32;
+            self.busy = Duration::ZERO;
+            self.count = 0;
+            self.since = Instant::now();
+        }
+    }
+
+    /// Time from a sound reaching the input to it showing in a reading.
+    pub fn latency(&self) -> Duration {
+        self.window + self.last_time
+    }
+}
+
+/// Why audio input is unavailable, shown in place of the tuner with a list
+/// of devices to try. Entry 0 is the default input device.
+pub struct AudioError {
+    pub message: String,
+    pub devices: Vec<String>,
+    pub cursor: usize,
+}
+
+impl AudioError {
+    pub fn new(message: String) -> Self {
+        AudioError {
+            message,
+            devices: audio::input_device_names(),
+            cursor: 0,
+        }
+    }
+
+    pub fn move_cursor(&mut self, delta: i32) {
+        let count = self.devices.len() as i32 + 1;
+        self.cursor = (self.cursor as i32 + delta).rem_euclid(count) as usize;
+    }
+
+    /// The selected device name, or `None` for the default device.
+    pub fn selected(&self) -> Option<&str> {
+        self.cursor.checked_sub(1).and_then(|i| self.devices.get(i)).map(String::as_str)
+    }
+}
+
+pub struct Guidance {
+    pub text: &'static str,
+    pub arrows: usize,
+    pub flat: bool,
+}
+
+pub struct UiState {
+    pub current_freq: Option<f32>,
+    pub current_note: Option<String>,
+    pub current_octave: Option<i32>,
+    pub deviation_cents: Option<f32>,
+    pub target_note: String,
+    pub target_octave: i32,
+    pub a4_freq: f32,
+    pub in_tune_cents: f32,
+    pub close_cents: f32,
+    pub beat_mode: bool,
+    /// Detected minus target frequency in Hz: beats per second, positive when sharp.
+    pub beat_rate: Option<f32>,
+    /// Captured notes of another instrument to tune to instead of A4.
+    pub references: References,
+    pub output_device: Option<String>,
+    pub metronome_running: bool,
+    pub metronome_bpm: u32,
+    pub metronome_pattern: usize,
+    pub metronome_beat: usize,
+    pub metronome_beats_played: u64,
+    pub metronome_flash_at: Option<Instant>,
+    pub chime_enabled: bool,
+    pub in_tune_since: Option<Instant>,
+    pub in_tune_confirmed: bool,
+    pub flash_enabled: bool,
+    pub flash_at: Option<Instant>,
+    /// Show a desktop notification when the target holds in tune.
+    pub desktop_notify: bool,
+    pub bell: BellMode,
+    pub string_timer: StringTimer,
+    /// Seconds allowed per string for speed-tuning practice; 0 shows no countdown.
+    pub countdown: u64,
+    /// Seconds a note must hold before auto-lock makes it the target; 0 keeps
+    /// the target where it is set.
+    pub auto_lock: f32,
+    pub lock: AutoLock,
+    pub preset: usize,
+    pub pitch_pipe: bool,
+    pub pipe_string: usize,
+    pub pipe_playing: bool,
+    pub pipe_sequence_start: Option<Instant>,
+    pub show_waveform: bool,
+    pub waveform: Vec<f32>,
+    pub show_history: bool,
+    pub show_piano: bool,
+    pub deviation_history: VecDeque<(Instant, f32)>,
+    pub damping: Damping,
+    /// The register chosen on the Settings page; `None` follows the preset.
+    pub register: Option<Register>,
+    pub mode: Mode,
+    pub needle_cents: Option<f32>,
+    pub meter_range: f32,
+    pub vertical_meter: bool,
+    /// Pin the needle to the center while the reading is inside the in-tune zone.
+    pub dead_zone: bool,
+    /// Warn when the pitch is more than a semitone from the target.
+    pub off_note_alarm: bool,
+    /// Readings in a row that far from the target; silence ends the run.
+    off_note_run: usize,
+    /// Cents against the target on a scale two octaves wide, with no note names,
+    /// for fretless instruments, trombone, and theremin.
+    pub continuous: bool,
+    pub big_note: bool,
+    pub zen: bool,
+    pub theme: Theme,
+    pub keymap: Keymap,
+    pub tab: Tab,
+    pub spectrum: Vec<f32>,
+    pub spectrum_bin_hz: f32,
+    /// Recent spectra reduced to frequency bands, oldest first.
+    pub spectrogram: VecDeque<Vec<f32>>,
+    pub partials: Vec<Partial>,
+    pub analysis: AnalysisStatus,
+    pub audio_error: Option<AudioError>,
+    pub settings_cursor: usize,
+    pub settings_message: Option<String>,
+    pub show_help: bool,
+    pub show_capo: bool,
+    /// Set by the snapshot key; the next drawn frame is saved to files.
+    pub snapshot_requested: bool,
+    pub notices: VecDeque<(Notice, Instant)>,
+    /// Last key press, mouse event, or detected pitch.
+    pub last_activity: Instant,
+    /// Inactivity after which the display dims; `None` never dims.
+    pub dim_after: Option<Duration>,
+    pub paused_at: Option<Instant>,
+    pub last_reading: Option<Reading>,
+    pub note_log: VecDeque<Reading>,
+    pub session_start: Instant,
+    pub stats: SessionStats,
+    pub peak_cents: Option<f32>,
+    pub practice: Practice,
+    needle_target: Option<f32>,
+    needle_at: Instant,
+    peak_at: Instant,
+    peak_updated: Instant,
+    stable_note: Option<(String, i32)>,
+    stable_frames: u32,
+    /// Recent frequency estimates of the current note.
+    recent_freqs: VecDeque<f32>,
+    /// Whether the current note has been added to `note_log`.
+    note_logged: bool,
+}
+
+impl UiState {
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            current_freq: None,
+            current_note: None,
+            current_octave: None,
+            deviation_cents: None,
+            target_note: "A".to_string(),
+            target_octave: 4,
+            a4_freq: 440.0,
+            in_tune_cents: 5.0,
+            close_cents: 20.0,
+            beat_mode: false,
+            beat_rate: None,
+            references: References::default(),
+            output_device: None,
+            metronome_running: false,
+            metronome_bpm: 120,
+            metronome_pattern: 3,
+            metronome_beat: 0,
+            metronome_beats_played: 0,
+            metronome_flash_at: None,
+            chime_enabled: false,
+            in_tune_since: None,
+            in_tune_confirmed: false,
+            flash_enabled: false,
+            flash_at: None,
+            desktop_notify: false,
+            bell: BellMode::Off,
+            string_timer: StringTimer::new(),
+            countdown: 0,
+            auto_lock: 0.0,
+            lock: AutoLock::new(),
+            preset: 0,
+            pitch_pipe: false,
+            pipe_string: 0,
+            pipe_playing: false,
+            pipe_sequence_start: None,
+            show_waveform: false,
+            waveform: Vec::with_capacity(WAVEFORM_LEN),
+            show_history: false,
+            show_piano: false,
+            deviation_history: VecDeque::new(),
+            damping: Damping::Smooth,
+            register: None,
+            mode: Mode::Accurate,
+            needle_cents: None,
+            meter_range: 50.0,
+            vertical_meter: false,
+            dead_zone: false,
+            off_note_alarm: true,
+            off_note_run: 0,
+            continuous: false,
+            big_note: false,
+            zen: false,
+            theme,
+            keymap: Keymap::default(),
+            tab: Tab::Tuner,
+            spectrum: Vec::new(),
+            spectrum_bin_hz: 0.0,
+            spectrogram: VecDeque::with_capacity(spectrogram::HISTORY),
+            partials: Vec::new(),
+            analysis: AnalysisStatus::new(String::new(), 0, "", Duration::ZERO),
+            audio_error: None,
+            settings_cursor: 0,
+            settings_message: None,
+            show_help: false,
+            show_capo: false,
+            snapshot_requested: false,
+            notices: VecDeque::new(),
+            last_activity: Instant::now(),
+            dim_after: Some(DEFAULT_DIM_AFTER),
+            paused_at: None,
+            last_reading: None,
+            note_log: VecDeque::new(),
+            session_start: Instant::now(),
+            stats: SessionStats::default(),
+            peak_cents: None,
+            practice: Practice::new(),
+            needle_target: None,
+            needle_at: Instant::now(),
+            peak_at: Instant::now(),
+            peak_updated: Instant::now(),
+            stable_note: None,
+            stable_frames: 0,
+            recent_freqs: VecDeque::new(),
+            note_logged: false,
+        }
+    }
+
+    pub fn push_notice(&mut self, notice: Notice) {
+        let level = match notice.severity {
+            Severity::Info => ::log::Level::Info,
+            Severity::Warning => ::log::Level::Warn,
+            Severity::Error => ::log::Level::Error,
+        };
+        ::log::log!(target: "notice", level, "{}", notice.text);
+        if self.notices.len() == MAX_NOTICES {
+            self.notices.pop_front();
+        }
+        self.notices.push_back((notice, Instant::now()));
+    }
+
+    pub fn expire_notices(&mut self) {
+        self.notices.retain(|(notice, at)| at.elapsed() < notice.severity.lifetime());
+    }
+
+    pub fn is_dimmed(&self) -> bool {
+        self.dim_after.is_some_and(|after| self.last_activity.elapsed() >= after)
+    }
+
+    /// Whether something on screen moves on its own and needs the full frame rate.
+    pub fn is_animating(&self) -> bool {
+        let flashing = self.flash_at.is_some_and(|at| at.elapsed() < FLASH_DURATION);
+        let needle_moving = match (self.needle_cents, self.needle_target) {
+            (Some(drawn), Some(target)) => (drawn - target).abs() > 0.05,
+            (drawn, target) => drawn.is_some() != target.is_some(),
+        };
+        self.metronome_running || self.pipe_sequence_start.is_some() || flashing || needle_moving
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused_at = match self.paused_at {
+            Some(_) => None,
+            None => Some(Instant::now()),
+        };
+    }
+
+    /// The instant the display is drawn at; frozen while paused.
+    pub fn display_time(&self) -> Instant {
+        self.paused_at.unwrap_or_else(Instant::now)
+    }
+
+    /// Remembers the current reading once the same note has been detected
+    /// for a few consecutive analysis frames.
+    pub fn track_reading(&mut self) {
+        let (Some(freq), Some(note), Some(octave), Some(deviation)) = (
+            self.current_freq,
+            self.current_note.as_ref(),
+            self.current_octave,
+            self.deviation_cents,
+        ) else {
+            self.stable_note = None;
+            self.stable_frames = 0;
+            self.recent_freqs.clear();
+            self.off_note_run = 0;
+            return;
+        };
+        self.off_note_run = if deviation.abs() > OFF_NOTE_CENTS { self.off_note_run + 1 } else { 0 };
+
+        let in_tune = matches!(self.status_for(Some(deviation)), TuningStatus::Perfect);
+        self.stats.record_frame(deviation, in_tune);
+
+        let same_note = self.stable_note.as_ref().is_some_and(|(n, o)| n == note && *o == octave);
+        if !same_note {
+            self.recent_freqs.clear();
+            self.note_logged = false;
+        }
+        self.recent_freqs.push_back(freq);
+        if self.recent_freqs.len() > STABILITY_WINDOW {
+            self.recent_freqs.pop_front();
+        }
+        self.stable_frames = if same_note { self.stable_frames + 1 } else { 1 };
+        self.stable_note = Some((note.clone(), octave));
+
+        if self.stable_frames < STABLE_FRAMES || !self.is_steady() {
+            return;
+        }
+
+        let reading = Reading {
+            freq,
+            note: note.clone(),
+            octave,
+            deviation_cents: deviation,
+            at: Instant::now(),
+        };
+        if !self.note_logged {
+            self.note_logged = true;
+            self.stats.record_note();
+            self.note_log.push_back(reading.clone());
+            if self.note_log.len() > NOTE_LOG_LEN {
+                self.note_log.pop_front();
+            }
+        } else if let Some(entry) = self.note_log.back_mut() {
+            entry.freq = reading.freq;
+            entry.deviation_cents = reading.deviation_cents;
+        }
+        self.last_reading = Some(reading);
+    }
+
+    /// The current reading once the same note has been heard for
+    /// `STABLE_FRAMES` and is steady.
+    pub fn stable_reading(&self) -> Option<&Reading> {
+        (self.stable_frames >= STABLE_FRAMES && self.is_steady()).then_some(self.last_reading.as_ref()).flatten()
+    }
+
+    /// Standard deviation in cents of the recent estimates of the current
+    /// note, which tells a wavering note from detector noise.
+    pub fn stability(&self) -> Option<f32> {
+        if self.recent_freqs.len() < STABLE_FRAMES as usize {
+            return None;
+        }
+        let count = self.recent_freqs.len() as f32;
+        let mean = self.recent_freqs.iter().sum::<f32>() / count;
+        let cents: Vec<f32> = self.recent_freqs.iter().map(|freq| 1200.0 * (freq / mean).log2()).collect();
+        Some((cents.iter().map(|c| c * c).sum::<f32>() / count).sqrt())
+    }
+
+    fn is_steady(&self) -> bool {
+        self.stability().is_some_and(|spread| spread <= MAX_STEADY_SPREAD)
+    }
+
+    /// The last stable reading, while there is no live signal to show instead.
+    pub fn held_reading(&self) -> Option<&Reading> {
+        if self.current_freq.is_some() {
+            return None;
+        }
+        self.last_reading
+            .as_ref()
+            .filter(|reading| self.display_time().duration_since(reading.at) <= HOLD_WINDOW)
+    }
+
+    /// Holds the largest recent deviation for `PEAK_HOLD`, then lets it fall
+    /// back towards the live reading at `PEAK_DECAY` cents per second.
+    pub fn update_peak(&mut self, deviation: Option<f32>) {
+        let now = Instant::now();
+        let dt = now.duration_since(self.peak_updated).as_secs_f32();
+        self.peak_updated = now;
+
+        if let Some(dev) = deviation {
+            if self.peak_cents.is_none_or(|peak| dev.abs() >= peak.abs()) {
+                self.peak_cents = Some(dev);
+                self.peak_at = now;
+                return;
+            }
+        }
+
+        let Some(peak) = self.peak_cents else {
+            return;
+        };
+        if now.duration_since(self.peak_at) < PEAK_HOLD {
+            return;
+        }
+        let magnitude = peak.abs() - PEAK_DECAY * dt;
+        self.peak_cents = (magnitude > 0.5).then(|| magnitude.copysign(peak));
+    }
+
+    /// Where `cents` falls on the meter, from -1.0 to 1.0.
+    pub fn meter_position(&self, cents: f32) -> f32 {
+        (cents / self.meter_range).clamp(-1.0, 1.0)
+    }
+
+    pub fn cycle_meter_range(&mut self, delta: i32) {
+        let count = METER_RANGES.len() as i32;
+        let idx = METER_RANGES.iter().position(|&r| r == self.meter_range).unwrap_or(count as usize - 1) as i32;
+        self.meter_range = METER_RANGES[(idx + delta).rem_euclid(count) as usize];
+    }
+
+    /// Feeds a new measurement to the needle; `animate_needle` moves the
+    /// drawn position towards it between measurements.
+    pub fn update_needle(&mut self, deviation: Option<f32>) {
+        self.needle_target = match (deviation, self.needle_target) {
+            (Some(target), _) if self.dead_zone && target.abs() < self.in_tune_cents => Some(0.0),
+            (Some(target), Some(_)) if self.mode == Mode::Fast => Some(target),
+            (Some(target), Some(current)) => {
+                Some(current + (target - current) * self.damping.factor())
+            }
+            (target, _) => target,
+        };
+        if self.damping == Damping::Off {
+            self.needle_cents = self.needle_target;
+        }
+    }
+
+    /// Advances the drawn needle towards the last measurement; called once per rendered frame.
+    pub fn animate_needle(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.needle_at).as_secs_f32();
+        self.needle_at = now;
+        if self.is_dimmed() {
+            return;
+        }
+
+        let glide = if self.mode == Mode::Fast { FAST_NEEDLE_GLIDE } else { NEEDLE_GLIDE };
+        self.needle_cents = match (self.needle_target, self.needle_cents) {
+            (Some(target), Some(current)) if self.paused_at.is_none() => {
+                Some(current + (target - current) * (1.0 - (-elapsed / glide).exp()))
+            }
+            (Some(_), Some(current)) => Some(current),
+            (target, _) => target,
+        };
+    }
+
+    pub fn push_deviation(&mut self, deviation: f32) {
+        let now = Instant::now();
+        self.deviation_history.push_back((now, deviation));
+        while let Some(&(at, _)) = self.deviation_history.front() {
+            if now.duration_since(at) <= HISTORY_WINDOW {
+                break;
+            }
+            self.deviation_history.pop_front();
+        }
+    }
+
+    /// Adds the current spectrum as the newest spectrogram column.
+    pub fn push_spectrogram(&mut self) {
+        if self.spectrogram.len() == spectrogram::HISTORY {
+            self.spectrogram.pop_front();
+        }
+        self.spectrogram.push_back(spectrogram::column(&self.spectrum, self.spectrum_bin_hz));
+    }
+
+    pub fn push_waveform(&mut self, samples: &[f32]) {
+        self.waveform.extend_from_slice(samples);
+        let excess = self.waveform.len().saturating_sub(WAVEFORM_LEN);
+        self.waveform.drain(..excess);
+    }
+
+    /// Which way to turn, for the active instrument, with one arrow per
+    /// 10 cents off (up to five). `None` when in tune or without a signal.
+    pub fn guidance(&self) -> Option<Guidance> {
+        let deviation = self.deviation_cents?;
+        if matches!(self.get_tuning_status(), TuningStatus::Perfect) {
+            return None;
+        }
+
+        let preset = &presets::all()[self.preset];
+        let flat = deviation < 0.0;
+        Some(Guidance {
+            text: tr(if flat { preset.raise } else { preset.lower }),
+            arrows: ((deviation.abs() / 10.0).ceil() as usize).clamp(1, 5),
+            flat,
+        })
+    }
+
+    /// The note heard instead, while the pitch has stayed more than a
+    /// semitone from the target: most likely the wrong string, or a slipped
+    /// peg, rather than a string to be tuned hundreds of cents. An auto-lock
+    /// target only counts once it is locked.
+    pub fn off_note(&self) -> Option<String> {
+        let settled = !self.auto_locking() || self.lock.is_locked();
+        if !self.off_note_alarm || !settled || self.off_note_run < OFF_NOTE_READINGS {
+            return None;
+        }
+        Some(format!("{}{}", self.current_note.as_ref()?, self.current_octave?))
+    }
+
+    pub fn get_tuning_status(&self) -> TuningStatus {
+        self.status_for(self.deviation_cents)
+    }
+
+    /// MIDI note number of the target.
+    pub fn target_number(&self) -> u8 {
+        let freq = Tuner::note_name_to_frequency(&self.target_note, self.target_octave, self.a4_freq);
+        midi::midi_note(freq, self.a4_freq).map_or(69, |(number, _)| number)
+    }
+
+    /// Frequency of the target: on the reference instrument when notes have
+    /// been captured from one, otherwise equal-tempered from A4.
+    pub fn target_freq(&self) -> f32 {
+        match self.references.frequency(self.target_number()) {
+            Some((_, freq)) => freq,
+            None => Tuner::note_name_to_frequency(&self.target_note, self.target_octave, self.a4_freq),
+        }
+    }
+
+    /// The register the tuner should be set up for: as chosen, or the preset's.
+    pub fn register(&self) -> Register {
+        self.register.unwrap_or_else(|| presets::all()[self.preset].register())
+    }
+
+    /// Whether auto-lock picks the target: only when it is on and the preset
+    /// has no strings, so a preset's or the Practice page's targets stay put.
+    pub fn auto_locking(&self) -> bool {
+        self.auto_lock > 0.0 && presets::all()[self.preset].strings.is_empty() && self.tab != Tab::Practice
+    }
+
+    pub fn set_target_number(&mut self, number: u8) {
+        let (note, octave) = midi::note_name(number);
+        self.target_note = note.to_string();
+        self.target_octave = octave.clamp(0, 8);
+    }
+
+    pub fn status_for(&self, deviation: Option<f32>) -> TuningStatus {
+        if let Some(deviation) = deviation {
+            if deviation.abs() < self.in_tune_cents {
+                TuningStatus::Perfect
+            } else if deviation.abs() < self.close_cents {
+                TuningStatus::Close
+            } else {
+                TuningStatus::Far
+            }
+        } else {
+            TuningStatus::NoSignal
+        }
+    }
+
+    /// The needle colour for `cents`: the in-tune colour inside the in-tune
+    /// zone, blending to the close colour at the edge of the close zone and on
+    /// to the far colour at the end of the meter. Themes without RGB colours,
+    /// such as mono, keep the plain status colour.
+    pub fn deviation_color(&self, cents: f32) -> Color {
+        let theme = &self.theme;
+        let cents = cents.abs();
+        let far_end = self.meter_range.max(self.close_cents + 1.0);
+        let blended = if cents <= self.in_tune_cents {
+            Some(theme.perfect)
+        } else if cents <= self.close_cents {
+            let t = (cents - self.in_tune_cents) / (self.close_cents - self.in_tune_cents);
+            theme::mix(theme.perfect, theme.close, t)
+        } else {
+            theme::mix(theme.close, theme.far, (cents - self.close_cents) / (far_end - self.close_cents))
+        };
+        blended.unwrap_or_else(|| status_appearance(self.status_for(Some(cents)), theme).0)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Damping {
+    Off,
+    Fast,
+    Smooth,
+    Strobe,
+}
+
+impl Damping {
+    pub fn next(self) -> Self {
+        match self {
+            Damping::Off => Damping::Fast,
+            Damping::Fast => Damping::Smooth,
+            Damping::Smooth => Damping::Strobe,
+            Damping::Strobe => Damping::Off,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            Damping::Off => Damping::Strobe,
+            Damping::Fast => Damping::Off,
+            Damping::Smooth => Damping::Fast,
+            Damping::Strobe => Damping::Smooth,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Damping::Off, Damping::Fast, Damping::Smooth, Damping::Strobe]
+            .into_iter()
+            .find(|d| d.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Damping::Off => "raw",
+            Damping::Fast => "fast",
+            Damping::Smooth => "smooth",
+            Damping::Strobe => "strobe-like",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Damping::Off => tr("damping.off"),
+            Damping::Fast => tr("damping.fast"),
+            Damping::Smooth => tr("damping.smooth"),
+            Damping::Strobe => tr("damping.strobe"),
+        }
+    }
+
+    fn factor(self) -> f32 {
+        match self {
+            Damping::Off => 1.0,
+            Damping::Fast => 0.5,
+            Damping::Smooth => 0.2,
+            Damping::Strobe => 0.05,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Hit {
+    Tab(Tab),
+    TargetNote,
+    TargetOctave,
+    A4,
+    PipeString(usize),
+    Setting(usize),
+}
+
+pub type Hits = Vec<(Rect, Hit)>;
+
+pub fn hit_at(hits: &Hits, column: u16, row: u16) -> Option<Hit> {
+    hits.iter()
+        .find(|(rect, _)| column >= rect.x && column < rect.right() && row >= rect.y && row < rect.bottom())
+        .map(|&(_, hit)| hit)
+}
+
+fn centered_span_hits(row: Rect, spans: &[Span], targets: &[(usize, Hit)], hits: &mut Hits) {
+    let width: usize = spans.iter().map(|span| span.width()).sum();
+    let mut x = row.x + (row.width / 2).saturating_sub(width as u16 / 2);
+
+    for (i, span) in spans.iter().enumerate() {
+        let span_width = span.width() as u16;
+        if let Some(&(_, hit)) = targets.iter().find(|(idx, _)| *idx == i) {
+            let rect = Rect::new(x, row.y, span_width, 1).intersection(row);
+            if !rect.is_empty() {
+                hits.push((rect, hit));
+            }
+        }
+        x = x.saturating_add(span_width);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LayoutSize {
+    Compact,
+    Normal,
+    Large,
+}
+
+impl LayoutSize {
+    pub fn for_area(area: Rect) -> Self {
+        if area.height < COMPACT_HEIGHT || area.width < COMPACT_WIDTH {
+            LayoutSize::Compact
+        } else if area.height >= LARGE_HEIGHT && area.width >= LARGE_WIDTH {
+            LayoutSize::Large
+        } else {
+            LayoutSize::Normal
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Tab {
+    Tuner,
+    Spectrum,
+    Settings,
+    Log,
+    Practice,
+    Spectrogram,
+}
+
+pub const TABS: [Tab; 6] = [Tab::Tuner, Tab::Spectrum, Tab::Settings, Tab::Log, Tab::Practice, Tab::Spectrogram];
+
+impl Tab {
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Tuner => tr("tab.tuner"),
+            Tab::Spectrum => tr("tab.spectrum"),
+            Tab::Settings => tr("tab.settings"),
+            Tab::Log => tr("tab.log"),
+            Tab::Practice => tr("tab.practice"),
+            Tab::Spectrogram => tr("tab.spectrogram"),
+        }
+    }
+
+    fn index(self) -> usize {
+        TABS.iter().position(|&t| t == self).unwrap_or(0)
+    }
+
+    pub fn next(self) -> Self {
+        TABS[(self.index() + 1) % TABS.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        TABS[(self.index() + TABS.len() - 1) % TABS.len()]
+    }
+}
+
+pub enum TuningStatus {
+    Perfect,
+    Close,
+    Far,
+    NoSignal,
+}
+
+pub fn render_ui(frame: &mut Frame, state: &UiState) -> Hits {
+    let size = frame.size();
+    let mut hits = Hits::new();
+    if state.zen {
+        tuner::render_zen(frame, state, size);
+    } else if state.big_note {
+        tuner::render_big_note(frame, state, size);
+    } else {
+        render_pages(frame, state, size, &mut hits);
+    }
+
+    if state.paused_at.is_some() {
+        render_paused_badge(frame, state, size);
+    }
+    notices::render(frame, state, size);
+
+    let flashing = state.flash_at.is_some_and(|at| at.elapsed() < FLASH_DURATION);
+    if state.flash_enabled && flashing {
+        frame
+            .buffer_mut()
+            .set_style(size, Style::default().add_modifier(Modifier::REVERSED));
+    }
+
+    if state.is_dimmed() {
+        frame.buffer_mut().set_style(
+            size,
+            Style::default().fg(state.theme.muted).add_modifier(Modifier::DIM),
+        );
+    }
+
+    if state.audio_error.is_some() {
+        audio_error::render(frame, state, size);
+        hits.clear();
+    } else if state.show_help {
+        help::render(frame, state, size);
+        hits.clear();
+    } else if state.show_capo {
+        capo::render(frame, state, size);
+        hits.clear();
+    }
+
+    hits
+}
+
+fn render_paused_badge(frame: &mut Frame, state: &UiState, size: Rect) {
+    let badge = format!(" ⏸ {} ", tr("badge.paused"));
+    let width = (Span::raw(badge.as_str()).width() as u16).min(size.width);
+    let area = Rect::new(size.right().saturating_sub(width + 1), size.y, width, 1.min(size.height));
+
+    Paragraph::new(Line::styled(
+        badge,
+        Style::default().fg(state.theme.warning).add_modifier(Modifier::BOLD | Modifier::REVERSED),
+    ))
+    .render(area, frame.buffer_mut());
+}
+
+fn render_pages(frame: &mut Frame, state: &UiState, size: Rect, hits: &mut Hits) {
+    let layout = LayoutSize::for_area(size);
+    let tab_bar_height = if layout == LayoutSize::Compact { 1 } else { 3 };
+
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(tab_bar_height), Constraint::Min(0), Constraint::Length(1)])
+        .split(size);
+
+    render_tab_bar(frame, state, vertical[0], layout, hits);
+
+    match state.tab {
+        Tab::Tuner => tuner::render(frame, state, vertical[1], layout, hits),
+        Tab::Spectrum => spectrum::render(frame, state, vertical[1]),
+        Tab::Settings => settings::render(frame, state, vertical[1], hits),
+        Tab::Log => log::render(frame, state, vertical[1]),
+        Tab::Practice => practice::render(frame, state, vertical[1]),
+        Tab::Spectrogram => spectrogram::render(frame, state, vertical[1]),
+    }
+    status::render(frame, state, vertical[2]);
+}
+
+fn render_tab_bar(frame: &mut Frame, state: &UiState, area: Rect, layout: LayoutSize, hits: &mut Hits) {
+    let title_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(Line::from(vec![
+            Span::styled("🎸 ", Style::default().fg(state.theme.highlight)),
+            Span::styled(tr("app.title"), Style::default().fg(state.theme.accent).add_modifier(Modifier::BOLD)),
+        ]))
+        .title_alignment(Alignment::Center);
+
+    let titles: Vec<Line> = TABS
+        .iter()
+        .enumerate()
+        .map(|(i, tab)| Line::from(format!("{} {}", i + 1, tab.title())))
+        .collect();
+
+    let title_block = if layout == LayoutSize::Compact { Block::default() } else { title_block };
+    let inner = title_block.inner(area);
+    let mut x = inner.x;
+    for (tab, title) in TABS.iter().zip(titles.iter()) {
+        let width = title.width() as u16 + 2;
+        let rect = Rect::new(x, inner.y, width, 1).intersection(inner);
+        if !rect.is_empty() {
+            hits.push((rect, Hit::Tab(*tab)));
+        }
+        x = x.saturating_add(width + 1);
+    }
+
+    Tabs::new(titles)
+        .block(title_block)
+        .select(state.tab.index())
+        .style(Style::default().fg(state.theme.muted))
+        .highlight_style(
+            Style::default()
+                .fg(state.theme.highlight)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        )
+        .render(area, frame.buffer_mut());
+}
+
+fn status_appearance(status: TuningStatus, theme: &Theme) -> (Color, &'static str, &'static str) {
+    match status {
+        TuningStatus::Perfect => (theme.perfect, "●", tr("status.in_tune")),
+        TuningStatus::Close => (theme.close, "◐", tr("status.close")),
+        TuningStatus::Far => (theme.far, "◑", tr("status.far")),
+        TuningStatus::NoSignal => (theme.no_signal, "○", tr("status.no_signal")),
+    }
+}
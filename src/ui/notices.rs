@@ -0,0 +1,54 @@
+use super::UiState;
+use crate::notify::Severity;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget, Wrap},
+    Frame,
+};
+
+const MAX_WIDTH: u16 = 60;
+const MAX_LINES: u16 = 3;
+
+/// Stacks the live notices upwards from the bottom-right corner, newest
+/// lowest, leaving the status bar row visible.
+pub(super) fn render(frame: &mut Frame, state: &UiState, size: Rect) {
+    let width = MAX_WIDTH.min(size.width);
+    let mut bottom = size.bottom().saturating_sub(1);
+
+    for (notice, _) in state.notices.iter().rev() {
+        if width < 5 {
+            break;
+        }
+        let (color, symbol) = match notice.severity {
+            Severity::Info => (state.theme.accent, "ℹ"),
+            Severity::Warning => (state.theme.highlight, "⚠"),
+            Severity::Error => (state.theme.warning, "✖"),
+        };
+
+        let text_width = Span::raw(notice.text.as_str()).width() as u16 + 3;
+        let toast_width = (text_width + 2).min(width);
+        let lines = text_width.div_ceil(toast_width - 2).min(MAX_LINES);
+        if bottom < size.y + lines + 2 {
+            break;
+        }
+        let area = Rect::new(size.right() - toast_width, bottom - lines - 2, toast_width, lines + 2);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color));
+        let line = Line::from(vec![
+            Span::styled(format!(" {} ", symbol), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::styled(notice.text.as_str(), Style::default().fg(state.theme.text)),
+        ]);
+
+        Clear.render(area, frame.buffer_mut());
+        Paragraph::new(line)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .render(area, frame.buffer_mut());
+        bottom = area.y;
+    }
+}
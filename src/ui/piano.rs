@@ -0,0 +1,141 @@
+use super::{status_appearance, UiState};
+use crate::i18n::tr;
+use crate::tuner::Tuner;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::Style,
+    widgets::{Block, Borders},
+    Frame,
+};
+
+const KEY_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+const WHITE_KEYS: [i32; 7] = [0, 2, 4, 5, 7, 9, 11];
+const WHITE_WIDTH: u16 = 4;
+const MAX_OCTAVES: u16 = 3;
+
+fn key_number(note: &str, octave: i32) -> Option<i32> {
+    KEY_NAMES.iter().position(|&n| n == note).map(|idx| octave * 12 + idx as i32)
+}
+
+/// Column at the centre of `key`, where black keys sit on the boundary
+/// between their neighbouring white keys.
+fn key_center(key: i32, first_octave: i32, x: u16) -> i32 {
+    let octave = key.div_euclid(12) - first_octave;
+    let class = key.rem_euclid(12);
+    let white_before = WHITE_KEYS.iter().filter(|&&w| w < class).count() as i32;
+    let start = x as i32 + (octave * 7 + white_before) * WHITE_WIDTH as i32;
+
+    if WHITE_KEYS.contains(&class) {
+        start + WHITE_WIDTH as i32 / 2
+    } else {
+        start
+    }
+}
+
+pub(super) fn render(frame: &mut Frame, state: &UiState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(tr("keyboard.title"))
+        .title_alignment(Alignment::Center);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let octaves = (inner.width / (7 * WHITE_WIDTH)).min(MAX_OCTAVES);
+    if octaves == 0 || inner.height < 3 {
+        return;
+    }
+
+    let detected = match (state.current_note.as_ref(), state.current_octave) {
+        (Some(note), Some(octave)) => key_number(note, octave),
+        _ => None,
+    };
+    let target = key_number(&state.target_note, state.target_octave);
+    let focus = detected.or(target).map_or(4, |key| key.div_euclid(12));
+    let first_octave = focus - (octaves as i32 - 1) / 2;
+
+    let keyboard_width = octaves * 7 * WHITE_WIDTH + 1;
+    let x = inner.x + (inner.width - keyboard_width) / 2;
+    let (status_color, _, _) = status_appearance(state.get_tuning_status(), &state.theme);
+    let key_color = |key: i32| {
+        if Some(key) == detected {
+            Some(status_color)
+        } else if Some(key) == target {
+            Some(state.theme.target)
+        } else {
+            None
+        }
+    };
+
+    let buffer = frame.buffer_mut();
+    let key_rows = inner.height.min(4) - 1;
+    let black_rows = (key_rows * 2).div_ceil(3);
+
+    for white in 0..octaves as i32 * 7 {
+        let key = (first_octave + white / 7) * 12 + WHITE_KEYS[(white % 7) as usize];
+        let x0 = x + white as u16 * WHITE_WIDTH;
+        for row in 0..key_rows {
+            let y = inner.y + row;
+            let cell = buffer.get_mut(x0, y);
+            cell.set_char('│');
+            cell.set_fg(state.theme.muted);
+            for dx in 1..WHITE_WIDTH {
+                let cell = buffer.get_mut(x0 + dx, y);
+                match key_color(key) {
+                    Some(color) => {
+                        cell.set_char('█');
+                        cell.set_fg(color);
+                    }
+                    None => {
+                        cell.set_char(' ');
+                    }
+                }
+            }
+        }
+    }
+    for row in 0..key_rows {
+        let cell = buffer.get_mut(x + keyboard_width - 1, inner.y + row);
+        cell.set_char('│');
+        cell.set_fg(state.theme.muted);
+    }
+
+    for octave in 0..octaves as i32 {
+        for class in (0..12).filter(|c| !WHITE_KEYS.contains(c)) {
+            let key = (first_octave + octave) * 12 + class;
+            let center = key_center(key, first_octave, x) as u16;
+            let color = key_color(key).unwrap_or(state.theme.muted);
+            for row in 0..black_rows {
+                for bx in center - 1..=center + 1 {
+                    let cell = buffer.get_mut(bx, inner.y + row);
+                    cell.set_char('█');
+                    cell.set_fg(color);
+                }
+            }
+        }
+    }
+
+    let marker_row = inner.y + key_rows;
+    let left = x as i32;
+    let right = (x + keyboard_width - 1) as i32;
+    let mut place = |column: i32, symbol: char, color| {
+        if (left..=right).contains(&column) {
+            let cell = buffer.get_mut(column as u16, marker_row);
+            cell.set_char(symbol);
+            cell.set_fg(color);
+        }
+    };
+
+    if let Some(key) = target {
+        place(key_center(key, first_octave, x), '△', state.theme.target);
+    }
+    if let (Some(key), Some(freq), Some(note), Some(octave)) =
+        (detected, state.current_freq, state.current_note.as_ref(), state.current_octave)
+    {
+        let cents = 1200.0 * (freq / Tuner::note_name_to_frequency(note, octave, state.a4_freq)).log2();
+        let center = key_center(key, first_octave, x) as f32;
+        let neighbour = if cents < 0.0 { key - 1 } else { key + 1 };
+        let step = (key_center(neighbour, first_octave, x) as f32 - center).abs();
+        let offset = (cents.clamp(-50.0, 50.0) / 100.0) * step;
+        place((center + offset).round() as i32, '▲', status_color);
+    }
+}
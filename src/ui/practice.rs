@@ -0,0 +1,773 @@
+use super::{status_appearance, UiState};
+use crate::i18n::{tr, trf};
+use crate::keymap::Action;
+use crate::midi;
+use crate::practice::{
+    Advance, Exercise, GamePhase, HoldPhase, HoldResult, Phase, Reading, WarmUpPhase, EXERCISES, GAME_ROUNDS,
+    INTERVALS, Sequence, SequencePhase, PATTERNS, SCALES,
+};
+use crate::tuner::Tuner;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Tabs, Widget, Wrap},
+    Frame,
+};
+use std::time::Instant;
+
+pub(super) fn render(frame: &mut Frame, state: &UiState, area: Rect) {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(7), Constraint::Min(3)])
+        .split(area);
+
+    render_exercises(frame, state, vertical[0]);
+    match state.practice.exercise {
+        Exercise::Intervals => {
+            render_question(frame, state, vertical[1]);
+            render_rounds(frame, state, vertical[2]);
+        }
+        Exercise::Game => {
+            render_game(frame, state, vertical[1]);
+            render_score(frame, state, vertical[2]);
+        }
+        Exercise::LongTone => {
+            render_long_tone(frame, state, vertical[1]);
+            render_holds(frame, state, vertical[2]);
+        }
+        Exercise::WarmUp => {
+            render_warmup(frame, state, vertical[1]);
+            render_steps(frame, state, vertical[2]);
+        }
+        Exercise::Scale => {
+            render_scale(frame, state, vertical[1]);
+            render_degrees(frame, state, vertical[2]);
+        }
+        Exercise::Sequence => {
+            render_sequence(frame, state, vertical[1]);
+            render_chart(frame, state, vertical[2]);
+        }
+        Exercise::DoubleStop => {
+            render_double_stop(frame, state, vertical[1]);
+            render_comparison(frame, state, vertical[2]);
+        }
+    }
+}
+
+fn render_exercises(frame: &mut Frame, state: &UiState, area: Rect) {
+    let titles: Vec<Line> = EXERCISES.iter().map(|exercise| Line::from(tr(exercise.title()))).collect();
+    let index = EXERCISES.iter().position(|&e| e == state.practice.exercise).unwrap_or(0);
+    let keys = state.keymap.pair_label(Action::NotePrevious, Action::NoteNext);
+    let horizontal = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(keys.chars().count() as u16 + 1)])
+        .split(area);
+
+    Tabs::new(titles)
+        .select(index)
+        .style(Style::default().fg(state.theme.muted))
+        .highlight_style(Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD))
+        .render(horizontal[0], frame.buffer_mut());
+    Paragraph::new(Line::styled(keys, Style::default().fg(state.theme.muted)))
+        .alignment(Alignment::Right)
+        .render(horizontal[1], frame.buffer_mut());
+}
+
+fn cents_text(cents: f32) -> String {
+    trf("fmt.cents", &[&format!("{:+.1}", cents)])
+}
+
+fn render_question(frame: &mut Frame, state: &UiState, area: Rect) {
+    let drill = &state.practice.intervals;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(tr("practice.title"))
+        .title_alignment(Alignment::Center);
+
+    let text = Style::default().fg(state.theme.text);
+    let bold = Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD);
+    let interval = tr(drill.interval());
+    let root = drill.root_name();
+    let key = state.keymap.label(Action::Select);
+
+    let (prompt, detail) = match &drill.phase {
+        Phase::Idle => (Line::styled(trf("practice.start", &[&key]), text), Line::raw("")),
+        Phase::Reference { .. } => (Line::styled(trf("practice.listen", &[&root]), bold), Line::raw("")),
+        Phase::Listening { .. } => {
+            let live = match state.current_freq {
+                Some(freq) => {
+                    let cents = drill.deviation(freq, state.a4_freq);
+                    let (color, _, _) = status_appearance(state.status_for(Some(cents)), &state.theme);
+                    Line::styled(cents_text(cents), Style::default().fg(color))
+                }
+                None => Line::styled(tr("status.no_signal"), Style::default().fg(state.theme.muted)),
+            };
+            (Line::styled(trf("practice.ask", &[&interval, &root]), bold), live)
+        }
+        Phase::Scored(Some(cents)) => {
+            let (color, symbol, _) = status_appearance(state.status_for(Some(*cents)), &state.theme);
+            let result = trf("practice.result", &[&interval, &root, &cents_text(*cents)]);
+            let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+            (Line::styled(format!("{} {}", symbol, result), style), Line::raw(""))
+        }
+        Phase::Scored(None) => {
+            let style = Style::default().fg(state.theme.warning);
+            (Line::styled(tr("practice.no_answer"), style), Line::raw(""))
+        }
+    };
+    let hint = Line::styled(trf("practice.hint", &[&key]), Style::default().fg(state.theme.muted));
+
+    Paragraph::new(vec![prompt, detail, Line::raw(""), hint])
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn render_rounds(frame: &mut Frame, state: &UiState, area: Rect) {
+    let drill = &state.practice.intervals;
+    let average = drill.average().map_or("---".to_string(), |cents| trf("fmt.cents", &[&format!("{:.1}", cents)]));
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.info))
+        .title(trf("practice.rounds", &[&average]))
+        .title_alignment(Alignment::Center);
+
+    let lines: Vec<Line> = drill
+        .rounds
+        .iter()
+        .map(|round| {
+            let (note, octave) = midi::note_name(round.root);
+            let question = format!("{:>16} / {}{}", tr(INTERVALS[round.semitones as usize - 1]), note, octave);
+            let (answer, color) = match round.cents {
+                Some(cents) => (cents_text(cents), status_appearance(state.status_for(Some(cents)), &state.theme).0),
+                None => ("---".to_string(), state.theme.muted),
+            };
+            Line::from(vec![
+                Span::styled(question, Style::default().fg(state.theme.text)),
+                Span::raw("   "),
+                Span::styled(answer, Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn note_text(number: u8) -> String {
+    let (note, octave) = midi::note_name(number);
+    format!("{}{}", note, octave)
+}
+
+fn render_game(frame: &mut Frame, state: &UiState, area: Rect) {
+    let game = &state.practice.game;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(tr("game.title"))
+        .title_alignment(Alignment::Center);
+
+    let muted = Style::default().fg(state.theme.muted);
+    let bold = Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD);
+    let key = state.keymap.label(Action::Select);
+    let target = format!("{}{}", state.target_note, state.target_octave);
+    let start = trf("game.start", &[&key, &GAME_ROUNDS.to_string(), &target]);
+
+    let lines = match (&game.phase, game.progress(Instant::now())) {
+        (GamePhase::Playing { .. }, Some((left, hold))) => {
+            let live = match state.deviation_cents {
+                Some(cents) => {
+                    let (color, symbol, _) = status_appearance(state.status_for(Some(cents)), &state.theme);
+                    Line::styled(format!("{} {}", symbol, cents_text(cents)), Style::default().fg(color))
+                }
+                None => Line::styled(tr("status.no_signal"), muted),
+            };
+            let filled = (hold * 10.0).round() as usize;
+            let bar = format!("{}{}", "▰".repeat(filled), "▱".repeat(10 - filled));
+            vec![
+                Line::styled(trf("game.hit", &[&note_text(game.target)]), bold),
+                live,
+                Line::from(vec![
+                    Span::styled(bar, Style::default().fg(state.theme.perfect)),
+                    Span::styled(format!("  {}", trf("game.left", &[&left.as_secs().to_string()])), muted),
+                ]),
+                Line::styled(trf("game.stop", &[&key]), muted),
+            ]
+        }
+        (GamePhase::Over, _) => vec![
+            Line::styled(trf("game.over", &[&game.score.to_string()]), bold),
+            Line::raw(""),
+            Line::raw(""),
+            Line::styled(start, muted),
+        ],
+        _ => vec![
+            Line::styled(start, Style::default().fg(state.theme.text)),
+            Line::raw(""),
+            Line::raw(""),
+            Line::styled(tr("game.rules"), muted),
+        ],
+    };
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn render_score(frame: &mut Frame, state: &UiState, area: Rect) {
+    let game = &state.practice.game;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.info))
+        .title(tr("game.stats"))
+        .title_alignment(Alignment::Center);
+
+    let text = Style::default().fg(state.theme.text);
+    let accuracy = game.accuracy().map_or("---".to_string(), |percent| format!("{:.0}%", percent));
+    let average = game.average_cents().map_or("---".to_string(), |cents| trf("fmt.cents", &[&format!("{:.1}", cents)]));
+    let note = game.played + usize::from(matches!(game.phase, GamePhase::Playing { .. }));
+    let mut lines = vec![
+        Line::styled(trf("game.score", &[&game.score.to_string()]), text.add_modifier(Modifier::BOLD)),
+        Line::styled(trf("game.round", &[&note.to_string(), &GAME_ROUNDS.to_string()]), text),
+        Line::styled(trf("game.accuracy", &[&game.hits.to_string(), &accuracy]), text),
+        Line::styled(trf("game.average", &[&average]), text),
+        Line::styled(trf("game.streak", &[&game.streak.to_string(), &game.best_streak.to_string()]), text),
+    ];
+    if let Some(hit) = &game.last {
+        let line = match hit.result {
+            Some((points, cents)) => Line::styled(
+                trf("game.last_hit", &[&note_text(hit.target), &points.to_string(), &cents_text(cents)]),
+                Style::default().fg(state.theme.perfect),
+            ),
+            None => {
+                Line::styled(trf("game.last_miss", &[&note_text(hit.target)]), Style::default().fg(state.theme.far))
+            }
+        };
+        lines.push(Line::raw(""));
+        lines.push(line);
+    }
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn hold_text(result: &HoldResult) -> String {
+    trf(
+        "hold.stats",
+        &[&format!("{:+.1}", result.offset), &format!("{:.1}", result.spread), &format!("{:+.1}", result.drift)],
+    )
+}
+
+fn render_long_tone(frame: &mut Frame, state: &UiState, area: Rect) {
+    let hold = &state.practice.long_tone;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(tr("hold.title"))
+        .title_alignment(Alignment::Center);
+
+    let muted = Style::default().fg(state.theme.muted);
+    let bold = Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD);
+    let key = state.keymap.label(Action::Select);
+    let seconds = hold.duration().as_secs().to_string();
+    let lengths = state.keymap.pair_label(Action::OctaveDown, Action::OctaveUp);
+
+    let lines = match (&hold.phase, hold.live(Instant::now())) {
+        (HoldPhase::Holding { .. }, Some((held, live))) => {
+            let progress = held.as_secs_f32() / hold.duration().as_secs_f32();
+            let filled = ((progress * 20.0) as usize).min(20);
+            let (color, _, _) = status_appearance(state.status_for(Some(live.spread)), &state.theme);
+            vec![
+                Line::styled(
+                    trf("hold.holding", &[&note_text(live.note), &format!("{:.1}", held.as_secs_f32())]),
+                    bold,
+                ),
+                Line::styled(hold_text(&live), Style::default().fg(color)),
+                Line::styled(
+                    format!("{}{}", "█".repeat(filled), "░".repeat(20 - filled)),
+                    Style::default().fg(state.theme.info),
+                ),
+                Line::styled(trf("hold.stop", &[&key]), muted),
+            ]
+        }
+        (HoldPhase::Waiting, _) => vec![
+            Line::styled(trf("hold.waiting", &[&seconds]), bold),
+            Line::raw(""),
+            Line::raw(""),
+            Line::styled(trf("hold.cancel", &[&key]), muted),
+        ],
+        _ => {
+            let summary = match hold.results.front() {
+                Some(result) if matches!(hold.phase, HoldPhase::Done) => {
+                    let (color, _, _) = status_appearance(state.status_for(Some(result.spread)), &state.theme);
+                    Line::styled(
+                        trf("hold.grade", &[&result.grade().to_string(), &note_text(result.note)]),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    )
+                }
+                _ => Line::raw(""),
+            };
+            vec![
+                summary,
+                Line::raw(""),
+                Line::styled(trf("hold.start", &[&key, &seconds]), Style::default().fg(state.theme.text)),
+                Line::styled(trf("hold.length", &[&lengths]), muted),
+            ]
+        }
+    };
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn render_holds(frame: &mut Frame, state: &UiState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.info))
+        .title(tr("hold.results"))
+        .title_alignment(Alignment::Center);
+
+    let lines: Vec<Line> = state
+        .practice
+        .long_tone
+        .results
+        .iter()
+        .map(|result| {
+            let (color, _, _) = status_appearance(state.status_for(Some(result.spread)), &state.theme);
+            let held = if result.complete {
+                format!("{:.0} s", result.held.as_secs_f32())
+            } else {
+                trf("hold.broken", &[&format!("{:.1}", result.held.as_secs_f32())])
+            };
+            Line::from(vec![
+                Span::styled(format!("{}  {:<4}", result.grade(), note_text(result.note)), Style::default().fg(color)),
+                Span::styled(format!("{:<18}", held), Style::default().fg(state.theme.muted)),
+                Span::styled(hold_text(result), Style::default().fg(state.theme.text)),
+            ])
+        })
+        .collect();
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn render_warmup(frame: &mut Frame, state: &UiState, area: Rect) {
+    let warmup = &state.practice.warmup;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(tr("warmup.title"))
+        .title_alignment(Alignment::Center);
+
+    let muted = Style::default().fg(state.theme.muted);
+    let bold = Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD);
+    let key = state.keymap.label(Action::Select);
+    let advance = match warmup.advance {
+        Advance::Timer => tr("warmup.timer"),
+        Advance::Match => tr("warmup.match"),
+    };
+    let pattern = Line::styled(
+        format!("{} · {}", tr(PATTERNS[warmup.pattern].0), advance),
+        Style::default().fg(state.theme.info),
+    );
+
+    let lines = match &warmup.phase {
+        WarmUpPhase::Running { step, .. } => {
+            let target = format!("{}{}", state.target_note, state.target_octave);
+            let live = match state.deviation_cents {
+                Some(cents) => {
+                    let (color, symbol, _) = status_appearance(state.status_for(Some(cents)), &state.theme);
+                    Line::styled(format!("{} {}", symbol, cents_text(cents)), Style::default().fg(color))
+                }
+                None => Line::styled(tr("status.no_signal"), muted),
+            };
+            vec![
+                pattern,
+                Line::styled(
+                    trf("warmup.sing", &[&target, &(step + 1).to_string(), &warmup.total_steps().to_string()]),
+                    bold,
+                ),
+                live,
+                Line::styled(trf("warmup.stop", &[&key]), muted),
+            ]
+        }
+        _ => {
+            let summary = match warmup.average() {
+                Some(cents) if matches!(warmup.phase, WarmUpPhase::Done) => {
+                    let (color, _, _) = status_appearance(state.status_for(Some(cents)), &state.theme);
+                    let average = trf("fmt.cents", &[&format!("{:.1}", cents)]);
+                    let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+                    Line::styled(trf("warmup.done", &[&average]), style)
+                }
+                _ => Line::raw(""),
+            };
+            let choose = state.keymap.pair_label(Action::OctaveDown, Action::OctaveUp);
+            vec![
+                pattern,
+                summary,
+                Line::styled(
+                    trf("warmup.start", &[&key, &format!("{}{}", state.target_note, state.target_octave)]),
+                    Style::default().fg(state.theme.text),
+                ),
+                Line::styled(trf("warmup.choose", &[&choose]), muted),
+            ]
+        }
+    };
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn render_steps(frame: &mut Frame, state: &UiState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.info))
+        .title(tr("warmup.steps"))
+        .title_alignment(Alignment::Center);
+
+    let mut spans = Vec::new();
+    for step in &state.practice.warmup.steps {
+        let (text, color) = match step.cents {
+            Some(cents) => (format!("{:+.0}", cents), status_appearance(state.status_for(Some(cents)), &state.theme).0),
+            None => ("---".to_string(), state.theme.muted),
+        };
+        let took = step.took.map_or(String::new(), |took| format!(" {:.1}s", took.as_secs_f32()));
+        spans.push(Span::styled(format!("{} ", note_text(step.target)), Style::default().fg(state.theme.text)));
+        spans.push(Span::styled(text, Style::default().fg(color)));
+        spans.push(Span::styled(format!("{}   ", took), Style::default().fg(state.theme.muted)));
+    }
+
+    Paragraph::new(Line::from(spans))
+        .block(block)
+        .wrap(Wrap { trim: true })
+        .render(area, frame.buffer_mut());
+}
+
+fn render_scale(frame: &mut Frame, state: &UiState, area: Rect) {
+    let scale = &state.practice.scale;
+    let (key, _) = midi::note_name(scale.key);
+    let name = trf("scale.name", &[&key, &tr(SCALES[scale.scale].0)]);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(tr("scale.title"))
+        .title_alignment(Alignment::Center);
+
+    let muted = Style::default().fg(state.theme.muted);
+    let bold = Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD);
+    let heard = match &scale.heard {
+        Some(heard) => {
+            let (color, _, _) = status_appearance(state.status_for(Some(heard.cents)), &state.theme);
+            match heard.degree {
+                Some(degree) => Line::from(vec![
+                    Span::styled(trf("scale.degree", &[&(degree + 1).to_string(), &note_text(heard.note)]), bold),
+                    Span::styled(format!("  {}", cents_text(heard.cents)), Style::default().fg(color)),
+                ]),
+                None => Line::styled(
+                    trf("scale.outside", &[&note_text(heard.note), &name]),
+                    Style::default().fg(state.theme.far),
+                ),
+            }
+        }
+        None => Line::styled(tr("status.no_signal"), muted),
+    };
+
+    let len = scale.degrees().len();
+    let expected = scale.expected().map(|degree| degree % len);
+    let playing = scale.heard.as_ref().and_then(|heard| heard.degree);
+    let mut strip = Vec::new();
+    for (degree, &step) in scale.degrees().iter().enumerate() {
+        let (note, _) = midi::note_name(scale.key + step);
+        let marker = if expected == Some(degree) { "▶" } else { " " };
+        let style = if playing == Some(degree) {
+            Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Style::default().fg(state.theme.text)
+        };
+        strip.push(Span::styled(marker, Style::default().fg(state.theme.perfect)));
+        strip.push(Span::styled(format!("{} {:<2}", degree + 1, note), style));
+        strip.push(Span::raw(" "));
+    }
+
+    let keys = state.keymap.pair_label(Action::OctaveDown, Action::OctaveUp);
+    let follow = if scale.cursor.is_some() { tr("scale.follow_off") } else { tr("scale.follow_on") };
+    let hint = trf("scale.hint", &[&keys, &state.keymap.label(Action::Select), &follow]);
+    let lines = vec![
+        Line::styled(name, Style::default().fg(state.theme.info)),
+        heard,
+        Line::from(strip),
+        Line::styled(hint, muted),
+    ];
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn render_degrees(frame: &mut Frame, state: &UiState, area: Rect) {
+    let scale = &state.practice.scale;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.info))
+        .title(tr("scale.averages"))
+        .title_alignment(Alignment::Center);
+
+    let lines: Vec<Line> = scale
+        .degrees()
+        .iter()
+        .zip(scale.averages())
+        .enumerate()
+        .map(|(degree, (&step, average))| {
+            let (note, _) = midi::note_name(scale.key + step);
+            let (text, color) = match average {
+                Some(cents) => (cents_text(cents), status_appearance(state.status_for(Some(cents)), &state.theme).0),
+                None => ("---".to_string(), state.theme.muted),
+            };
+            Line::from(vec![
+                Span::styled(format!("{} {:<2}  ", degree + 1, note), Style::default().fg(state.theme.text)),
+                Span::styled(format!("{:>12}", text), Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn render_sequence(frame: &mut Frame, state: &UiState, area: Rect) {
+    let drill = &state.practice.sequence;
+    let name = match &drill.sequences[drill.selected] {
+        Sequence::Arpeggio(name, _) => trf(name, &[&format!("{}{}", state.target_note, state.target_octave)]),
+        Sequence::Custom(name, _) => name.clone(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(tr("sequence.title"))
+        .title_alignment(Alignment::Center);
+
+    let muted = Style::default().fg(state.theme.muted);
+    let bold = Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD);
+    let key = state.keymap.label(Action::Select);
+    let (notes, position) = match &drill.phase {
+        SequencePhase::Playing { position, notes } => (notes.clone(), Some(*position)),
+        _ => (drill.notes(state.target_number()), None),
+    };
+
+    let mut strip = Vec::new();
+    for (index, &note) in notes.iter().enumerate() {
+        let style = match position {
+            Some(position) if index == position => bold.add_modifier(Modifier::REVERSED),
+            Some(position) if index < position => muted,
+            _ => Style::default().fg(state.theme.text),
+        };
+        strip.push(Span::styled(note_text(note), style));
+        strip.push(Span::raw(" "));
+    }
+
+    let (prompt, hint) = match position {
+        Some(position) => (
+            Line::styled(
+                trf(
+                    "sequence.play",
+                    &[&note_text(notes[position]), &(position + 1).to_string(), &notes.len().to_string()],
+                ),
+                bold,
+            ),
+            trf("sequence.stop", &[&key]),
+        ),
+        None => {
+            let choose = state.keymap.pair_label(Action::OctaveDown, Action::OctaveUp);
+            (Line::raw(""), trf("sequence.start", &[&key, &choose]))
+        }
+    };
+
+    let title = Line::styled(name, Style::default().fg(state.theme.info));
+    Paragraph::new(vec![title, Line::from(strip), prompt, Line::styled(hint, muted)])
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .render(area, frame.buffer_mut());
+}
+
+/// Half the width of the error chart, in cells, and the cents at its edges.
+const CHART_HALF: usize = 12;
+const CHART_CENTS: f32 = 50.0;
+
+fn render_chart(frame: &mut Frame, state: &UiState, area: Rect) {
+    let drill = &state.practice.sequence;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.info))
+        .title(tr("sequence.chart"))
+        .title_alignment(Alignment::Center);
+
+    let muted = Style::default().fg(state.theme.muted);
+    let (low, high) = (format!("-{:.0}", CHART_CENTS), format!("+{:.0}", CHART_CENTS));
+    let mut lines = vec![Line::styled(format!("{:<5}{:<w$}0{:>w$}", "", low, high, w = CHART_HALF), muted)];
+    for result in &drill.results {
+        let Some(cents) = result.cents else {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<5}", note_text(result.note)), Style::default().fg(state.theme.text)),
+                Span::styled(format!("{:^w$}", tr("sequence.skipped"), w = 2 * CHART_HALF + 1), muted),
+            ]));
+            continue;
+        };
+        let (color, _, _) = status_appearance(state.status_for(Some(cents)), &state.theme);
+        let offset = (cents / CHART_CENTS * CHART_HALF as f32).round().clamp(-(CHART_HALF as f32), CHART_HALF as f32);
+        let cell = (CHART_HALF as i32 + offset as i32) as usize;
+        let bar: String = (0..=2 * CHART_HALF)
+            .map(|i| match i {
+                _ if i == cell => '●',
+                _ if i == CHART_HALF => '│',
+                _ if (i > cell && i < CHART_HALF) || (i < cell && i > CHART_HALF) => '━',
+                _ => '·',
+            })
+            .collect();
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<5}", note_text(result.note)), Style::default().fg(state.theme.text)),
+            Span::styled(bar, Style::default().fg(color)),
+            Span::styled(format!("  {}", cents_text(cents)), Style::default().fg(color)),
+        ]));
+    }
+    if let (SequencePhase::Done, false) = (&drill.phase, drill.results.is_empty()) {
+        let played: Vec<f32> = drill.results.iter().filter_map(|r| r.cents).map(f32::abs).collect();
+        if !played.is_empty() {
+            let average = played.iter().sum::<f32>() / played.len() as f32;
+            lines.push(Line::raw(""));
+            lines.push(Line::styled(
+                trf("sequence.average", &[&trf("fmt.cents", &[&format!("{:.1}", average)])]),
+                Style::default().fg(state.theme.text).add_modifier(Modifier::BOLD),
+            ));
+        }
+    }
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn interval_name(reading: &Reading) -> String {
+    semitone_name(reading.semitones())
+}
+
+pub(super) fn semitone_name(semitones: u32) -> String {
+    match semitones {
+        0 => tr("interval.P1").to_string(),
+        semitones if semitones <= 12 => tr(INTERVALS[semitones as usize - 1]).to_string(),
+        semitones => trf("double_stop.compound", &[&tr(INTERVALS[(semitones as usize - 1) % 12])]),
+    }
+}
+
+fn render_double_stop(frame: &mut Frame, state: &UiState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(tr("double_stop.title"))
+        .title_alignment(Alignment::Center);
+
+    let muted = Style::default().fg(state.theme.muted);
+    let lines = match &state.practice.double_stop.reading {
+        Some(reading) => {
+            let note = |freq: f32| {
+                let (name, octave, _) = Tuner::nearest_note(freq, state.a4_freq);
+                format!("{}{} ({:.1} Hz)", name, octave, freq)
+            };
+            let ((numerator, denominator), just) = reading.just();
+            let cents = trf("fmt.cents", &[&format!("{:.1}", reading.cents)]);
+            let size = trf("double_stop.size", &[&interval_name(reading), &cents]);
+            let equal = trf("double_stop.equal", &[&format!("{:.0}", reading.equal())]);
+            let just = trf("double_stop.just", &[&format!("{}:{}", numerator, denominator), &format!("{:.1}", just)]);
+            let deviation = |cents: f32| {
+                let (color, _, _) = status_appearance(state.status_for(Some(cents)), &state.theme);
+                Span::styled(format!(" ({:+.1})", cents), Style::default().fg(color))
+            };
+            vec![
+                Line::styled(
+                    trf("double_stop.notes", &[&note(reading.low), &note(reading.high)]),
+                    Style::default().fg(state.theme.info),
+                ),
+                Line::styled(size, Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD)),
+                Line::from(vec![
+                    Span::styled(equal, Style::default().fg(state.theme.text)),
+                    deviation(reading.equal_deviation()),
+                    Span::styled(format!("   {}", just), Style::default().fg(state.theme.text)),
+                    deviation(reading.just_deviation()),
+                ]),
+                Line::styled(tr("double_stop.hint"), muted),
+            ]
+        }
+        None => vec![
+            Line::styled(tr("double_stop.listening"), Style::default().fg(state.theme.text)),
+            Line::raw(""),
+            Line::raw(""),
+            Line::styled(tr("double_stop.hint"), muted),
+        ],
+    };
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+/// Half the width of the comparison scale, in cells, and the cents either
+/// side of the equal-tempered interval at its edges.
+const COMPARISON_HALF: usize = 20;
+const COMPARISON_CENTS: f32 = 30.0;
+
+/// The measured interval on a scale centred on equal temperament, with the
+/// just interval marked, so it is clear which tuning it leans towards.
+fn render_comparison(frame: &mut Frame, state: &UiState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.info))
+        .title(tr("double_stop.comparison"))
+        .title_alignment(Alignment::Center);
+
+    let muted = Style::default().fg(state.theme.muted);
+    let (low, high) = (format!("-{:.0}", COMPARISON_CENTS), format!("+{:.0}", COMPARISON_CENTS));
+    let mut lines = vec![Line::styled(format!("{:<w$}0{:>w$}", low, high, w = COMPARISON_HALF), muted)];
+    if let Some(reading) = &state.practice.double_stop.reading {
+        let cell = |cents: f32| {
+            let offset = (cents / COMPARISON_CENTS * COMPARISON_HALF as f32).round();
+            (COMPARISON_HALF as i32 + offset.clamp(-(COMPARISON_HALF as f32), COMPARISON_HALF as f32) as i32) as usize
+        };
+        let (measured, just) = (cell(reading.equal_deviation()), cell(reading.just().1 - reading.equal()));
+        let (color, _, _) = status_appearance(state.status_for(Some(reading.just_deviation())), &state.theme);
+        let spans: Vec<Span> = (0..=2 * COMPARISON_HALF)
+            .map(|i| match i {
+                _ if i == measured => Span::styled("●", Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                _ if i == just => Span::styled("J", Style::default().fg(state.theme.perfect)),
+                _ if i == COMPARISON_HALF => Span::styled("│", Style::default().fg(state.theme.text)),
+                _ => Span::styled("·", muted),
+            })
+            .collect();
+        lines.push(Line::from(spans));
+    } else {
+        lines.push(Line::styled(format!("{:·<w$}│{:·<w$}", "", "", w = COMPARISON_HALF), muted));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(tr("double_stop.legend"), muted));
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
@@ -0,0 +1,77 @@
+use super::{Hit, Hits, UiState};
+use crate::i18n::tr;
+use crate::keymap::Action;
+use crate::settings::SETTINGS;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget},
+    Frame,
+};
+
+pub(super) fn render(frame: &mut Frame, state: &UiState, area: Rect, hits: &mut Hits) {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(3)])
+        .split(area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(tr("settings.title"))
+        .title_alignment(Alignment::Center);
+
+    let lines: Vec<Line> = SETTINGS
+        .iter()
+        .enumerate()
+        .map(|(i, setting)| {
+            let selected = i == state.settings_cursor;
+            let marker = if selected { "▶ " } else { "  " };
+            let mut value_style = Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD);
+            if selected {
+                value_style = value_style.add_modifier(Modifier::REVERSED);
+            }
+            Line::from(vec![
+                Span::styled(marker, Style::default().fg(state.theme.highlight)),
+                Span::styled(format!("{:<22}", setting.label()), Style::default().fg(state.theme.text)),
+                Span::styled(format!(" {} ", setting.value(state)), value_style),
+            ])
+        })
+        .collect();
+
+    let inner = block.inner(vertical[0]);
+    for i in 0..SETTINGS.len().min(inner.height as usize) {
+        hits.push((Rect::new(inner.x, inner.y + i as u16, inner.width, 1), Hit::Setting(i)));
+    }
+
+    Paragraph::new(lines).block(block).render(vertical[0], frame.buffer_mut());
+
+    let hint_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.muted));
+
+    let mut hint = vec![
+        Span::styled(
+            state.keymap.pair_label(Action::OctaveUp, Action::OctaveDown),
+            Style::default().fg(state.theme.highlight),
+        ),
+        Span::raw(format!(" {} | ", tr("settings.select"))),
+        Span::styled(
+            state.keymap.pair_label(Action::NotePrevious, Action::NoteNext),
+            Style::default().fg(state.theme.highlight),
+        ),
+        Span::raw(format!(" {} | ", tr("settings.change"))),
+        Span::styled(state.keymap.label(Action::Save), Style::default().fg(state.theme.highlight)),
+        Span::raw(format!(" {}", tr("settings.save"))),
+    ];
+    if let Some(message) = &state.settings_message {
+        hint.push(Span::raw(" | "));
+        hint.push(Span::styled(message.clone(), Style::default().fg(state.theme.info)));
+    }
+
+    Paragraph::new(Line::from(hint))
+        .block(hint_block)
+        .alignment(Alignment::Center)
+        .render(vertical[1], frame.buffer_mut());
+}
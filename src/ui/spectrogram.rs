@@ -0,0 +1,117 @@
+use super::UiState;
+use crate::i18n::tr;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Widget},
+    Frame,
+};
+
+/// Columns of history kept, and log-spaced frequency bands in each.
+pub(super) const HISTORY: usize = 300;
+const BANDS: usize = 120;
+const MIN_FREQ: f32 = 40.0;
+const MAX_FREQ: f32 = 5000.0;
+/// Magnitudes this far below the loudest in view are drawn blank.
+const FLOOR_DB: f32 = -60.0;
+const LABELS: [(f32, &str); 7] =
+    [(50.0, "50"), (100.0, "100"), (200.0, "200"), (500.0, "500"), (1000.0, "1k"), (2000.0, "2k"), (4000.0, "4k")];
+const LABEL_WIDTH: u16 = 4;
+
+/// Frequency at the bottom edge of band `band`, on the log scale.
+fn band_freq(band: f32) -> f32 {
+    MIN_FREQ * (MAX_FREQ / MIN_FREQ).powf(band / BANDS as f32)
+}
+
+/// Reduces an FFT frame to the loudest magnitude in each band; bands
+/// narrower than a bin take the nearest bin.
+pub(super) fn column(spectrum: &[f32], bin_hz: f32) -> Vec<f32> {
+    if spectrum.is_empty() || bin_hz <= 0.0 {
+        return vec![0.0; BANDS];
+    }
+    (0..BANDS)
+        .map(|band| {
+            let low = band_freq(band as f32) / bin_hz;
+            let high = band_freq(band as f32 + 1.0) / bin_hz;
+            let (first, last) = (low.ceil() as usize, high.floor() as usize);
+            if first > last {
+                let nearest = ((low + high) / 2.0).round() as usize;
+                return spectrum.get(nearest).copied().unwrap_or(0.0);
+            }
+            let bins = first.min(spectrum.len())..(last + 1).min(spectrum.len());
+            spectrum[bins].iter().fold(0.0f32, |a, &m| a.max(m))
+        })
+        .collect()
+}
+
+/// Row of `freq` in a plot `rows` high, counted from the top.
+fn row_of(freq: f32, rows: u16) -> Option<u16> {
+    if !(MIN_FREQ..MAX_FREQ).contains(&freq) {
+        return None;
+    }
+    let position = (freq / MIN_FREQ).ln() / (MAX_FREQ / MIN_FREQ).ln();
+    Some(rows - 1 - ((position * rows as f32) as u16).min(rows - 1))
+}
+
+/// Shading and colour for a level from 0 (floor) to 1 (loudest), so the
+/// plot reads in monochrome themes as well.
+fn shade(level: f32, state: &UiState) -> (char, Color) {
+    match (level * 5.0) as usize {
+        0 => (' ', state.theme.muted),
+        1 => ('░', state.theme.muted),
+        2 => ('▒', state.theme.info),
+        3 => ('▓', state.theme.close),
+        _ => ('█', state.theme.far),
+    }
+}
+
+/// Time runs left to right with the newest frame at the right edge,
+/// frequency bottom to top on a log scale; the detected pitch is marked.
+pub(super) fn render(frame: &mut Frame, state: &UiState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(tr("spectrogram.title"))
+        .title_alignment(Alignment::Center);
+    let inner = block.inner(area);
+    block.render(area, frame.buffer_mut());
+    if inner.width <= LABEL_WIDTH + 2 || inner.height < 2 {
+        return;
+    }
+
+    let buffer = frame.buffer_mut();
+    let rows = inner.height;
+    let plot_x = inner.x + LABEL_WIDTH;
+    let width = (inner.width - LABEL_WIDTH - 1) as usize;
+    let columns: Vec<&Vec<f32>> = state.spectrogram.iter().rev().take(width).collect();
+    let peak = columns.iter().flat_map(|c| c.iter()).fold(0.0f32, |a, &m| a.max(m)).max(1e-9);
+
+    for (label_freq, label) in LABELS {
+        if let Some(row) = row_of(label_freq, rows) {
+            let style = Style::default().fg(state.theme.muted);
+            buffer.set_stringn(inner.x, inner.y + row, label, LABEL_WIDTH as usize, style);
+        }
+    }
+
+    for (age, column) in columns.iter().enumerate() {
+        let x = plot_x + (width - 1 - age) as u16;
+        for row in 0..rows {
+            // Bands covered by this row, from the top of the plot down.
+            let top = BANDS as f32 * (rows - row) as f32 / rows as f32;
+            let bottom = BANDS as f32 * (rows - row - 1) as f32 / rows as f32;
+            let (first, last) = (bottom as usize, (top.ceil() as usize).clamp(bottom as usize + 1, BANDS));
+            let magnitude = column[first..last].iter().fold(0.0f32, |a, &m| a.max(m));
+            let db = 20.0 * (magnitude / peak).max(1e-9).log10();
+            let (symbol, color) = shade(1.0 - db / FLOOR_DB, state);
+            let cell = buffer.get_mut(x, inner.y + row);
+            cell.set_char(symbol);
+            cell.set_fg(color);
+        }
+    }
+
+    if let Some(row) = state.current_freq.and_then(|freq| row_of(freq, rows)) {
+        let cell = buffer.get_mut(plot_x + width as u16, inner.y + row);
+        cell.set_char('◀');
+        cell.set_fg(state.theme.target);
+    }
+}
@@ -0,0 +1,237 @@
+use super::practice::semitone_name;
+use super::{status_appearance, UiState};
+use crate::coincident::{self, Coincidence};
+use crate::i18n::{tr, trf};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::canvas::{Canvas, Line as CanvasLine},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Widget},
+    Frame,
+};
+
+const CLIP_LEVEL: f32 = 0.99;
+const MIN_FREQ: f64 = 20.0;
+const MAX_FREQ: f64 = 5000.0;
+const FLOOR_DB: f64 = -80.0;
+/// Coincident partials beating slower than this are drawn as in tune, and
+/// faster than `FAST_BEATS` as far off.
+const SLOW_BEATS: f32 = 0.5;
+const FAST_BEATS: f32 = 3.0;
+
+pub(super) fn render(frame: &mut Frame, state: &UiState, area: Rect) {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(10), Constraint::Length(3), Constraint::Length(3), Constraint::Length(8)])
+        .split(area);
+
+    render_spectrum(frame, state, vertical[0]);
+    render_partials(frame, state, vertical[1]);
+    render_coincident(frame, state, vertical[2]);
+    render_waveform(frame, state, vertical[3]);
+}
+
+/// Beat rates between the partials of the sounding note and those of the
+/// target that coincide in their interval, such as 2:1, 4:2, and 6:3 for an
+/// octave: what an aural piano tuner listens to.
+fn render_coincident(frame: &mut Frame, state: &UiState, area: Rect) {
+    let target = format!("{}{}", state.target_note, state.target_octave);
+    let found = state.current_freq.and_then(|freq| coincident::coincidences(freq, &state.partials, state.target_freq()));
+    let title = match &found {
+        Some((semitones, _)) => trf("coincident.title", &[&semitone_name(*semitones), &target]),
+        None => trf("coincident.title_none", &[&target]),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.info))
+        .title(title)
+        .title_alignment(Alignment::Center);
+
+    let muted = Style::default().fg(state.theme.muted);
+    let line = match found {
+        Some((_, pairs)) if !pairs.is_empty() => {
+            let mut spans = Vec::new();
+            for Coincidence { lower, upper, beats } in pairs {
+                if !spans.is_empty() {
+                    spans.push(Span::styled(" │ ", muted));
+                }
+                let color = match beats.abs() {
+                    rate if rate < SLOW_BEATS => state.theme.perfect,
+                    rate if rate < FAST_BEATS => state.theme.close,
+                    _ => state.theme.far,
+                };
+                spans.push(Span::styled(
+                    format!("{}:{} ", lower, upper),
+                    Style::default().fg(state.theme.text).add_modifier(Modifier::BOLD),
+                ));
+                spans.push(Span::styled(trf("coincident.rate", &[&format!("{:+.1}", beats)]), Style::default().fg(color)));
+            }
+            Line::from(spans)
+        }
+        Some(_) => Line::styled(tr("coincident.no_pairs"), muted),
+        None => Line::styled("---", muted),
+    };
+
+    Paragraph::new(line).block(block).alignment(Alignment::Center).render(area, frame.buffer_mut());
+}
+
+fn render_partials(frame: &mut Frame, state: &UiState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.info))
+        .title(tr("spectrum.partials"))
+        .title_alignment(Alignment::Center);
+
+    let mut spans = Vec::new();
+    for partial in &state.partials {
+        if !spans.is_empty() {
+            spans.push(Span::raw(" | "));
+        }
+        let (color, _, _) = status_appearance(state.status_for(Some(partial.deviation_cents)), &state.theme);
+        spans.push(Span::styled(
+            format!("{}: ", partial.number),
+            Style::default().fg(state.theme.text).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(format!("{:.1} Hz ", partial.freq), Style::default().fg(state.theme.highlight)));
+        spans.push(Span::styled(format!("{:+.1}c", partial.deviation_cents), Style::default().fg(color)));
+    }
+    if spans.is_empty() {
+        spans.push(Span::styled("---", Style::default().fg(state.theme.muted)));
+    }
+
+    Paragraph::new(Line::from(spans))
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn render_spectrum(frame: &mut Frame, state: &UiState, area: Rect) {
+    let peak = state.spectrum.iter().fold(0.0f32, |peak, &m| peak.max(m)).max(1e-9);
+
+    let points: Vec<(f64, f64)> = state
+        .spectrum
+        .iter()
+        .enumerate()
+        .filter_map(|(bin, &magnitude)| {
+            let freq = bin as f64 * state.spectrum_bin_hz as f64;
+            if !(MIN_FREQ..=MAX_FREQ).contains(&freq) {
+                return None;
+            }
+            let db = (20.0 * (magnitude / peak).max(1e-9).log10() as f64).max(FLOOR_DB);
+            Some((freq.log10(), db))
+        })
+        .collect();
+
+    let marker: Vec<(f64, f64)> = match state.current_freq {
+        Some(freq) => vec![((freq as f64).log10(), FLOOR_DB), ((freq as f64).log10(), 0.0)],
+        None => Vec::new(),
+    };
+    let partial_markers: Vec<(f64, f64)> = state
+        .partials
+        .iter()
+        .skip(1)
+        .filter_map(|partial| {
+            let bin = (partial.freq / state.spectrum_bin_hz).round() as usize;
+            let magnitude = *state.spectrum.get(bin)?;
+            let db = (20.0 * (magnitude / peak).max(1e-9).log10() as f64).max(FLOOR_DB);
+            Some(((partial.freq as f64).log10(), db))
+        })
+        .collect();
+
+    let datasets = vec![
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(state.theme.perfect))
+            .data(&points),
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(state.theme.highlight))
+            .data(&marker),
+        Dataset::default()
+            .marker(Marker::Dot)
+            .graph_type(GraphType::Scatter)
+            .style(Style::default().fg(state.theme.info))
+            .data(&partial_markers),
+    ];
+
+    let title = match state.current_freq {
+        Some(freq) => trf("spectrum.title_peak", &[&format!("{:.2}", freq)]),
+        None => tr("spectrum.title").to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(title)
+        .title_alignment(Alignment::Center);
+
+    Chart::new(datasets)
+        .block(block)
+        .x_axis(
+            Axis::default()
+                .bounds([MIN_FREQ.log10(), MAX_FREQ.log10()])
+                .labels(vec![
+                    Span::raw("20"),
+                    Span::raw("100"),
+                    Span::raw("1k"),
+                    Span::raw("5k"),
+                ])
+                .style(Style::default().fg(state.theme.muted)),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([FLOOR_DB, 0.0])
+                .labels(vec![Span::raw("-80dB"), Span::raw("-40dB"), Span::raw("0dB")])
+                .style(Style::default().fg(state.theme.muted)),
+        )
+        .render(area, frame.buffer_mut());
+}
+
+pub(super) fn render_waveform(frame: &mut Frame, state: &UiState, area: Rect) {
+    let peak = state.waveform.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    let clipping = peak >= CLIP_LEVEL;
+
+    let (title_color, level_text) = if clipping {
+        (state.theme.warning, tr("waveform.clip").to_string())
+    } else {
+        let db = 20.0 * peak.max(1e-6).log10();
+        (state.theme.accent, trf("waveform.peak", &[&format!("{:.1}", db)]))
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(title_color))
+        .title(trf("waveform.title", &[&level_text]))
+        .title_alignment(Alignment::Center);
+
+    let points = (area.width.saturating_sub(2) as usize * 2).max(1);
+    let step = (state.waveform.len() as f64 / points as f64).max(1.0);
+    let samples: Vec<(f64, f64)> = (0..points)
+        .filter_map(|i| {
+            let idx = (i as f64 * step) as usize;
+            state.waveform.get(idx).map(|&s| (i as f64, s as f64))
+        })
+        .collect();
+
+    let color = if clipping { state.theme.warning } else { state.theme.perfect };
+    Canvas::default()
+        .block(block)
+        .marker(Marker::Braille)
+        .x_bounds([0.0, points as f64])
+        .y_bounds([-1.0, 1.0])
+        .paint(move |ctx| {
+            for pair in samples.windows(2) {
+                ctx.draw(&CanvasLine {
+                    x1: pair[0].0,
+                    y1: pair[0].1,
+                    x2: pair[1].0,
+                    y2: pair[1].1,
+                    color,
+                });
+            }
+        })
+        .render(area, frame.buffer_mut());
+}
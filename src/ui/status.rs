@@ -0,0 +1,68 @@
+use super::UiState;
+use crate::analysis::Mode;
+use crate::i18n::{tr, trf};
+use crate::tuner::Register;
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    Frame,
+};
+
+const MIN_DEVICE_WIDTH: usize = 10;
+
+/// One-line summary of the input device and the analysis loop; the device
+/// name is shortened first when the line does not fit.
+pub(super) fn render(frame: &mut Frame, state: &UiState, area: Rect) {
+    if area.height == 0 {
+        return;
+    }
+    let analysis = &state.analysis;
+    let muted = Style::default().fg(state.theme.muted);
+    let value = Style::default().fg(state.theme.text);
+    let separator = Span::styled(" │ ", muted);
+
+    let mut details = Line::from(vec![
+        Span::styled(format!("{} Hz", analysis.sample_rate), value),
+        separator.clone(),
+        Span::styled(analysis.algorithm, value),
+    ]);
+    let register = match analysis.register {
+        Register::Normal => None,
+        Register::Low => Some(tr("statusbar.low_register")),
+        Register::High => Some(tr("statusbar.high_register")),
+    };
+    if let Some(register) = register {
+        details.spans.push(separator.clone());
+        details.spans.push(Span::styled(register, Style::default().fg(state.theme.info)));
+    }
+    if analysis.mode == Mode::Fast {
+        details.spans.push(separator.clone());
+        details.spans.push(Span::styled(tr("statusbar.fast"), Style::default().fg(state.theme.info)));
+    }
+    details.spans.extend([
+        separator.clone(),
+        Span::styled(trf("statusbar.latency", &[&analysis.latency().as_millis()]), value),
+        separator.clone(),
+        Span::styled(trf("statusbar.rate", &[&analysis.rate]), value),
+        separator.clone(),
+        Span::styled(trf("statusbar.cpu", &[&format!("{:.1}", analysis.cpu_percent)]), value),
+    ]);
+    if analysis.dropped_frames > 0 {
+        details.spans.push(separator);
+        let dropped = trf("statusbar.dropped", &[&analysis.dropped_frames]);
+        details.spans.push(Span::styled(dropped, Style::default().fg(state.theme.warning)));
+    }
+    details.spans.push(Span::raw(" "));
+    let details_width = (details.width() as u16).min(area.width);
+    let details_x = area.right() - details_width;
+
+    let buffer = frame.buffer_mut();
+    buffer.set_style(area, muted);
+    let room = details_x.saturating_sub(area.x + 1) as usize;
+    if room >= MIN_DEVICE_WIDTH {
+        let device = format!(" {} {}", tr("statusbar.input"), analysis.input_device);
+        buffer.set_stringn(area.x, area.y, device, room, value);
+    }
+    buffer.set_line(details_x, area.y, &details, details_width);
+}
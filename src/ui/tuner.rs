@@ -0,0 +1,970 @@
+use super::{
+    centered_span_hits, log, piano, spectrum, status_appearance, Hit, Hits, LayoutSize, UiState, MAX_STEADY_SPREAD,
+};
+use crate::bigtext;
+use crate::i18n::{tr, trf};
+use crate::keymap::Action;
+use crate::metronome;
+use crate::midi;
+use crate::presets;
+use crate::timing::Clock;
+use crate::tuner::Tuner;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Widget},
+    Frame,
+};
+use std::time::Duration;
+
+const METRONOME_FLASH: Duration = Duration::from_millis(100);
+/// Cents either side of the target in continuous mode, and the labelled ticks.
+const CONTINUOUS_RANGE: f32 = 1200.0;
+const CONTINUOUS_LABELS: [f32; 5] = [-1200.0, -600.0, 0.0, 600.0, 1200.0];
+
+pub(super) fn render(frame: &mut Frame, state: &UiState, area: Rect, layout: LayoutSize, hits: &mut Hits) {
+    if layout == LayoutSize::Compact {
+        render_compact(frame, state, area, hits);
+        return;
+    }
+
+    let (indicator_height, waveform_height, history_height) = match layout {
+        LayoutSize::Large => (16, (area.height / 5).max(8), (area.height / 3).max(10)),
+        _ => (10, 8, 10),
+    };
+
+    let mut constraints = vec![Constraint::Min(indicator_height)];
+    if state.show_waveform {
+        constraints.push(Constraint::Length(waveform_height));
+    }
+    if state.show_history {
+        constraints.push(Constraint::Length(history_height));
+    }
+    if state.show_piano {
+        constraints.push(Constraint::Length(6));
+    }
+    constraints.extend([
+        Constraint::Length(5),
+        Constraint::Length(3),
+        Constraint::Length(3),
+    ]);
+
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(area);
+    let mut next = 1;
+
+    if state.pitch_pipe {
+        render_pitch_pipe(frame, state, vertical[0], hits);
+    } else if state.continuous {
+        render_continuous(frame, state, vertical[0]);
+    } else if state.vertical_meter {
+        render_vertical_meter(frame, state, vertical[0]);
+    } else {
+        render_tuning_indicator(frame, state, vertical[0]);
+    }
+    if state.show_waveform {
+        spectrum::render_waveform(frame, state, vertical[next]);
+        next += 1;
+    }
+    if state.show_history {
+        log::render_deviation_history(frame, state, vertical[next], state.meter_range);
+        next += 1;
+    }
+    if state.show_piano {
+        piano::render(frame, state, vertical[next]);
+        next += 1;
+    }
+    render_frequency_display(frame, state, vertical[next]);
+    render_target_note_selector(frame, state, vertical[next + 1], hits);
+    render_controls(frame, state, vertical[next + 2]);
+}
+
+fn render_compact(frame: &mut Frame, state: &UiState, area: Rect, hits: &mut Hits) {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3), Constraint::Length(3)])
+        .split(area);
+
+    if state.pitch_pipe {
+        render_pitch_pipe(frame, state, vertical[0], hits);
+    } else if state.continuous {
+        render_continuous_scale(frame, state, vertical[0]);
+    } else if state.vertical_meter {
+        render_vertical_meter(frame, state, vertical[0]);
+    } else {
+        render_compact_meter(frame, state, vertical[0]);
+    }
+    render_frequency_display(frame, state, vertical[1]);
+    render_target_note_selector(frame, state, vertical[2], hits);
+}
+
+fn render_compact_meter(frame: &mut Frame, state: &UiState, area: Rect) {
+    let (color, symbol, text) = status_appearance(state.get_tuning_status(), &state.theme);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(format!("{} {}", symbol, text))
+        .title_alignment(Alignment::Center);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if inner.width < 3 || inner.height == 0 {
+        return;
+    }
+
+    let y = inner.y + inner.height / 2;
+    draw_meter_line(frame, state, Rect::new(inner.x, y, inner.width, 1));
+
+    if y + 1 < inner.bottom() {
+        render_guidance(frame, state, Rect::new(inner.x, y + 1, inner.width, 1));
+    }
+}
+
+/// A single-row cents meter: the needle as ●, the held peak as ◆.
+fn draw_meter_line(frame: &mut Frame, state: &UiState, row: Rect) {
+    let y = row.y;
+    let center = row.x + row.width / 2;
+    let half_width = (row.width / 2).saturating_sub(1) as f32;
+    let position = |cents: f32| {
+        let normalized = state.meter_position(cents);
+        (center as f32 + normalized * half_width).round() as u16
+    };
+    let marker = state.needle_cents.map(position);
+    let color = state.needle_cents.map_or(state.theme.no_signal, |cents| state.deviation_color(cents));
+    let peak = state.peak_cents.map(position);
+
+    let buffer = frame.buffer_mut();
+    for x in row.x..row.right() {
+        let cell = buffer.get_mut(x, y);
+        if Some(x) == marker {
+            cell.set_char('●');
+            cell.set_fg(color);
+        } else if Some(x) == peak {
+            cell.set_char('◆');
+            cell.set_fg(state.theme.warning);
+        } else if x == center {
+            cell.set_char('┼');
+            cell.set_fg(state.theme.perfect);
+        } else {
+            cell.set_char('─');
+            cell.set_fg(state.theme.muted);
+        }
+    }
+}
+
+pub(super) fn render_big_note(frame: &mut Frame, state: &UiState, area: Rect) {
+    let (mut color, _, status_text) = status_appearance(state.get_tuning_status(), &state.theme);
+    let mut status_text = status_text.to_string();
+
+    let mut note_text = match (state.current_note.as_ref(), state.current_octave) {
+        (Some(note), Some(octave)) => format!("{}{}", note, octave),
+        _ => "--".to_string(),
+    };
+    let mut cents_text = match state.deviation_cents {
+        Some(dev) => format!("{:+.1}c", dev),
+        None => "-".to_string(),
+    };
+
+    if let Some(held) = state.held_reading() {
+        let age = state.display_time().duration_since(held.at).as_secs_f32();
+        color = state.theme.muted;
+        note_text = format!("{}{}", held.note, held.octave);
+        cents_text = format!("{:+.1}c", held.deviation_cents);
+        status_text = trf("bignote.held", &[&format!("{:.1}", age)]);
+    } else if let Some(heard) = state.off_note() {
+        color = state.theme.warning;
+        let target = format!("{}{}", state.target_note, state.target_octave);
+        status_text = trf("offnote.warning", &[&heard, &target]);
+    }
+
+    let footer_height = 2;
+    let widest = bigtext::text_width(&note_text).max(bigtext::text_width(&cents_text)).max(1);
+    let width_scale = area.width as usize / widest;
+    let height_scale = (area.height as usize).saturating_sub(footer_height + 1) / (bigtext::GLYPH_HEIGHT * 2);
+    let scale = width_scale.min(height_scale).max(1);
+
+    let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+    let mut lines: Vec<Line> = bigtext::render_lines(&note_text, scale)
+        .into_iter()
+        .map(|l| Line::styled(l, style))
+        .collect();
+    lines.push(Line::raw(""));
+    lines.extend(
+        bigtext::render_lines(&cents_text, scale)
+            .into_iter()
+            .map(|l| Line::styled(l, style)),
+    );
+    lines.push(Line::raw(""));
+    lines.push(Line::from(vec![
+        Span::styled(status_text, style),
+        Span::raw(format!(" | {} ", tr("bignote.target"))),
+        Span::styled(
+            format!("{}{}", state.target_note, state.target_octave),
+            Style::default().fg(state.theme.highlight),
+        ),
+        Span::raw(" | "),
+        Span::styled(state.keymap.label(Action::BigNote), Style::default().fg(state.theme.highlight)),
+        Span::raw(format!(" {}", tr("bignote.exit"))),
+    ]));
+
+    let top_padding = (area.height as usize).saturating_sub(lines.len()) / 2;
+    let mut padded = vec![Line::raw(""); top_padding];
+    padded.extend(lines);
+
+    Paragraph::new(padded)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+/// Nothing but the note name and a borderless meter, for distraction-free
+/// tuning on stage.
+pub(super) fn render_zen(frame: &mut Frame, state: &UiState, area: Rect) {
+    let (color, _, _) = status_appearance(state.get_tuning_status(), &state.theme);
+    let note_text = match (&state.current_note, state.current_octave) {
+        (Some(note), Some(octave)) => format!("{}{}", note, octave),
+        _ => "-".to_string(),
+    };
+
+    let meter_height = 2;
+    let widest = bigtext::text_width(&note_text).max(1);
+    let width_scale = area.width as usize / widest;
+    let height_scale = (area.height as usize).saturating_sub(meter_height + 1) / bigtext::GLYPH_HEIGHT;
+    let scale = width_scale.min(height_scale).max(1);
+
+    let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+    let lines: Vec<Line> = bigtext::render_lines(&note_text, scale)
+        .into_iter()
+        .map(|l| Line::styled(l, style))
+        .collect();
+
+    let content_height = lines.len() as u16 + 1 + meter_height as u16;
+    let top = area.y + area.height.saturating_sub(content_height) / 2;
+    let note_area = Rect::new(area.x, top, area.width, lines.len() as u16).intersection(area);
+    Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .render(note_area, frame.buffer_mut());
+
+    let meter_y = note_area.bottom() + 1;
+    if meter_y < area.bottom() && area.width > 4 {
+        draw_meter_line(frame, state, Rect::new(area.x + 2, meter_y, area.width - 4, 1));
+    }
+}
+
+/// A tall cents scale with sharp at the top and flat at the bottom, for
+/// narrow terminal splits.
+fn render_vertical_meter(frame: &mut Frame, state: &UiState, area: Rect) {
+    let (color, symbol, text) = status_appearance(state.get_tuning_status(), &state.theme);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(format!("{} {}", symbol, text))
+        .title_alignment(Alignment::Center);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if inner.width < 7 || inner.height < 3 {
+        return;
+    }
+
+    let guidance_row = inner.height >= 7;
+    let rows = inner.height - guidance_row as u16;
+    let half = (rows - 1) / 2;
+    let middle = inner.y + half;
+    let column = inner.x + inner.width / 2;
+    let row_of = |cents: f32| (middle as f32 - state.meter_position(cents) * half as f32).round() as u16;
+    let cents_of = |y: u16| (middle as f32 - y as f32) / half.max(1) as f32 * state.meter_range;
+
+    let label_style = Style::default().fg(state.theme.muted);
+    let labels = [
+        (row_of(state.meter_range), format!("+{}", state.meter_range)),
+        (middle, "0".to_string()),
+        (row_of(-state.meter_range), format!("-{}", state.meter_range)),
+    ];
+
+    let buffer = frame.buffer_mut();
+    for y in inner.y..inner.y + rows {
+        let cell = buffer.get_mut(column, y);
+        if y == middle {
+            cell.set_char('┼');
+            cell.set_fg(state.theme.perfect);
+        } else if cents_of(y).abs() <= state.in_tune_cents {
+            cell.set_char('┃');
+            cell.set_fg(state.theme.perfect);
+        } else {
+            cell.set_char('│');
+            cell.set_fg(state.theme.muted);
+        }
+    }
+    for (y, label) in &labels {
+        let x = column.saturating_sub(label.len() as u16 + 1).max(inner.x);
+        buffer.set_stringn(x, *y, label, (column - x) as usize, label_style);
+    }
+
+    if let Some(peak) = state.peak_cents {
+        let cell = buffer.get_mut(column, row_of(peak));
+        cell.set_char('◆');
+        cell.set_fg(state.theme.warning);
+    }
+
+    if let Some(deviation) = state.needle_cents {
+        let y = row_of(deviation);
+        let color = state.deviation_color(deviation);
+        for x in column.saturating_sub(1)..=(column + 1).min(inner.right() - 1) {
+            let cell = buffer.get_mut(x, y);
+            cell.set_char(if x == column { '●' } else { '━' });
+            cell.set_fg(color);
+        }
+        if column + 3 < inner.right() {
+            let width = (inner.right() - column - 3) as usize;
+            buffer.set_stringn(column + 3, y, format!("{:+.1}", deviation), width, Style::default().fg(color));
+        }
+    }
+
+    if guidance_row {
+        render_guidance(frame, state, Rect::new(inner.x, inner.bottom() - 1, inner.width, 1));
+    }
+}
+
+/// Continuous mode: the reading against the target with no snapping to the
+/// nearest note, on a scale with a tick per semitone, above a trace as wide.
+fn render_continuous(frame: &mut Frame, state: &UiState, area: Rect) {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(5), Constraint::Min(5)])
+        .split(area);
+    render_continuous_scale(frame, state, vertical[0]);
+    log::render_deviation_history(frame, state, vertical[1], CONTINUOUS_RANGE);
+}
+
+fn render_continuous_scale(frame: &mut Frame, state: &UiState, area: Rect) {
+    let target = format!("{}{}", state.target_note, state.target_octave);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(trf("continuous.title", &[&target, &format!("{:.2}", state.target_freq())]))
+        .title_alignment(Alignment::Center);
+    let inner = block.inner(area);
+    block.render(area, frame.buffer_mut());
+    if inner.width < 12 || inner.height < 3 {
+        return;
+    }
+
+    let value = match (state.current_freq, state.deviation_cents) {
+        (Some(freq), Some(cents)) => {
+            let bold = Style::default().fg(state.theme.text).add_modifier(Modifier::BOLD);
+            let mut spans = vec![
+                Span::styled(format!("{:.2} Hz", freq), Style::default().fg(state.theme.highlight)),
+                Span::raw("  "),
+                Span::styled(format_deviation(cents), bold),
+            ];
+            if cents.abs() > CONTINUOUS_RANGE {
+                let arrow = if cents > 0.0 { " ▶" } else { " ◀" };
+                spans.push(Span::styled(arrow, Style::default().fg(state.theme.warning)));
+            }
+            Line::from(spans)
+        }
+        _ => Line::styled("--- Hz", Style::default().fg(state.theme.muted)),
+    };
+    Paragraph::new(value).alignment(Alignment::Center).render(Rect { height: 1, ..inner }, frame.buffer_mut());
+
+    let span = (inner.width - 1) as f32;
+    let column = |cents: f32| {
+        let position = (cents.clamp(-CONTINUOUS_RANGE, CONTINUOUS_RANGE) / CONTINUOUS_RANGE + 1.0) / 2.0;
+        inner.x + (position * span).round() as u16
+    };
+    let buffer = frame.buffer_mut();
+    let (scale_y, label_y) = (inner.y + 1, inner.y + 2);
+    for semitone in -12..=12 {
+        let octave = semitone % 12 == 0;
+        let cell = buffer.get_mut(column(semitone as f32 * 100.0), scale_y);
+        cell.set_char(if octave { '┃' } else { '│' });
+        cell.set_fg(if octave { state.theme.text } else { state.theme.muted });
+    }
+    for cents in CONTINUOUS_LABELS {
+        let label = if cents == 0.0 { "0".to_string() } else { format!("{:+}", cents) };
+        let width = label.len() as u16;
+        let x = column(cents).saturating_sub(width / 2).clamp(inner.x, inner.right() - width);
+        buffer.set_string(x, label_y, label, Style::default().fg(state.theme.muted));
+    }
+    if let Some(cents) = state.needle_cents {
+        let cell = buffer.get_mut(column(cents), scale_y);
+        cell.set_char('█');
+        cell.set_fg(state.theme.highlight);
+    }
+}
+
+fn render_guidance(frame: &mut Frame, state: &UiState, row: Rect) {
+    if let Some(heard) = state.off_note() {
+        let target = format!("{}{}", state.target_note, state.target_octave);
+        let style = Style::default().fg(state.theme.warning).add_modifier(Modifier::BOLD | Modifier::REVERSED);
+        Paragraph::new(Line::styled(format!(" {} ", trf("offnote.warning", &[&heard, &target])), style))
+            .alignment(Alignment::Center)
+            .render(row, frame.buffer_mut());
+        return;
+    }
+    let Some(guidance) = state.guidance() else {
+        return;
+    };
+    let (color, _, _) = status_appearance(state.get_tuning_status(), &state.theme);
+    let arrows = if guidance.flat { "↑" } else { "↓" }.repeat(guidance.arrows);
+
+    Paragraph::new(Line::styled(
+        format!("{} {} {}", arrows, guidance.text, arrows),
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    ))
+    .alignment(Alignment::Center)
+    .render(row, frame.buffer_mut());
+}
+
+fn render_contrast_meter(frame: &mut Frame, state: &UiState, area: Rect) {
+    let (color, symbol, text) = status_appearance(state.get_tuning_status(), &state.theme);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Thick)
+        .border_style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+        .title(trf("indicator.title", &[&state.damping.label(), &state.meter_range]))
+        .title_alignment(Alignment::Center);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if inner.width < 3 || inner.height < 3 {
+        return;
+    }
+
+    let bar_top = inner.y + 1;
+    let bar_height = inner.height.saturating_sub(3).max(1);
+    let center = inner.x + inner.width / 2;
+    let half_width = (inner.width / 2).saturating_sub(1) as f32;
+
+    let buffer = frame.buffer_mut();
+    for y in bar_top..bar_top + bar_height {
+        let cell = buffer.get_mut(center, y);
+        cell.set_char('┃');
+        cell.set_fg(state.theme.text);
+    }
+
+    if let Some(deviation) = state.needle_cents {
+        let normalized = state.meter_position(deviation);
+        let marker = (center as f32 + normalized * half_width).round() as u16;
+        let (start, end) = if marker < center { (marker, center) } else { (center, marker) };
+
+        for y in bar_top..bar_top + bar_height {
+            for x in start..=end {
+                let cell = buffer.get_mut(x, y);
+                cell.set_char(' ');
+                cell.set_bg(color);
+            }
+            for x in marker.saturating_sub(1)..=(marker + 1).min(inner.right() - 1) {
+                let cell = buffer.get_mut(x, y);
+                cell.set_char('█');
+                cell.set_fg(state.theme.text);
+                cell.set_bg(color);
+            }
+        }
+    }
+
+    if let Some(peak) = state.peak_cents {
+        let normalized = state.meter_position(peak);
+        let x = (center as f32 + normalized * half_width).round() as u16;
+        let cell = buffer.get_mut(x.min(inner.right() - 1), inner.y);
+        cell.set_char('▼');
+        cell.set_fg(state.theme.warning);
+    }
+
+    if inner.height >= 4 {
+        render_guidance(frame, state, Rect::new(inner.x, inner.bottom() - 2, inner.width, 1));
+    }
+
+    let text_area = Rect::new(inner.x, inner.bottom() - 1, inner.width, 1);
+    Paragraph::new(Line::styled(
+        format!(" {} {} ", symbol, text),
+        Style::default().fg(color).add_modifier(Modifier::BOLD | Modifier::REVERSED),
+    ))
+    .alignment(Alignment::Center)
+    .render(text_area, frame.buffer_mut());
+}
+
+fn render_tuning_indicator(frame: &mut Frame, state: &UiState, area: Rect) {
+    if state.theme.high_contrast {
+        render_contrast_meter(frame, state, area);
+        return;
+    }
+
+    let status = state.get_tuning_status();
+
+    let (color, symbol, text) = status_appearance(status, &state.theme);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(trf("indicator.title", &[&state.damping.label(), &state.meter_range]))
+        .title_alignment(Alignment::Center);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // A half dial opening upwards from the middle of the box, flat to the left
+    // and sharp to the right. A cell is about twice as tall as it is wide, so
+    // the dial is twice as many cells across as it is high.
+    let center_x = area.x + area.width / 2;
+    let center_y = area.y + area.height / 2;
+    let radius_y = center_y.saturating_sub(inner.y + 1) as f32;
+    let radius_x = (radius_y * 2.0).min((inner.width / 2).saturating_sub(1) as f32);
+    // The cell at `scale` of the radius in the needle's direction for a
+    // meter position from -1 to 1.
+    let point = |normalized: f32, scale: f32| {
+        let angle = std::f32::consts::FRAC_PI_2 * (1.0 - normalized);
+        let x = center_x as f32 + angle.cos() * radius_x * scale;
+        let y = center_y as f32 - angle.sin() * radius_y * scale;
+        (x.round() as u16, y.round() as u16)
+    };
+    let inside = |(x, y): (u16, u16)| x >= inner.x && x < inner.right() && y >= inner.y && y < inner.bottom();
+
+    let buffer = frame.buffer_mut();
+    // Each mark of the arc takes the colour the needle would have there.
+    let marks = ((radius_x as u16) * 3).max(8) / 2 * 2;
+    for i in 0..=marks {
+        let normalized = 2.0 * i as f32 / marks as f32 - 1.0;
+        let (x, y) = point(normalized, 1.0);
+        if inside((x, y)) {
+            let cell = buffer.get_mut(x, y);
+            if i == marks / 2 {
+                cell.set_char('┬');
+                cell.set_fg(state.theme.perfect);
+            } else {
+                cell.set_char('·');
+                cell.set_fg(state.deviation_color(normalized * state.meter_range));
+            }
+        }
+    }
+
+    if let Some(deviation) = state.needle_cents {
+        let normalized = state.meter_position(deviation);
+        let needle_color = state.deviation_color(deviation);
+        let symbol = match normalized {
+            n if n.abs() < 0.25 => '│',
+            n if n.abs() >= 0.75 => '─',
+            n if n > 0.0 => '╱',
+            _ => '╲',
+        };
+        let end = point(normalized, 0.8);
+        let steps = end.0.abs_diff(center_x).max(end.1.abs_diff(center_y)).max(1);
+        for i in 1..=steps {
+            let cell_at = point(normalized, 0.8 * i as f32 / steps as f32);
+            if inside(cell_at) {
+                let cell = buffer.get_mut(cell_at.0, cell_at.1);
+                cell.set_char(symbol);
+                cell.set_fg(needle_color);
+            }
+        }
+        if inside((center_x, center_y)) {
+            let cell = buffer.get_mut(center_x, center_y);
+            cell.set_char('●');
+            cell.set_fg(needle_color);
+        }
+    }
+
+    if let Some(peak) = state.peak_cents {
+        let cell_at = point(state.meter_position(peak), 1.0);
+        if inside(cell_at) {
+            let cell = buffer.get_mut(cell_at.0, cell_at.1);
+            cell.set_char('◆');
+            cell.set_fg(state.theme.warning);
+        }
+    }
+
+    if area.height >= 6 {
+        render_guidance(
+            frame,
+            state,
+            Rect::new(area.x + 2, area.y + area.height - 3, area.width.saturating_sub(4), 1),
+        );
+    }
+
+    let text_area = Rect::new(
+        area.x + 2,
+        area.y + area.height.saturating_sub(2),
+        area.width.saturating_sub(4),
+        1,
+    );
+
+    let mut text_spans = vec![
+        Span::styled(symbol, Style::default().fg(color)),
+        Span::raw(" "),
+        Span::styled(text, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+    ];
+
+    if state.chime_enabled {
+        let chime_style = if state.in_tune_confirmed {
+            Style::default().fg(state.theme.perfect).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(state.theme.muted)
+        };
+        text_spans.push(Span::styled(" ♪", chime_style));
+    }
+
+    let text_line = Line::from(text_spans);
+
+    Paragraph::new(text_line)
+        .alignment(Alignment::Center)
+        .render(text_area, frame.buffer_mut());
+}
+
+fn render_pitch_pipe(frame: &mut Frame, state: &UiState, area: Rect, hits: &mut Hits) {
+    let preset = &presets::all()[state.preset];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.accent))
+        .title(trf("pipe.title", &[&preset.name]))
+        .title_alignment(Alignment::Center);
+
+    let mut note_spans = Vec::new();
+    let mut freq_spans = Vec::new();
+    for (i, &(note, octave)) in preset.strings.iter().enumerate() {
+        let freq = Tuner::note_name_to_frequency(note, octave, state.a4_freq);
+        let selected = i == state.pipe_string;
+        let marker = if selected && state.pipe_playing { "♪" } else { " " };
+
+        let mut style = Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD);
+        if selected {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+
+        note_spans.push(Span::styled(format!(" {}{:<3}{} ", note, octave, marker), style));
+        note_spans.push(Span::raw("  "));
+        freq_spans.push(Span::styled(format!("{:^8.2}", freq), Style::default().fg(state.theme.accent)));
+        freq_spans.push(Span::raw("  "));
+    }
+
+    let inner = block.inner(area);
+    if inner.height > 1 {
+        let targets: Vec<(usize, Hit)> = (0..preset.strings.len()).map(|i| (i * 2, Hit::PipeString(i))).collect();
+        let row = Rect::new(inner.x, inner.y + 1, inner.width, 1);
+        centered_span_hits(row, &note_spans, &targets, hits);
+    }
+
+    let status = if state.pipe_sequence_start.is_some() {
+        tr("pipe.playing_sequence")
+    } else if state.pipe_playing {
+        tr("pipe.playing")
+    } else {
+        tr("pipe.stopped")
+    };
+
+    let lines = vec![
+        Line::raw(""),
+        Line::from(note_spans),
+        Line::from(freq_spans),
+        Line::raw(""),
+        Line::styled(status, Style::default().fg(state.theme.perfect)),
+        Line::from(vec![
+            Span::styled(
+                state.keymap.pair_label(Action::NotePrevious, Action::NoteNext),
+                Style::default().fg(state.theme.highlight),
+            ),
+            Span::raw(format!(" {} | ", tr("pipe.string"))),
+            Span::styled(state.keymap.label(Action::Select), Style::default().fg(state.theme.highlight)),
+            Span::raw(format!(" {} | ", tr("pipe.play_stop"))),
+            Span::styled(state.keymap.label(Action::PipeAll), Style::default().fg(state.theme.highlight)),
+            Span::raw(format!(" {} | ", tr("pipe.play_all"))),
+            Span::styled(state.keymap.label(Action::Instrument), Style::default().fg(state.theme.highlight)),
+            Span::raw(format!(" {}", tr("pipe.instrument"))),
+        ]),
+    ];
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn format_deviation(dev: f32) -> String {
+    if dev.abs() < 0.1 {
+        trf("fmt.cents", &[&"±0.0"])
+    } else {
+        trf("fmt.cents", &[&format!("{:+.1}", dev)])
+    }
+}
+
+/// Beats faster than this are too fast to count by ear, so the rate is only
+/// shown near unison unless beat comparison is on.
+const MAX_BEATS: f32 = 20.0;
+
+/// Beats per second against the target and which side of it the pitch is on.
+fn beat_text(state: &UiState) -> Option<String> {
+    if state.beat_mode && state.output_device.is_none() {
+        return Some(tr("beat.no_output").to_string());
+    }
+    match state.beat_rate {
+        Some(rate) if rate.abs() < 0.05 => Some(tr("beat.unison").to_string()),
+        Some(rate) if rate.abs() <= MAX_BEATS || state.beat_mode => {
+            let key = if rate > 0.0 { "beat.sharp" } else { "beat.flat" };
+            Some(trf(key, &[&format!("{:.2}", rate.abs())]))
+        }
+        _ if state.beat_mode => Some(tr("beat.none").to_string()),
+        _ => None,
+    }
+}
+
+fn render_frequency_display(frame: &mut Frame, state: &UiState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.info))
+        .title(tr("frequency.title"))
+        .title_alignment(Alignment::Center);
+
+    if let Some(held) = state.held_reading() {
+        let age = state.display_time().duration_since(held.at).as_secs_f32();
+        let muted = Style::default().fg(state.theme.muted);
+        let text = Line::from(vec![
+            Span::styled(format!("{:.2} Hz", held.freq), muted),
+            Span::raw(" | "),
+            Span::styled(format!("{}{}", held.note, held.octave), muted.add_modifier(Modifier::BOLD)),
+            Span::raw(" | "),
+            Span::styled(format_deviation(held.deviation_cents), muted),
+            Span::raw(" | "),
+            Span::styled(
+                trf("frequency.held", &[&format!("{:.1}", age)]),
+                muted.add_modifier(Modifier::ITALIC),
+            ),
+        ]);
+
+        Paragraph::new(text)
+            .block(block)
+            .alignment(Alignment::Center)
+            .render(area, frame.buffer_mut());
+        return;
+    }
+
+    let freq_text = if let Some(freq) = state.current_freq {
+        format!("{:.2} Hz", freq)
+    } else {
+        "--- Hz".to_string()
+    };
+
+    let note_text = if let (Some(note), Some(octave)) = (state.current_note.as_ref(), state.current_octave) {
+        format!("{}{}", note, octave)
+    } else {
+        "---".to_string()
+    };
+
+    let deviation_text = match state.deviation_cents {
+        Some(dev) => format_deviation(dev),
+        None => "---".to_string(),
+    };
+
+    let mut spans = vec![Span::styled(freq_text, Style::default().fg(state.theme.highlight)), Span::raw(" | ")];
+    // Continuous mode measures against the target alone, so no nearest note is named.
+    if !state.continuous {
+        spans.push(Span::styled(note_text, Style::default().fg(state.theme.accent).add_modifier(Modifier::BOLD)));
+        spans.push(Span::raw(" | "));
+    }
+    spans.push(Span::styled(deviation_text, Style::default().fg(state.theme.perfect)));
+
+    if let (Some(spread), Some(_)) = (state.stability(), state.current_freq) {
+        let color = if spread <= MAX_STEADY_SPREAD { state.theme.muted } else { state.theme.warning };
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(trf("frequency.spread", &[&format!("{:.1}", spread)]), Style::default().fg(color)));
+    }
+
+    if let Some(beat_text) = beat_text(state) {
+        spans.push(Span::raw(" | "));
+        spans.push(Span::styled(beat_text, Style::default().fg(state.theme.info)));
+    }
+
+    let text = Line::from(spans);
+
+    Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+fn render_target_note_selector(frame: &mut Frame, state: &UiState, area: Rect, hits: &mut Hits) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.target))
+        .title(tr("target.title"))
+        .title_alignment(Alignment::Center);
+
+    let mut spans = vec![
+        Span::styled(format!("{}: ", tr("target.label")), Style::default().fg(state.theme.text)),
+        Span::styled(
+            state.target_note.clone(),
+            Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            state.target_octave.to_string(),
+            Style::default().fg(state.theme.highlight).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" | "),
+        Span::styled("A4: ", Style::default().fg(state.theme.text)),
+        Span::styled(
+            format!("{:.1} Hz", state.a4_freq),
+            Style::default().fg(state.theme.accent),
+        ),
+        Span::raw(" | "),
+        Span::styled(presets::all()[state.preset].name, Style::default().fg(state.theme.text)),
+    ];
+
+    if let Some((source, freq)) = state.references.frequency(state.target_number()) {
+        let (note, octave) = midi::note_name(source);
+        let equal = Tuner::note_name_to_frequency(&state.target_note, state.target_octave, state.a4_freq);
+        let offset = format_deviation(1200.0 * (freq / equal).log2());
+        spans.push(Span::raw(" | "));
+        spans.push(Span::styled(
+            trf("target.reference", &[&format!("{}{}", note, octave), &offset]),
+            Style::default().fg(state.theme.info),
+        ));
+    }
+
+    if let Some(timer) = timer_span(state) {
+        spans.push(Span::raw(" | "));
+        spans.push(timer);
+    }
+
+    if state.auto_locking() {
+        spans.push(Span::raw(" | "));
+        spans.push(if state.lock.is_locked() {
+            Span::styled(tr("lock.locked"), Style::default().fg(state.theme.target).add_modifier(Modifier::BOLD))
+        } else {
+            Span::styled(tr("lock.following"), Style::default().fg(state.theme.muted))
+        });
+    }
+
+    if state.metronome_running {
+        spans.push(Span::raw(" | "));
+        spans.extend(metronome_spans(state));
+    }
+
+    if state.beat_mode || state.metronome_running || state.chime_enabled {
+        spans.push(Span::raw(" | "));
+        spans.push(Span::styled(format!("{}: ", tr("target.output")), Style::default().fg(state.theme.text)));
+        spans.push(Span::styled(
+            state.output_device.clone().unwrap_or_else(|| tr("common.none").to_string()),
+            Style::default().fg(state.theme.info),
+        ));
+    }
+
+    let inner = block.inner(area);
+    if inner.height > 0 {
+        let targets = [(1, Hit::TargetNote), (2, Hit::TargetOctave), (5, Hit::A4)];
+        centered_span_hits(Rect::new(inner.x, inner.y, inner.width, 1), &spans, &targets, hits);
+    }
+
+    let text = Line::from(spans);
+
+    Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
+
+/// Time spent on the current string, or what is left of the countdown, and
+/// the final time once it holds in tune.
+fn timer_span(state: &UiState) -> Option<Span<'static>> {
+    let countdown = Duration::from_secs(state.countdown);
+    let seconds = |duration: Duration| format!("{:.1}", duration.as_secs_f32());
+    match state.string_timer.clock() {
+        Clock::Waiting if state.countdown > 0 => {
+            Some(Span::styled(trf("timing.running", &[&seconds(countdown)]), Style::default().fg(state.theme.info)))
+        }
+        Clock::Waiting => None,
+        Clock::Running(start) => {
+            let elapsed = state.display_time().saturating_duration_since(start);
+            Some(if state.countdown == 0 {
+                Span::styled(trf("timing.running", &[&seconds(elapsed)]), Style::default().fg(state.theme.info))
+            } else if elapsed <= countdown {
+                Span::styled(
+                    trf("timing.running", &[&seconds(countdown - elapsed)]),
+                    Style::default().fg(state.theme.info),
+                )
+            } else {
+                Span::styled(
+                    trf("timing.over", &[&seconds(elapsed - countdown)]),
+                    Style::default().fg(state.theme.warning).add_modifier(Modifier::BOLD),
+                )
+            })
+        }
+        Clock::Done(elapsed) => {
+            let late = state.countdown > 0 && elapsed > countdown;
+            let color = if late { state.theme.warning } else { state.theme.perfect };
+            Some(Span::styled(trf("timing.done", &[&seconds(elapsed)]), Style::default().fg(color)))
+        }
+    }
+}
+
+fn metronome_spans(state: &UiState) -> Vec<Span<'static>> {
+    let (name, accents) = metronome::PATTERNS[state.metronome_pattern];
+    let flashing = state
+        .metronome_flash_at
+        .is_some_and(|at| at.elapsed() < METRONOME_FLASH);
+
+    let mut spans = vec![Span::styled(
+        format!("♩ {} BPM {} ", state.metronome_bpm, name),
+        Style::default().fg(state.theme.text),
+    )];
+
+    for (beat, &accent) in accents.iter().enumerate() {
+        let active = beat == state.metronome_beat;
+        let symbol = match (accent, active) {
+            (true, true) => "◆",
+            (true, false) => "◇",
+            (false, true) => "●",
+            (false, false) => "○",
+        };
+        let mut style = Style::default().fg(if accent { state.theme.warning } else { state.theme.highlight });
+        if active && flashing {
+            style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+        }
+        spans.push(Span::styled(symbol, style));
+    }
+
+    spans
+}
+
+fn render_controls(frame: &mut Frame, state: &UiState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.muted))
+        .title(tr("controls.title"))
+        .title_alignment(Alignment::Center);
+
+    let controls_text = Line::from(vec![
+        Span::styled(
+            state.keymap.pair_label(Action::NotePrevious, Action::NoteNext),
+            Style::default().fg(state.theme.highlight),
+        ),
+        Span::raw(format!(" {} | ", tr("controls.note"))),
+        Span::styled(
+            state.keymap.pair_label(Action::OctaveUp, Action::OctaveDown),
+            Style::default().fg(state.theme.highlight),
+        ),
+        Span::raw(format!(" {} | ", tr("controls.octave"))),
+        Span::styled(
+            state.keymap.pair_label(Action::A4Up, Action::A4Down),
+            Style::default().fg(state.theme.highlight),
+        ),
+        Span::raw(format!(" {} | ", tr("controls.a4"))),
+        Span::styled(state.keymap.label(Action::NextPage), Style::default().fg(state.theme.highlight)),
+        Span::raw(format!(" {} | ", tr("controls.pages"))),
+        Span::styled(state.keymap.label(Action::Pause), Style::default().fg(state.theme.highlight)),
+        Span::raw(format!(" {} | ", tr("controls.pause"))),
+        Span::styled(state.keymap.label(Action::Help), Style::default().fg(state.theme.highlight)),
+        Span::raw(format!(" {} | ", tr("controls.help"))),
+        Span::styled(state.keymap.label(Action::Quit), Style::default().fg(state.theme.warning)),
+        Span::raw(format!(" {}", tr("controls.quit"))),
+    ]);
+
+    Paragraph::new(controls_text)
+        .block(block)
+        .alignment(Alignment::Center)
+        .render(area, frame.buffer_mut());
+}
@@ -0,0 +1,180 @@
+use std::fs;
+use std::path::Path;
+
+const FORMAT_PCM: u16 = 1;
+const FORMAT_FLOAT: u16 = 3;
+const FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// A decoded WAV file, mixed down to mono.
+pub struct Wav {
+    pub sample_rate: u32,
+    pub samples: Vec<f32>,
+}
+
+/// Reads 8, 16, 24, or 32-bit integer PCM and 32-bit float WAV files.
+pub fn read(path: &Path) -> Result<Wav, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse(&bytes).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn parse(bytes: &[u8]) -> Result<Wav, String> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("not a WAV file".to_string());
+    }
+
+    let mut format = None;
+    let mut data = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = u32::from_le_bytes([bytes[pos + 4], bytes[pos + 5], bytes[pos + 6], bytes[pos + 7]]) as usize;
+        let body = &bytes[pos + 8..(pos + 8 + size).min(bytes.len())];
+        match id {
+            b"fmt " => format = Some(Format::parse(body)?),
+            b"data" => data = Some(body),
+            _ => {}
+        }
+        // Chunks are padded to an even length.
+        pos += 8 + size + size % 2;
+    }
+
+    let format = format.ok_or("missing fmt chunk")?;
+    let data = data.ok_or("missing data chunk")?;
+    let frame_bytes = format.channels as usize * format.bits as usize / 8;
+    let samples = data
+        .chunks_exact(frame_bytes)
+        .map(|frame| {
+            let sum: f32 = frame.chunks_exact(format.bits as usize / 8).map(|s| format.decode(s)).sum();
+            sum / format.channels as f32
+        })
+        .collect();
+
+    Ok(Wav {
+        sample_rate: format.sample_rate,
+        samples,
+    })
+}
+
+struct Format {
+    float: bool,
+    channels: u16,
+    sample_rate: u32,
+    bits: u16,
+}
+
+impl Format {
+    fn parse(body: &[u8]) -> Result<Self, String> {
+        if body.len() < 16 {
+            return Err("fmt chunk too short".to_string());
+        }
+        let word = |at: usize| u16::from_le_bytes([body[at], body[at + 1]]);
+        let mut tag = word(0);
+        // The extensible format keeps the real tag at the start of its sub-format GUID.
+        if tag == FORMAT_EXTENSIBLE && body.len() >= 26 {
+            tag = word(24);
+        }
+
+        let format = Format {
+            float: tag == FORMAT_FLOAT,
+            channels: word(2),
+            sample_rate: u32::from_le_bytes([body[4], body[5], body[6], body[7]]),
+            bits: word(14),
+        };
+        let supported = match tag {
+            FORMAT_PCM => matches!(format.bits, 8 | 16 | 24 | 32),
+            FORMAT_FLOAT => format.bits == 32,
+            _ => false,
+        };
+        if !supported {
+            return Err(format!("unsupported format {} with {} bits per sample", tag, format.bits));
+        }
+        if format.channels == 0 || format.sample_rate == 0 {
+            return Err("no channels or sample rate".to_string());
+        }
+        Ok(format)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> f32 {
+        match (self.float, bytes) {
+            (true, &[a, b, c, d]) => f32::from_le_bytes([a, b, c, d]),
+            (false, &[a]) => (a as f32 - 128.0) / 128.0,
+            (false, &[a, b]) => i16::from_le_bytes([a, b]) as f32 / 32768.0,
+            (false, &[a, b, c]) => (i32::from_le_bytes([0, a, b, c]) >> 8) as f32 / 8_388_608.0,
+            (false, &[a, b, c, d]) => i32::from_le_bytes([a, b, c, d]) as f32 / 2_147_483_648.0,
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, FORMAT_FLOAT, FORMAT_PCM};
+
+    /// A canonical WAV file holding `data` as-is.
+    fn encode(tag: u16, channels: u16, sample_rate: u32, bits: u16, data: &[u8]) -> Vec<u8> {
+        let block = channels * bits / 8;
+        let mut bytes = b"RIFF".to_vec();
+        bytes.extend((36 + data.len() as u32).to_le_bytes());
+        bytes.extend(b"WAVEfmt ");
+        bytes.extend(16u32.to_le_bytes());
+        bytes.extend(tag.to_le_bytes());
+        bytes.extend(channels.to_le_bytes());
+        bytes.extend(sample_rate.to_le_bytes());
+        bytes.extend((sample_rate * block as u32).to_le_bytes());
+        bytes.extend(block.to_le_bytes());
+        bytes.extend(bits.to_le_bytes());
+        bytes.extend(b"data");
+        bytes.extend((data.len() as u32).to_le_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    fn error(bytes: &[u8]) -> String {
+        parse(bytes).err().expect("parsed")
+    }
+
+    #[test]
+    fn sixteen_bit_pcm_is_scaled_and_mixed_to_mono() {
+        let data: Vec<u8> = [0i16, 16384, -32768, 16384].iter().flat_map(|s| s.to_le_bytes()).collect();
+        let mono = parse(&encode(FORMAT_PCM, 1, 44_100, 16, &data)).unwrap();
+        assert_eq!(mono.sample_rate, 44_100);
+        assert_eq!(mono.samples, [0.0, 0.5, -1.0, 0.5]);
+
+        let stereo = parse(&encode(FORMAT_PCM, 2, 48_000, 16, &data)).unwrap();
+        assert_eq!(stereo.sample_rate, 48_000);
+        assert_eq!(stereo.samples, [0.25, -0.25]);
+    }
+
+    #[test]
+    fn float_samples_are_read_unchanged() {
+        let samples = [0.25f32, -0.75, 1.0, 0.0];
+        let data: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        assert_eq!(parse(&encode(FORMAT_FLOAT, 1, 22_050, 32, &data)).unwrap().samples, samples);
+    }
+
+    #[test]
+    fn unsupported_formats_are_errors() {
+        let data = [0u8; 8];
+        // 2 is Microsoft ADPCM.
+        assert!(error(&encode(2, 1, 44_100, 4, &data)).contains("unsupported format 2"));
+        assert!(error(&encode(FORMAT_FLOAT, 1, 44_100, 64, &data)).contains("unsupported"));
+        assert!(error(&encode(FORMAT_PCM, 1, 44_100, 12, &data)).contains("unsupported"));
+        assert!(parse(&encode(FORMAT_PCM, 0, 44_100, 16, &data)).is_err());
+        assert!(error(b"RIFF\0\0\0\0AVI LIST").contains("not a WAV file"));
+    }
+
+    #[test]
+    fn truncated_files_are_errors_or_shorter_not_panics() {
+        let data: Vec<u8> = (0..64i16).flat_map(|s| (s * 512).to_le_bytes()).collect();
+        let bytes = encode(FORMAT_PCM, 2, 44_100, 16, &data);
+        for len in 0..bytes.len() {
+            match parse(&bytes[..len]) {
+                Ok(wav) => assert!(wav.samples.len() < 32, "{} bytes read as {} samples", len, wav.samples.len()),
+                Err(e) => assert!(!e.is_empty()),
+            }
+        }
+        assert_eq!(error(&bytes[..16]), "missing fmt chunk");
+        assert_eq!(error(&bytes[..30]), "fmt chunk too short");
+        assert_eq!(error(&bytes[..40]), "missing data chunk");
+    }
+}
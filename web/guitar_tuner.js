@@ -0,0 +1,65 @@
+// JavaScript wrapper for the wasm32 build of the detector:
+//   cargo build --lib --release --target wasm32-unknown-unknown
+//   cp target/wasm32-unknown-unknown/release/guitar_tuner.wasm web/
+//
+//   const tuner = await Tuner.load("guitar_tuner.wasm", audioContext.sampleRate);
+//   if (tuner.feed(float32Samples) && tuner.pitch > 0) console.log(tuner.note(tuner.pitch));
+
+const NOTE_SIZE = 12; // char name[4]; int octave; float cents
+
+export class Tuner {
+  static async load(url, sampleRate) {
+    const { instance } = await WebAssembly.instantiateStreaming(fetch(url), {});
+    return new Tuner(instance.exports, sampleRate);
+  }
+
+  constructor(exports, sampleRate) {
+    this.wasm = exports;
+    this.handle = exports.gt_tuner_new(sampleRate);
+    if (this.handle === 0) throw new Error("invalid sample rate");
+    this.note_ptr = exports.gt_alloc(NOTE_SIZE);
+    this.samples_ptr = 0;
+    this.samples_len = 0;
+  }
+
+  /** Feeds a Float32Array of mono samples; true when a detection ran. */
+  feed(samples) {
+    if (samples.length > this.samples_len) {
+      if (this.samples_ptr) this.wasm.gt_free(this.samples_ptr, this.samples_len * 4);
+      this.samples_len = samples.length;
+      this.samples_ptr = this.wasm.gt_alloc(this.samples_len * 4);
+    }
+    // Views are made after allocating, which may grow and detach the memory buffer.
+    new Float32Array(this.wasm.memory.buffer, this.samples_ptr, samples.length).set(samples);
+    return this.wasm.gt_tuner_feed(this.handle, this.samples_ptr, samples.length) === 1;
+  }
+
+  /** The last detected frequency in Hz, or 0. */
+  get pitch() {
+    return this.wasm.gt_tuner_pitch(this.handle);
+  }
+
+  /** Share of spectral energy on the detected pitch's harmonics, 0-1. */
+  get confidence() {
+    return this.wasm.gt_tuner_confidence(this.handle);
+  }
+
+  /** The nearest note to `freq` as { name, octave, cents }, or null. */
+  note(freq, a4 = 440) {
+    if (this.wasm.gt_tuner_note(this.handle, freq, a4, this.note_ptr) !== 0) return null;
+    const view = new DataView(this.wasm.memory.buffer, this.note_ptr, NOTE_SIZE);
+    const bytes = new Uint8Array(this.wasm.memory.buffer, this.note_ptr, 4);
+    return {
+      name: String.fromCharCode(...bytes.slice(0, bytes.indexOf(0))),
+      octave: view.getInt32(4, true),
+      cents: view.getFloat32(8, true),
+    };
+  }
+
+  free() {
+    if (this.samples_ptr) this.wasm.gt_free(this.samples_ptr, this.samples_len * 4);
+    this.wasm.gt_free(this.note_ptr, NOTE_SIZE);
+    this.wasm.gt_tuner_free(this.handle);
+    this.handle = 0;
+  }
+}